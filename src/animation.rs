@@ -0,0 +1,398 @@
+use super::{quote, Error, GraphMaker, Plot, PythonEnv, Requirements, Style};
+use crate::PYTHON_HEADER;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Generates an animation (GIF or MP4) from a sequence of frames
+///
+/// Each frame consists of the commands of some [GraphMaker]s or of a [Plot]. The frames are
+/// drawn one after another on the same figure (cleared between frames) and grabbed by a writer
+/// from `matplotlib.animation`: the Pillow writer for GIF files or the ffmpeg writer for MP4 files.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{linspace, Animation, Curve, Error, Plot};
+///
+/// fn main() -> Result<(), Error> {
+///     let x = linspace(0.0, 6.3, 31);
+///     let mut animation = Animation::new();
+///     animation.set_fps(5.0).set_dpi(50.0).add_frames(10, |i| {
+///         let y: Vec<_> = x.iter().map(|v| f64::sin(v - 0.3 * (i as f64))).collect();
+///         let mut curve = Curve::new();
+///         curve.draw(&x, &y);
+///         let mut plot = Plot::new();
+///         plot.add(&curve).set_range(0.0, 6.3, -1.1, 1.1);
+///         plot
+///     })?;
+///     animation.save("/tmp/plotpy/doc_tests/doc_animation.gif")
+/// }
+/// ```
+pub struct Animation {
+    frames: Vec<String>,             // the plotting commands of each frame
+    fps: f64,                        // frames per second
+    repeat: bool,                    // loop forever (GIF only)
+    dpi: Option<f64>,                // resolution
+    figure_size: Option<(f64, f64)>, // figure size in inches
+    style: Style,                    // default appearance of all frames
+    requirements: Requirements,      // features used by the frames (checked against the Python environment)
+    runner: Plot,                    // runs python3 and handles the output
+}
+
+impl Animation {
+    /// Allocates a new instance
+    pub fn new() -> Self {
+        Animation {
+            frames: Vec::new(),
+            fps: 10.0,
+            repeat: true,
+            dpi: None,
+            figure_size: None,
+            style: Style::new(),
+            requirements: Requirements::default(),
+            runner: Plot::new(),
+        }
+    }
+
+    /// Adds a frame drawn by the given graph makers (e.g., Curve, Surface, Text)
+    pub fn add_frame(&mut self, graphs: &[&dyn GraphMaker]) -> &mut Self {
+        let mut buffer = String::new();
+        for graph in graphs {
+            buffer.push_str(graph.get_buffer());
+        }
+        self.frames.push(buffer);
+        self
+    }
+
+    /// Adds a frame drawn by a Plot
+    ///
+    /// **Note:** Only the plotting commands are used; i.e., the options for saving the figure
+    /// (e.g., [Plot::set_save_tight()]) and the style are ignored. See [Animation::set_style()].
+    ///
+    /// Returns an error if the Plot does not pass its checks; e.g., [Error::NonFinite] with the
+    /// [crate::NonFinite::Reject] policy (see [Plot::set_non_finite()]).
+    pub fn add_frame_plot(&mut self, plot: &Plot) -> Result<&mut Self, Error> {
+        plot.check_commands()?;
+        self.frames.push(plot.get_commands());
+        self.requirements.merge(&plot.get_requirements());
+        Ok(self)
+    }
+
+    /// Adds frames drawn by the Plots returned by a function
+    ///
+    /// # Input
+    ///
+    /// * `count` -- the number of frames
+    /// * `frame` -- a function that receives the index of the frame and returns the Plot
+    ///
+    /// Returns the error of the first Plot that does not pass its checks (see [Animation::add_frame_plot()]).
+    pub fn add_frames<F>(&mut self, count: usize, mut frame: F) -> Result<&mut Self, Error>
+    where
+        F: FnMut(usize) -> Plot,
+    {
+        for index in 0..count {
+            let plot = frame(index);
+            self.add_frame_plot(&plot)?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the number of frames per second (default = 10)
+    pub fn set_fps(&mut self, fps: f64) -> &mut Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Sets whether the animation repeats forever or plays only once (default = true; GIF only)
+    pub fn set_loop(&mut self, flag: bool) -> &mut Self {
+        self.repeat = flag;
+        self
+    }
+
+    /// Sets the resolution in dots per inch (default = the figure's dpi)
+    pub fn set_dpi(&mut self, dpi: f64) -> &mut Self {
+        self.dpi = Some(dpi);
+        self
+    }
+
+    /// Sets the figure size in inches
+    ///
+    /// **Note:** The frames must not change the figure size; otherwise, the writer may fail.
+    pub fn set_figure_size_inches(&mut self, width: f64, height: f64) -> &mut Self {
+        self.figure_size = Some((width, height));
+        self
+    }
+
+    /// Sets the style (default appearance) of all frames
+    pub fn set_style(&mut self, style: &Style) -> &mut Self {
+        self.style = style.clone();
+        self
+    }
+
+    /// Sets the python3 executable command (default = `python3`)
+    pub fn set_python_exe(&mut self, python_exe: &str) -> &mut Self {
+        self.runner.set_python_exe(python_exe);
+        self
+    }
+
+    /// Sets the option to show python errors on the console (default = false)
+    pub fn set_show_errors(&mut self, option: bool) -> &mut Self {
+        self.runner.set_show_errors(option);
+        self
+    }
+
+    /// Sets a wall-clock time limit for Python (see [Plot::set_timeout()])
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.runner.set_timeout(timeout);
+        self
+    }
+
+    /// Sets the Python environment to check the features used by the animation (see [Plot::set_python_env()])
+    ///
+    /// For instance, the GIF format requires the Pillow package.
+    pub fn set_python_env(&mut self, env: PythonEnv) -> &mut Self {
+        self.runner.set_python_env(env);
+        self
+    }
+
+    /// Returns the warnings (or other output) issued by Python during the last successful run
+    pub fn get_diagnostics(&self) -> Vec<String> {
+        self.runner.get_diagnostics()
+    }
+
+    /// Calls Python and saves the python script and the animation
+    ///
+    /// # Input
+    ///
+    /// * `path` -- may be a String, &str, or Path; the extension must be `gif` (Pillow writer)
+    ///   or `mp4` (ffmpeg writer)
+    ///
+    /// # Notes
+    ///
+    /// 1. The python script (and log file) is written next to the animation as in [Plot::save()]
+    /// 2. The MP4 format requires the `ffmpeg` program
+    pub fn save<S>(&self, path: &S) -> Result<(), Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let commands = self.generate_commands(Path::new(path))?;
+        self.runner
+            .check_requirements(&self.get_requirements(Path::new(path)))?;
+        self.runner.run_commands(&commands, Path::new(path))
+    }
+
+    /// Returns the features used by the animation saved to the given path
+    fn get_requirements(&self, path: &Path) -> Requirements {
+        let mut requirements = self.requirements;
+        requirements.usetex |= self.style.get_usetex();
+        requirements.pillow = path.extension().unwrap_or_default().eq_ignore_ascii_case("gif");
+        requirements
+    }
+
+    /// Generates the complete Python script
+    fn generate_commands(&self, path: &Path) -> Result<String, Error> {
+        if self.frames.is_empty() {
+            return Err(Error::InvalidInput("the animation must have at least one frame"));
+        }
+        let mut buffer =
+            String::from("#### >>>> file generated by plotpy <<<< ####\n\nimport matplotlib\nmatplotlib.use('Agg')\n");
+        buffer.push_str(PYTHON_HEADER);
+        buffer.push_str("import matplotlib.animation as ani\n");
+        buffer.push_str(&self.style.get_commands());
+        let extension = path.extension().unwrap_or_default().to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "gif" => write!(&mut buffer, "writer=ani.PillowWriter(fps={})\n", self.fps).unwrap(),
+            "mp4" => write!(
+                &mut buffer,
+                "if not ani.FFMpegWriter.isAvailable(): raise RuntimeError('ffmpeg is not available')\n\
+                 writer=ani.FFMpegWriter(fps={})\n",
+                self.fps
+            )
+            .unwrap(),
+            _ => return Err(Error::InvalidInput("the animation format must be gif or mp4")),
+        }
+        match self.figure_size {
+            Some((width, height)) => write!(&mut buffer, "fig=plt.figure(figsize=({},{}))\n", width, height).unwrap(),
+            None => buffer.push_str("fig=plt.figure()\n"),
+        }
+        let dpi = match self.dpi {
+            Some(dpi) => format!("{}", dpi),
+            None => "fig.dpi".to_string(),
+        };
+        write!(
+            &mut buffer,
+            "fn={}\nwriter.setup(fig,fn,dpi={})\n",
+            quote(&path.to_string_lossy()),
+            dpi
+        )
+        .unwrap();
+        for (index, frame) in self.frames.iter().enumerate() {
+            write!(
+                &mut buffer,
+                "\n########## frame {} ##########\n\
+                 fig.clf()\nAXES.clear()\nEXTRA_ARTISTS.clear()\nTHREE_D.clear()\nTHREE_D_ACTIVE=(1,1,1)\n\
+                 {}{}\nwriter.grab_frame()\n",
+                index,
+                frame,
+                self.style.get_presave_commands()
+            )
+            .unwrap();
+        }
+        buffer.push_str("\nwriter.finish()\n");
+        if extension == "gif" && !self.repeat {
+            // the Pillow writer always loops forever; thus, the GIF is saved again without the loop extension
+            write!(
+                &mut buffer,
+                "from PIL import Image, ImageSequence\n\
+                 with Image.open(fn) as gif:\n\
+                 \x20\x20\x20\x20frames=[frame.copy() for frame in ImageSequence.Iterator(gif)]\n\
+                 for frame in frames: frame.info.pop('loop',None)\n\
+                 frames[0].save(fn,save_all=True,append_images=frames[1:],duration=int(1000/{}))\n",
+                self.fps
+            )
+            .unwrap();
+        }
+        Ok(buffer)
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Animation;
+    use crate::{Curve, GraphMaker, NonFinite, Plot, PythonEnv, Style};
+    use std::path::Path;
+
+    #[test]
+    fn new_works() {
+        let animation = Animation::new();
+        assert_eq!(animation.frames.len(), 0);
+        assert_eq!(animation.fps, 10.0);
+        assert_eq!(animation.repeat, true);
+        assert_eq!(animation.dpi, None);
+        assert_eq!(animation.figure_size, None);
+        let animation = Animation::default();
+        assert_eq!(animation.frames.len(), 0);
+    }
+
+    #[test]
+    fn add_frame_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+        let mut plot = Plot::new();
+        plot.set_title("frame 1");
+        let mut animation = Animation::new();
+        animation
+            .add_frame(&[&curve, &curve])
+            .add_frame_plot(&plot)
+            .unwrap()
+            .add_frames(2, |i| {
+                let mut plot = Plot::new();
+                plot.set_title(&format!("frame {}", i + 2));
+                plot
+            })
+            .unwrap();
+        assert_eq!(animation.frames.len(), 4);
+        assert_eq!(animation.frames[0], curve.get_buffer().repeat(2));
+        assert!(animation.frames[1].contains("frame 1"));
+        assert!(animation.frames[3].contains("frame 3"));
+    }
+
+    #[test]
+    fn generate_commands_works() {
+        let mut animation = Animation::new();
+        let path = Path::new("/tmp/plotpy/unit_tests/animation.gif");
+        assert_eq!(
+            animation.generate_commands(path).err().unwrap().to_string(),
+            "the animation must have at least one frame"
+        );
+        animation.add_frame(&[]).add_frame(&[]);
+        let commands = animation.generate_commands(path).unwrap();
+        assert!(commands.contains("writer=ani.PillowWriter(fps=10)\nfig=plt.figure()\n"));
+        assert!(commands.contains("writer.setup(fig,fn,dpi=fig.dpi)\n"));
+        assert_eq!(commands.matches("writer.grab_frame()").count(), 2);
+        assert!(commands.ends_with("writer.grab_frame()\n\nwriter.finish()\n"));
+
+        animation
+            .set_fps(24.0)
+            .set_loop(false)
+            .set_dpi(100.0)
+            .set_figure_size_inches(4.0, 3.0)
+            .set_style(Style::new().set_line_width(3.0));
+        let commands = animation.generate_commands(path).unwrap();
+        assert!(commands
+            .contains("import matplotlib.animation as ani\nplt.rcParams.update({\n    r'lines.linewidth':3,\n})\n"));
+        assert!(commands.contains("writer=ani.PillowWriter(fps=24)\nfig=plt.figure(figsize=(4,3))\n"));
+        assert!(commands.contains("writer.setup(fig,fn,dpi=100)\n"));
+        assert!(!commands.contains("_frames"));
+        assert!(commands.contains("frame.info.pop('loop',None)\n"));
+        assert!(commands.ends_with("duration=int(1000/24))\n"));
+
+        let commands = animation.generate_commands(Path::new("animation.mp4")).unwrap();
+        assert!(commands.contains("writer=ani.FFMpegWriter(fps=24)\n"));
+        assert!(commands.ends_with("\nwriter.finish()\n"));
+        assert_eq!(
+            animation
+                .generate_commands(Path::new("animation.png"))
+                .err()
+                .unwrap()
+                .to_string(),
+            "the animation format must be gif or mp4"
+        );
+    }
+
+    #[test]
+    fn set_python_env_works() {
+        let mut env = PythonEnv::probe("python3").unwrap();
+        env.numpy_version = Some("1.26.0".to_string());
+        env.matplotlib_version = Some("3.8.0".to_string());
+        env.has_pillow = false;
+        let mut plot = Plot::new();
+        plot.set_subplot_3d(1, 1, 1).set_equal_axes(true);
+        let mut animation = Animation::new();
+        animation.set_python_env(env).add_frame_plot(&plot).unwrap();
+        let err = animation
+            .save("/tmp/plotpy/unit_tests/animation_env.gif")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "GIF animation is not supported: it requires pillow");
+        let requirements = animation.get_requirements(Path::new("animation.mp4"));
+        assert_eq!(requirements.pillow, false);
+        assert_eq!(requirements.three_d && requirements.equal_axes, true);
+    }
+
+    #[test]
+    fn add_frame_plot_captures_errors() {
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 1.0], &[0.0, f64::INFINITY]);
+        let mut plot = Plot::new();
+        plot.add(&curve).set_non_finite(NonFinite::Reject);
+        let mut animation = Animation::new();
+        assert_eq!(
+            animation.add_frame_plot(&plot).err().unwrap().to_string(),
+            "the data array y has non-finite values (NaN or ±inf)"
+        );
+        assert_eq!(
+            animation
+                .add_frames(3, |_| {
+                    let mut plot = Plot::new();
+                    plot.set_non_finite(NonFinite::Reject).set_ymax(f64::NAN);
+                    plot
+                })
+                .err()
+                .unwrap()
+                .to_string(),
+            "the axes limits given to set_ymax are not finite (NaN or ±inf)"
+        );
+        assert_eq!(animation.frames.len(), 0);
+    }
+}
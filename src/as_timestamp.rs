@@ -0,0 +1,157 @@
+use super::quote;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Defines a trait to handle timestamps given to the date/time functions (e.g., [crate::Curve::draw_dates()])
+///
+/// The timestamps are written into the Python script as the number of seconds since the Unix epoch
+/// (1970-01-01T00:00:00Z) or as ISO-8601 strings; e.g., `2024-03-15`, `2024-03-15T10:30:00`, or
+/// `2024-03-15T10:30:00+01:00`. The script converts them into dates in the time zone given by
+/// [crate::Plot::set_time_zone()] (UTC by default). ISO-8601 strings without offset are assumed to be
+/// in this time zone.
+///
+/// With the `chrono` feature, this trait is also implemented for `chrono::DateTime`, `chrono::NaiveDateTime`,
+/// and `chrono::NaiveDate`.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::AsTimestamp;
+///
+/// assert_eq!(1710498600.0.timestamp_literal(), "1710498600");
+/// assert_eq!("2024-03-15T10:30:00".timestamp_literal(), "r'2024-03-15T10:30:00'");
+/// ```
+pub trait AsTimestamp {
+    /// Returns the Python literal of the timestamp (the seconds since the Unix epoch or a string)
+    fn timestamp_literal(&self) -> String;
+}
+
+/// Defines the seconds since the Unix epoch
+impl AsTimestamp for f64 {
+    fn timestamp_literal(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// Defines the seconds since the Unix epoch
+impl AsTimestamp for i64 {
+    fn timestamp_literal(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// Defines the seconds since the Unix epoch
+impl AsTimestamp for i32 {
+    fn timestamp_literal(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// Defines the seconds since the Unix epoch
+impl AsTimestamp for u32 {
+    fn timestamp_literal(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// Defines the seconds since the Unix epoch
+impl AsTimestamp for u64 {
+    fn timestamp_literal(&self) -> String {
+        format!("{}", self)
+    }
+}
+
+/// Defines an ISO-8601 string
+impl AsTimestamp for &str {
+    fn timestamp_literal(&self) -> String {
+        quote(self)
+    }
+}
+
+/// Defines an ISO-8601 string
+impl AsTimestamp for String {
+    fn timestamp_literal(&self) -> String {
+        quote(self)
+    }
+}
+
+/// Defines a system time (converted to the seconds since the Unix epoch)
+impl AsTimestamp for SystemTime {
+    fn timestamp_literal(&self) -> String {
+        let seconds = match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs_f64(),
+            Err(e) => -e.duration().as_secs_f64(),
+        };
+        format!("{}", seconds)
+    }
+}
+
+/// Defines a date and time with time zone (requires the `chrono` feature)
+#[cfg(feature = "chrono")]
+impl<Tz> AsTimestamp for chrono::DateTime<Tz>
+where
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn timestamp_literal(&self) -> String {
+        quote(&self.to_rfc3339_opts(chrono::SecondsFormat::Micros, false))
+    }
+}
+
+/// Defines a date and time without time zone (requires the `chrono` feature)
+#[cfg(feature = "chrono")]
+impl AsTimestamp for chrono::NaiveDateTime {
+    fn timestamp_literal(&self) -> String {
+        quote(&self.format("%Y-%m-%dT%H:%M:%S%.6f").to_string())
+    }
+}
+
+/// Defines a date (requires the `chrono` feature)
+#[cfg(feature = "chrono")]
+impl AsTimestamp for chrono::NaiveDate {
+    fn timestamp_literal(&self) -> String {
+        quote(&self.format("%Y-%m-%d").to_string())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::AsTimestamp;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn timestamp_literal_works() {
+        assert_eq!(1710498600.5.timestamp_literal(), "1710498600.5");
+        assert_eq!((-86400_i64).timestamp_literal(), "-86400");
+        assert_eq!(1710498600_u64.timestamp_literal(), "1710498600");
+        assert_eq!(86400.timestamp_literal(), "86400");
+        assert_eq!(86400_u32.timestamp_literal(), "86400");
+        assert_eq!("2024-03-15".timestamp_literal(), "r'2024-03-15'");
+        assert_eq!(
+            String::from("2024-03-15T10:30:00Z").timestamp_literal(),
+            "r'2024-03-15T10:30:00Z'"
+        );
+        let time = UNIX_EPOCH + Duration::from_millis(1500);
+        assert_eq!(time.timestamp_literal(), "1.5");
+        let time = UNIX_EPOCH - Duration::from_secs(60);
+        assert_eq!(time.timestamp_literal(), "-60");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn timestamp_literal_works_with_chrono() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        assert_eq!(date.timestamp_literal(), "r'2024-03-15'");
+        let naive = date.and_hms_milli_opt(10, 30, 0, 250).unwrap();
+        assert_eq!(naive.timestamp_literal(), "r'2024-03-15T10:30:00.250000'");
+        let utc = Utc.from_utc_datetime(&naive);
+        assert_eq!(utc.timestamp_literal(), "r'2024-03-15T10:30:00.250000+00:00'");
+        let offset = FixedOffset::east_opt(3600)
+            .unwrap()
+            .from_local_datetime(&naive)
+            .unwrap();
+        assert_eq!(offset.timestamp_literal(), "r'2024-03-15T10:30:00.250000+01:00'");
+    }
+}
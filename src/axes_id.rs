@@ -0,0 +1,40 @@
+/// Holds a handle to an axes (subplot) created by [crate::Plot::subplots()]
+///
+/// The handle is given to [crate::Plot::set_axes()] or [crate::Plot::add_to()] to select the
+/// axes receiving the next commands; thus, the axes may be configured in any order. In the
+/// Python script, the axes is `AXES[index]` (see [crate::PYTHON_HEADER]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AxesId {
+    index: usize, // index in the AXES list of the python script
+}
+
+impl AxesId {
+    /// Allocates a new instance
+    pub(crate) fn new(index: usize) -> Self {
+        AxesId { index }
+    }
+
+    /// Returns the index of the axes in the `AXES` list of the python script
+    ///
+    /// The axes are numbered in the order of creation; e.g., row by row for [crate::Plot::subplots()].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::AxesId;
+
+    #[test]
+    fn new_and_derive_work() {
+        let a = AxesId::new(3);
+        let b = a;
+        assert_eq!(a.index(), 3);
+        assert_eq!(a, b);
+        assert!(AxesId::new(1) < a);
+        assert_eq!(format!("{:?}", a), "AxesId { index: 3 }");
+    }
+}
@@ -0,0 +1,153 @@
+use super::StrError;
+
+/// Defines the conversion between the values of an axis and a secondary axis
+///
+/// See [crate::Plot::set_secondary_x_axis()] and [crate::Plot::set_secondary_y_axis()].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisTransform {
+    /// Shows `k / x` on the secondary axis; e.g., the frequency `c / λ` of a wavelength `λ`
+    ///
+    /// The constant `k` must not be zero. The values near zero are shown as infinity.
+    Reciprocal(f64),
+
+    /// Shows the angle in radians on the secondary axis (the primary axis is in degrees)
+    DegreesToRadians,
+
+    /// Shows the angle in degrees on the secondary axis (the primary axis is in radians)
+    RadiansToDegrees,
+
+    /// Shows the temperature in kelvin on the secondary axis (the primary axis is in degrees Celsius)
+    CelsiusToKelvin,
+
+    /// Shows the temperature in degrees Celsius on the secondary axis (the primary axis is in kelvin)
+    KelvinToCelsius,
+
+    /// Shows `scale * x + offset` on the secondary axis (the scale must not be zero)
+    Linear {
+        /// The scale factor
+        scale: f64,
+        /// The offset
+        offset: f64,
+    },
+}
+
+impl AxisTransform {
+    /// Returns the forward and inverse functions (Python lambdas) required by `secondary_xaxis` and `secondary_yaxis`
+    pub(crate) fn functions(&self) -> Result<(String, String), StrError> {
+        let res = match self {
+            AxisTransform::Reciprocal(k) => {
+                if *k == 0.0 || !k.is_finite() {
+                    return Err("the constant of the reciprocal transform must be finite and not zero");
+                }
+                let f = format!(
+                    "lambda v:np.divide({},v,out=np.full(np.shape(v),np.inf),where=np.asarray(v)!=0)",
+                    k
+                );
+                (f.clone(), f)
+            }
+            AxisTransform::DegreesToRadians => ("np.deg2rad".to_string(), "np.rad2deg".to_string()),
+            AxisTransform::RadiansToDegrees => ("np.rad2deg".to_string(), "np.deg2rad".to_string()),
+            AxisTransform::CelsiusToKelvin => ("lambda v:v+273.15".to_string(), "lambda v:v-273.15".to_string()),
+            AxisTransform::KelvinToCelsius => ("lambda v:v-273.15".to_string(), "lambda v:v+273.15".to_string()),
+            AxisTransform::Linear { scale, offset } => {
+                if *scale == 0.0 || !scale.is_finite() {
+                    return Err("the scale of the linear transform must be finite and not zero");
+                }
+                if !offset.is_finite() {
+                    return Err("the offset of the linear transform must be finite");
+                }
+                (
+                    format!("lambda v:{}*v+({})", scale, offset),
+                    format!("lambda v:(v-({}))/{}", offset, scale),
+                )
+            }
+        };
+        Ok(res)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::AxisTransform;
+
+    #[test]
+    fn derive_works() {
+        let transform = AxisTransform::Linear {
+            scale: 2.0,
+            offset: 1.0,
+        };
+        let cloned = transform.clone();
+        assert_eq!(transform, cloned);
+        assert_eq!(format!("{:?}", transform), "Linear { scale: 2.0, offset: 1.0 }");
+    }
+
+    #[test]
+    fn functions_work() {
+        assert_eq!(
+            AxisTransform::Reciprocal(299792458.0).functions().unwrap().0,
+            "lambda v:np.divide(299792458,v,out=np.full(np.shape(v),np.inf),where=np.asarray(v)!=0)"
+        );
+        assert_eq!(
+            AxisTransform::DegreesToRadians.functions().unwrap(),
+            ("np.deg2rad".to_string(), "np.rad2deg".to_string())
+        );
+        assert_eq!(
+            AxisTransform::RadiansToDegrees.functions().unwrap(),
+            ("np.rad2deg".to_string(), "np.deg2rad".to_string())
+        );
+        assert_eq!(
+            AxisTransform::CelsiusToKelvin.functions().unwrap(),
+            ("lambda v:v+273.15".to_string(), "lambda v:v-273.15".to_string())
+        );
+        assert_eq!(
+            AxisTransform::KelvinToCelsius.functions().unwrap(),
+            ("lambda v:v-273.15".to_string(), "lambda v:v+273.15".to_string())
+        );
+        assert_eq!(
+            AxisTransform::Linear {
+                scale: 1.8,
+                offset: 32.0
+            }
+            .functions()
+            .unwrap(),
+            ("lambda v:1.8*v+(32)".to_string(), "lambda v:(v-(32))/1.8".to_string())
+        );
+    }
+
+    #[test]
+    fn functions_capture_errors() {
+        assert_eq!(
+            AxisTransform::Reciprocal(0.0).functions().err(),
+            Some("the constant of the reciprocal transform must be finite and not zero")
+        );
+        assert_eq!(
+            AxisTransform::Linear {
+                scale: 0.0,
+                offset: 1.0
+            }
+            .functions()
+            .err(),
+            Some("the scale of the linear transform must be finite and not zero")
+        );
+        assert_eq!(
+            AxisTransform::Linear {
+                scale: 1.0,
+                offset: f64::NAN
+            }
+            .functions()
+            .err(),
+            Some("the offset of the linear transform must be finite")
+        );
+        assert_eq!(
+            AxisTransform::Linear {
+                scale: 1.0,
+                offset: f64::INFINITY
+            }
+            .functions()
+            .err(),
+            Some("the offset of the linear transform must be finite")
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use super::{generate_list_quoted, vector_to_array, AsVector, GraphMaker};
-use num_traits::Num;
+use super::{
+    generate_list_quoted, quote, timestamps_to_dates, vector_to_array, AsTimestamp, AsVector, DataArrays, GraphMaker,
+};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Generates a Barplot plot
@@ -11,10 +13,10 @@ use std::fmt::Write;
 /// ## Basic bar plot
 ///
 /// ```
-/// use plotpy::{Barplot, Plot, StrError};
+/// use plotpy::{Barplot, Error, Plot};
 /// use std::collections::HashMap;
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data
 ///     let x = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 ///     let y = [5, 4, 3, 2, 1, 0, 1, 2, 3, 4];
@@ -38,10 +40,10 @@ use std::fmt::Write;
 /// The code below implements the [Bar Label Demo from Matplotlib documentation](https://matplotlib.org/stable/gallery/lines_bars_and_markers/bar_label_demo.html#sphx-glr-gallery-lines-bars-and-markers-bar-label-demo-py)
 ///
 /// ```
-/// use plotpy::{Barplot, Plot, StrError};
+/// use plotpy::{Barplot, Error, Plot};
 /// use std::collections::HashMap;
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data
 ///     let species = ["Adelie", "Chinstrap", "Gentoo"];
 ///     let sex_counts = HashMap::from([
@@ -79,9 +81,9 @@ use std::fmt::Write;
 /// ## Horizontal bars
 ///
 /// ```
-/// use plotpy::{Barplot, Plot, StrError};
+/// use plotpy::{Barplot, Error, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data
 ///     let fruits = ["Apple", "Banana", "Orange"];
 ///     let prices = [10.0, 20.0, 30.0];
@@ -120,6 +122,7 @@ pub struct Barplot {
     errors: Vec<f64>,          // Shows error icons on bars
     extra: String,             // Extra commands (comma separated)
     buffer: String,            // buffer
+    arrays: DataArrays,        // data arrays written to the buffer
 }
 
 impl Barplot {
@@ -135,6 +138,7 @@ impl Barplot {
             errors: Vec::new(),
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -142,47 +146,57 @@ impl Barplot {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        let opt = self.options();
-        if self.colors.len() > 0 {
-            generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
-        }
-        if self.bottom.len() > 0 {
-            vector_to_array(&mut self.buffer, "bottom", &self.bottom);
-        }
-        if self.errors.len() > 0 {
-            vector_to_array(&mut self.buffer, "err", &self.errors);
-        }
-        if self.horizontal {
-            write!(&mut self.buffer, "p=plt.barh(x,y{})\n", &opt).unwrap();
-        } else {
-            write!(&mut self.buffer, "p=plt.bar(x,y{})\n", &opt).unwrap();
-        }
-        if let Some(t) = &self.with_text {
-            write!(&mut self.buffer, "plt.gca().bar_label(p,label_type='{}')\n", t).unwrap();
-        }
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        self.draw_bars();
     }
 
     /// Draws the bar plot with strings
     pub fn draw_with_str<'a, T, U>(&mut self, x: &[&str], y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
         generate_list_quoted(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        self.draw_bars();
+    }
+
+    /// Draws the bar plot with dates
+    ///
+    /// The dates are along the x-axis (or the y-axis if horizontal) with the `AutoDateLocator` and
+    /// `ConciseDateFormatter`. See [AsTimestamp] and [crate::Plot::set_time_zone()].
+    ///
+    /// **Note:** The width of the bars is given in days (see [Barplot::set_width()]).
+    pub fn draw_dates<'a, S, T, U>(&mut self, dates: &[S], y: &'a T)
+    where
+        S: AsTimestamp,
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
+    {
+        timestamps_to_dates(&mut self.buffer, "x", dates);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        self.draw_bars();
+        if self.horizontal {
+            self.buffer.push_str("set_date_axis(plt.gca().yaxis)\n");
+        } else {
+            self.buffer.push_str("set_date_axis(plt.gca().xaxis)\n");
+        }
+    }
+
+    /// Writes the commands to draw the bars given x and y
+    fn draw_bars(&mut self) {
         let opt = self.options();
         if self.colors.len() > 0 {
             generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
         }
         if self.bottom.len() > 0 {
-            vector_to_array(&mut self.buffer, "bottom", &self.bottom);
+            vector_to_array(&mut self.buffer, &mut self.arrays, "bottom", &self.bottom);
         }
         if self.errors.len() > 0 {
-            vector_to_array(&mut self.buffer, "err", &self.errors);
+            vector_to_array(&mut self.buffer, &mut self.arrays, "err", &self.errors);
         }
         if self.horizontal {
             write!(&mut self.buffer, "p=plt.barh(x,y{})\n", &opt).unwrap();
@@ -264,7 +278,7 @@ impl Barplot {
     fn options(&self) -> String {
         let mut opt = String::new();
         if self.label != "" {
-            write!(&mut opt, ",label=r'{}'", self.label).unwrap();
+            write!(&mut opt, ",label={}", quote(&self.label)).unwrap();
         }
         if self.colors.len() > 0 {
             write!(&mut opt, ",color=colors").unwrap();
@@ -293,8 +307,12 @@ impl GraphMaker for Barplot {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -344,7 +362,7 @@ mod tests {
             .draw(&xx, &yy);
         let b: &str = "x=np.array([0,1,2,3,4,5,6,7,8,9,])\n\
                        y=np.array([5,4,3,2,1,0,1,2,3,4,])\n\
-                       colors=['red','green',]\n\
+                       colors=[r'red',r'green',]\n\
                        bottom=np.array([1,2,3,])\n\
                        p=plt.bar(x,y\
                        ,label=r'LABEL'\
@@ -359,13 +377,29 @@ mod tests {
         assert_eq!(bar.buffer, "");
     }
 
+    #[test]
+    fn draw_dates_works() {
+        let mut bar = Barplot::new();
+        bar.draw_dates(&[1710460800.0, 1710547200.0], &[3, 4]);
+        bar.set_horizontal(true).draw_dates(&["2024-03-15"], &[3]);
+        let b: &str = "x=to_dates([1710460800,1710547200,])\n\
+                       y=np.array([3,4,])\n\
+                       p=plt.bar(x,y)\n\
+                       set_date_axis(plt.gca().xaxis)\n\
+                       x=to_dates([r'2024-03-15',])\n\
+                       y=np.array([3,])\n\
+                       p=plt.barh(x,y)\n\
+                       set_date_axis(plt.gca().yaxis)\n";
+        assert_eq!(bar.buffer, b);
+    }
+
     #[test]
     fn draw_with_str_works_1() {
         let xx = ["one", "two", "three"];
         let yy = [1, 2, 3];
         let mut bar = Barplot::new();
         bar.draw_with_str(&xx, &yy);
-        let b: &str = "x=['one','two','three',]\n\
+        let b: &str = "x=[r'one',r'two',r'three',]\n\
                        y=np.array([1,2,3,])\n\
                        p=plt.bar(x,y)\n";
         assert_eq!(bar.buffer, b);
@@ -383,9 +417,9 @@ mod tests {
             .set_with_text("center")
             .set_extra("edgecolor='black'")
             .draw_with_str(&xx, &yy);
-        let b: &str = "x=['one','two','three',]\n\
+        let b: &str = "x=[r'one',r'two',r'three',]\n\
                        y=np.array([1,2,3,])\n\
-                       colors=['red','green',]\n\
+                       colors=[r'red',r'green',]\n\
                        bottom=np.array([1,2,3,])\n\
                        p=plt.bar(x,y\
                        ,label=r'LABEL'\
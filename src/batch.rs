@@ -0,0 +1,220 @@
+use super::{Error, Plot};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Saves many figures in parallel using a bounded number of Python processes
+///
+/// Each job is a Plot and the path of its figure. The jobs are distributed among `max_workers`
+/// threads; each thread runs one Python process at a time. Thus, at most `max_workers` Python
+/// processes run simultaneously.
+///
+/// # Input
+///
+/// * `jobs` -- pairs of Plot and figure path (may be a String, &str, or Path)
+/// * `max_workers` -- the maximum number of Python processes running at the same time;
+///   zero means the number of available CPUs
+///
+/// # Output
+///
+/// Returns one result per job, in the same order as `jobs`.
+///
+/// # Notes
+///
+/// 1. The python script (and log file) of each figure is written next to the figure as in [Plot::save()]
+/// 2. If two or more figures have the same stem (e.g., `fig.png` and `fig.svg`), the python script,
+///    the log file, and the binary data file are named `<stem>_<index>` instead, where `index` is the
+///    position of the job in `jobs` (followed by `_1`, `_2`, ... if another figure already has this stem).
+///    Hence, the jobs never overwrite each other's auxiliary files.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{linspace, save_batch, Curve, Error, Plot};
+///
+/// fn main() -> Result<(), Error> {
+///     let x = linspace(0.0, 1.0, 11);
+///     let mut jobs = Vec::new();
+///     for i in 0..4 {
+///         let y: Vec<_> = x.iter().map(|v| f64::powi(*v, i + 1)).collect();
+///         let mut curve = Curve::new();
+///         curve.draw(&x, &y);
+///         let mut plot = Plot::new();
+///         plot.add(&curve);
+///         jobs.push((plot, format!("/tmp/plotpy/doc_tests/doc_save_batch_{}.svg", i)));
+///     }
+///     for result in save_batch(&jobs, 2) {
+///         result?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn save_batch<S>(jobs: &[(Plot, S)], max_workers: usize) -> Vec<Result<(), Error>>
+where
+    S: AsRef<OsStr> + Sync,
+{
+    // auxiliary files
+    let stems = unique_stems(jobs.iter().map(|(_, path)| Path::new(path)));
+
+    // workers
+    let max_workers = if max_workers == 0 {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        max_workers
+    };
+    let n_workers = usize::min(max_workers, jobs.len());
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<(), Error>>>> = Mutex::new((0..jobs.len()).map(|_| None).collect());
+
+    // run
+    thread::scope(|scope| {
+        for _ in 0..n_workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= jobs.len() {
+                    break;
+                }
+                let (plot, path) = &jobs[index];
+                let result = plot.run_with_stem(&[Path::new(path)], &stems[index], false);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    // done
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.unwrap()) // all jobs have been processed
+        .collect()
+}
+
+/// Returns the paths used to name the auxiliary files of each figure
+///
+/// The figure path is returned if its stem is unique; otherwise, `<stem>_<index>` is used
+/// (with an extra `_1`, `_2`, ... suffix until the stem differs from all other stems).
+fn unique_stems<'a, I>(paths: I) -> Vec<PathBuf>
+where
+    I: Iterator<Item = &'a Path>,
+{
+    let paths: Vec<_> = paths.collect();
+    let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+    for path in &paths {
+        *counts.entry(path.with_extension("")).or_insert(0) += 1;
+    }
+    let mut taken: HashSet<PathBuf> = counts
+        .iter()
+        .filter(|(_, count)| **count == 1)
+        .map(|(key, _)| key.clone())
+        .collect();
+    paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let key = path.with_extension("");
+            if counts[&key] == 1 {
+                return path.to_path_buf();
+            }
+            let stem = key.file_name().unwrap_or_default().to_string_lossy();
+            let ext = path.extension().unwrap_or(OsStr::new("py")).to_string_lossy();
+            let mut name = format!("{}_{}", stem, index);
+            let mut suffix = 0;
+            while !taken.insert(key.with_file_name(&name)) {
+                suffix += 1;
+                name = format!("{}_{}_{}", stem, index, suffix);
+            }
+            key.with_file_name(format!("{}.{}", name, ext))
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{save_batch, unique_stems};
+    use crate::{Error, Plot};
+    use std::path::{Path, PathBuf};
+
+    const OUT_DIR: &str = "/tmp/plotpy/unit_tests";
+
+    #[test]
+    fn unique_stems_works() {
+        let paths = [
+            "/tmp/a.png",
+            "/tmp/b.png",
+            "/tmp/a.svg",
+            "/tmp/c/a.svg",
+            "/tmp/a.png",
+            "a.v2.pdf",
+        ];
+        let stems = unique_stems(paths.iter().map(Path::new));
+        let correct: Vec<PathBuf> = [
+            "/tmp/a_0.png",
+            "/tmp/b.png",
+            "/tmp/a_2.svg",
+            "/tmp/c/a.svg",
+            "/tmp/a_4.png",
+            "a.v2.pdf",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(stems, correct);
+        assert_eq!(stems[0].with_extension("py"), Path::new("/tmp/a_0.py"));
+
+        // the new stems do not collide with the stems of other figures
+        let paths = ["/tmp/a.png", "/tmp/a.svg", "/tmp/a_0.svg", "/tmp/a_0_1.pdf"];
+        let stems = unique_stems(paths.iter().map(Path::new));
+        let correct: Vec<PathBuf> = ["/tmp/a_0_2.png", "/tmp/a_1.svg", "/tmp/a_0.svg", "/tmp/a_0_1.pdf"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(stems, correct);
+    }
+
+    #[test]
+    fn save_batch_handles_errors() {
+        let mut jobs = Vec::new();
+        for i in 0..3 {
+            let mut plot = Plot::new();
+            plot.set_python_exe("__wrong_python_exe__");
+            if i == 1 {
+                plot.set_python_exe("python3").extra("raise ValueError('job 1')\n");
+            }
+            jobs.push((plot, Path::new(OUT_DIR).join("save_batch_handles_errors.svg")));
+        }
+        let results = save_batch(&jobs, 0);
+        assert_eq!(results.len(), 3);
+        for (i, result) in results.iter().enumerate() {
+            match result {
+                Err(Error::Spawn { .. }) => assert!(i != 1),
+                Err(Error::Python { .. }) => assert_eq!(i, 1),
+                _ => panic!("error expected"),
+            }
+        }
+        let log = std::fs::read_to_string(Path::new(OUT_DIR).join("save_batch_handles_errors_1.log")).unwrap();
+        assert!(log.contains("Error"));
+    }
+
+    #[test]
+    fn save_batch_works() {
+        let mut jobs = Vec::new();
+        for i in 0..5 {
+            let mut plot = Plot::new();
+            plot.set_title(&format!("figure {}", i));
+            let ext = if i % 2 == 0 { "svg" } else { "png" };
+            jobs.push((plot, format!("{}/save_batch_works.{}", OUT_DIR, ext)));
+        }
+        let results = save_batch(&jobs, 2);
+        assert!(results.iter().all(|r| r.is_ok()));
+        for i in 0..5 {
+            let script = std::fs::read_to_string(format!("{}/save_batch_works_{}.py", OUT_DIR, i)).unwrap();
+            assert!(script.contains(&format!("figure {}", i)));
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use super::{generate_list, generate_nested_list, matrix_to_array, AsMatrix, GraphMaker};
-use num_traits::Num;
+use super::{generate_list, generate_nested_list, matrix_to_array, quote, AsMatrix, DataArrays, GraphMaker};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Draw a box and whisker plot
@@ -11,9 +11,9 @@ use std::fmt::Write;
 /// ## Data as a nested list
 ///
 /// ```
-/// use plotpy::{Boxplot, Plot, StrError};
+/// use plotpy::{Boxplot, Error, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data (as a nested list)
 ///     let data = vec![
 ///         vec![1, 2, 3, 4, 5],              // A
@@ -47,9 +47,9 @@ use std::fmt::Write;
 /// ## Data as a 2D array
 ///
 /// ```
-/// use plotpy::{Boxplot, Plot, StrError};
+/// use plotpy::{Boxplot, Error, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data (as a 2D array/matrix)
 ///     let data = vec![
 ///         //   A  B  C  D  E
@@ -99,6 +99,7 @@ pub struct Boxplot {
     whisker_props: String, // The properties of the whisker
     extra: String,         // Extra commands (comma separated)
     buffer: String,        // Buffer
+    arrays: DataArrays,    // data arrays written to the buffer
 }
 
 impl Boxplot {
@@ -117,6 +118,7 @@ impl Boxplot {
             whisker_props: String::new(),
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -128,9 +130,9 @@ impl Boxplot {
     ///   [From Matplotlib](https://matplotlib.org/3.6.3/api/_as_gen/matplotlib.pyplot.boxplot.html)
     pub fn draw<T>(&mut self, data: &Vec<Vec<T>>)
     where
-        T: std::fmt::Display + Num,
+        T: std::fmt::Display + Num + ToPrimitive,
     {
-        generate_nested_list(&mut self.buffer, "x", data);
+        generate_nested_list(&mut self.buffer, &mut self.arrays, "x", data);
         if self.positions.len() > 0 {
             generate_list(&mut self.buffer, "positions", self.positions.as_slice());
         }
@@ -147,9 +149,9 @@ impl Boxplot {
     pub fn draw_mat<'a, T, U>(&mut self, data: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "x", data);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "x", data);
         if self.positions.len() > 0 {
             generate_list(&mut self.buffer, "positions", self.positions.as_slice());
         }
@@ -241,7 +243,7 @@ impl Boxplot {
     fn options(&self) -> String {
         let mut opt = String::new();
         if self.symbol != "" {
-            write!(&mut opt, ",sym=r'{}'", self.symbol).unwrap();
+            write!(&mut opt, ",sym={}", quote(&self.symbol)).unwrap();
         }
         if self.horizontal {
             write!(&mut opt, ",vert=False").unwrap();
@@ -281,8 +283,12 @@ impl GraphMaker for Boxplot {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
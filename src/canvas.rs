@@ -1,7 +1,7 @@
-use super::{GraphMaker, StrError};
-use crate::conversions::{matrix_to_array, vector_to_array};
+use super::{Error, GraphMaker, StrError};
+use crate::conversions::{matrix_to_array, quote, vector_to_array, DataArrays};
 use crate::{AsMatrix, AsVector};
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Defines the poly-curve code
@@ -37,9 +37,9 @@ pub enum PolyCode {
 /// ## Drawing functions with polyline set by an array
 ///
 /// ```
-/// use plotpy::{Canvas, Plot};
+/// use plotpy::{Canvas, Error, Plot};
 ///
-/// fn main() -> Result<(), &'static str> {
+/// fn main() -> Result<(), Error> {
 ///     // canvas object and common options
 ///     let mut canvas = Canvas::new();
 ///     canvas.set_line_width(3.0).set_edge_color("#cd0000").set_face_color("#eeea83");
@@ -82,9 +82,9 @@ pub enum PolyCode {
 /// ## Cubic Bezier and use of begin/end functions
 ///
 /// ```
-/// use plotpy::{Canvas, Plot, PolyCode, StrError};
+/// use plotpy::{Canvas, Error, Plot, PolyCode};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // codes
 ///     let data = [
 ///         (3.0, 0.0, PolyCode::MoveTo),
@@ -165,7 +165,8 @@ pub struct Canvas {
     glyph_bbox_opt: String,    // Python options for the dictionary setting the bounding box of 3D glyphs' text
 
     // buffer
-    buffer: String, // buffer
+    buffer: String,     // buffer
+    arrays: DataArrays, // data arrays written to the buffer
 }
 
 impl Canvas {
@@ -207,6 +208,7 @@ impl Canvas {
             glyph_bbox_opt: "boxstyle='circle,pad=0.1',facecolor='white',edgecolor='None'".to_string(),
             // buffer
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -265,12 +267,12 @@ impl Canvas {
     pub fn draw_triangles<'a, T, U, C>(&mut self, xx: &'a T, yy: &'a T, connectivity: &'a C) -> &mut Self
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
         C: AsMatrix<'a, usize>,
     {
-        vector_to_array(&mut self.buffer, "xx", xx);
-        vector_to_array(&mut self.buffer, "yy", yy);
-        matrix_to_array(&mut self.buffer, "triangles", connectivity);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "xx", xx);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "yy", yy);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "triangles", connectivity);
         let opt = self.options_triangles();
         write!(&mut self.buffer, "plt.triplot(xx,yy,triangles{})\n", &opt).unwrap();
         self
@@ -284,14 +286,14 @@ impl Canvas {
     pub fn draw_triangles_3d<'a, T, U, C>(&mut self, xx: &'a T, yy: &'a T, zz: &'a T, connectivity: &'a C) -> &mut Self
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
         C: AsMatrix<'a, usize>,
     {
         // write arrays
-        vector_to_array(&mut self.buffer, "xx", xx);
-        vector_to_array(&mut self.buffer, "yy", yy);
-        vector_to_array(&mut self.buffer, "zz", zz);
-        matrix_to_array(&mut self.buffer, "triangles", connectivity);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "xx", xx);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "yy", yy);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "zz", zz);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "triangles", connectivity);
 
         // Issue when setting facecolor directly:
         //
@@ -578,9 +580,9 @@ impl Canvas {
         T: std::fmt::Display + Num,
     {
         let size = self.glyph_size;
-        let lx = &self.glyph_label_x;
-        let ly = &self.glyph_label_y;
-        let lz = &self.glyph_label_z;
+        let lx = quote(&self.glyph_label_x);
+        let ly = quote(&self.glyph_label_y);
+        let lz = quote(&self.glyph_label_z);
         let lw = self.glyph_line_width;
         let r = &self.glyph_color_x;
         let g = &self.glyph_color_y;
@@ -605,9 +607,9 @@ impl Canvas {
             "plt.gca().plot([{x},{x}+{size}],[{y},{y}],[{z},{z}],color='{r}',linewidth={lw})\n\
              plt.gca().plot([{x},{x}],[{y},{y}+{size}],[{z},{z}],color='{g}',linewidth={lw})\n\
              plt.gca().plot([{x},{x}],[{y},{y}],[{z},{z}+{size}],color='{b}',linewidth={lw})\n\
-             tx=plt.gca().text({x}+{size},{y},{z},'{lx}',color='{tr}',ha='center',va='center')\n\
-             ty=plt.gca().text({x},{y}+{size},{z},'{ly}',color='{tg}',ha='center',va='center')\n\
-             tz=plt.gca().text({x},{y},{z}+{size},'{lz}',color='{tb}',ha='center',va='center')\n"
+             tx=plt.gca().text({x}+{size},{y},{z},{lx},color='{tr}',ha='center',va='center')\n\
+             ty=plt.gca().text({x},{y}+{size},{z},{ly},color='{tg}',ha='center',va='center')\n\
+             tz=plt.gca().text({x},{y},{z}+{size},{lz},color='{tb}',ha='center',va='center')\n"
         )
         .unwrap();
         if self.glyph_bbox_opt != "" {
@@ -639,17 +641,17 @@ impl Canvas {
         ndiv: &[usize],
         with_point_ids: bool,
         with_cell_ids: bool,
-    ) -> Result<(), StrError> {
+    ) -> Result<(), Error> {
         // check input
         let ndim = ndiv.len();
         if ndim < 2 || ndim > 3 {
-            return Err("len(ndiv) == ndim must be 2 or 3");
+            return Err(Error::InvalidInput("len(ndiv) == ndim must be 2 or 3"));
         }
         if xmin.len() != ndim {
-            return Err("size of xmin must equal ndim == len(ndiv)");
+            return Err(Error::InvalidInput("size of xmin must equal ndim == len(ndiv)"));
         }
         if xmax.len() != ndim {
-            return Err("size of xmax must equal ndim == len(ndiv)");
+            return Err(Error::InvalidInput("size of xmax must equal ndim == len(ndiv)"));
         }
 
         // compute delta
@@ -659,7 +661,7 @@ impl Canvas {
             npoint[i] = ndiv[i] + 1;
             delta[i] = xmax[i] - xmin[i];
             if delta[i] <= 0.0 {
-                return Err("xmax must be greater than xmin");
+                return Err(Error::InvalidInput("xmax must be greater than xmin"));
             }
             delta[i] /= ndiv[i] as f64;
         }
@@ -1144,18 +1146,22 @@ impl Canvas {
             self.options_text()
         };
         if ndim == 2 {
-            write!(&mut self.buffer, "plt.text({},{},'{}'{})\n", a[0], a[1], txt, &opt).unwrap();
+            write!(&mut self.buffer, "plt.text({},{},{}{})\n", a[0], a[1], quote(txt), &opt).unwrap();
         } else {
             write!(
                 &mut self.buffer,
-                "ax3d().text({},{},{},'{}'{})\n",
-                a[0], a[1], a[2], txt, &opt
+                "ax3d().text({},{},{},{}{})\n",
+                a[0],
+                a[1],
+                a[2],
+                quote(txt),
+                &opt
             )
             .unwrap();
         }
     }
 
-    /// Adjust 2D or 3D limits
+    /// Adjust 2D or 3D limits (the limits of polar axes are left to autoscaling)
     fn limits(&mut self, ndim: usize, xmin: &[f64], xmax: &[f64]) {
         const FACTOR: f64 = 0.1;
         let mut gap = [0.0; 3];
@@ -1165,7 +1171,7 @@ impl Canvas {
         if ndim == 2 {
             write!(
                 &mut self.buffer,
-                "plt.axis([{},{},{},{}])\n",
+                "if plt.gca().name!='polar': plt.axis([{},{},{},{}])\n",
                 xmin[0] - gap[0],
                 xmax[0] + gap[0],
                 xmin[1] - gap[1],
@@ -1194,8 +1200,12 @@ impl GraphMaker for Canvas {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -1332,6 +1342,13 @@ mod tests {
         assert_eq!(canvas.glyph_label_x, "Ux");
         assert_eq!(canvas.glyph_label_y, "Uy");
         assert_eq!(canvas.glyph_label_z, "Uz");
+        canvas.set_glyph_label_x("U'x").draw_glyph_3d(0, 0, 0);
+        assert!(canvas
+            .buffer
+            .contains("tx=plt.gca().text(0+2,0,0,r'U' \"'\" r'x',color='orange',ha='center',va='center')\n"));
+        assert!(canvas
+            .buffer
+            .contains("ty=plt.gca().text(0,0+2,0,r'Uy',color='cyan',ha='center',va='center')\n"));
     }
 
     #[test]
@@ -1358,8 +1375,14 @@ mod tests {
         canvas.text(3, &a, "hello", false);
         assert_eq!(
             canvas.buffer,
-            "plt.text(0,0,'hello',color='#a81414',fontsize=8,rotation=45)\n\
-             ax3d().text(0,0,0,'hello',color='#343434',ha='center',va='center',fontsize=10)\n"
+            "plt.text(0,0,r'hello',color='#a81414',fontsize=8,rotation=45)\n\
+             ax3d().text(0,0,0,r'hello',color='#343434',ha='center',va='center',fontsize=10)\n"
+        );
+        canvas.clear_buffer();
+        canvas.draw_text(1, 2, "it's C:\\");
+        assert_eq!(
+            canvas.buffer,
+            "plt.text(1,2,r'it' \"'\" r's C:' '\\\\',color='#343434',ha='center',va='center',fontsize=10)\n"
         );
     }
 
@@ -1372,7 +1395,7 @@ mod tests {
         canvas.limits(3, &xmin, &xmax);
         assert_eq!(
             canvas.buffer,
-            "plt.axis([0,0,0,0])\n\
+            "if plt.gca().name!='polar': plt.axis([0,0,0,0])\n\
             ax3d().set_xlim3d(0,0)\n\
             ax3d().set_ylim3d(0,0)\n\
             ax3d().set_zlim3d(0,0)\n"
@@ -1550,13 +1573,19 @@ mod tests {
     fn grid_fails_on_wrong_input() {
         let mut canvas = Canvas::new();
         let res = canvas.draw_grid(&[0.0, 0.0], &[1.0, 1.0], &[1], true, false);
-        assert_eq!(res, Err("len(ndiv) == ndim must be 2 or 3"));
+        assert_eq!(res.err().unwrap().to_string(), "len(ndiv) == ndim must be 2 or 3");
         let res = canvas.draw_grid(&[0.0], &[1.0, 1.0], &[1, 1], true, false);
-        assert_eq!(res, Err("size of xmin must equal ndim == len(ndiv)"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "size of xmin must equal ndim == len(ndiv)"
+        );
         let res = canvas.draw_grid(&[0.0, 0.0], &[1.0], &[1, 1], true, false);
-        assert_eq!(res, Err("size of xmax must equal ndim == len(ndiv)"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "size of xmax must equal ndim == len(ndiv)"
+        );
         let res = canvas.draw_grid(&[0.0, 0.0], &[0.0, 1.0], &[1, 1], true, false);
-        assert_eq!(res, Err("xmax must be greater than xmin"));
+        assert_eq!(res.err().unwrap().to_string(), "xmax must be greater than xmin");
     }
 
     #[test]
@@ -1575,7 +1604,7 @@ mod tests {
                       h=pth.Path(pts,cmd)\n\
                       p=pat.PathPatch(h,edgecolor='#427ce5')\n\
                       plt.gca().add_patch(p)\n\
-                      plt.axis([-0.1,1.1,-0.1,1.1])\n";
+                      if plt.gca().name!='polar': plt.axis([-0.1,1.1,-0.1,1.1])\n";
         assert_eq!(canvas.buffer, b);
     }
 
@@ -1593,12 +1622,12 @@ mod tests {
                       h=pth.Path(pts,cmd)\n\
                       p=pat.PathPatch(h,edgecolor='#427ce5')\n\
                       plt.gca().add_patch(p)\n\
-                      plt.text(0,0,'0',color='#a81414',fontsize=8,rotation=45)\n\
-                      plt.text(1,0,'1',color='#a81414',fontsize=8,rotation=45)\n\
-                      plt.text(0,1,'2',color='#a81414',fontsize=8,rotation=45)\n\
-                      plt.text(1,1,'3',color='#a81414',fontsize=8,rotation=45)\n\
-                      plt.text(0.5,0.5,'0',color='#343434',ha='center',va='center',fontsize=10)\n\
-                      plt.axis([-0.1,1.1,-0.1,1.1])\n";
+                      plt.text(0,0,r'0',color='#a81414',fontsize=8,rotation=45)\n\
+                      plt.text(1,0,r'1',color='#a81414',fontsize=8,rotation=45)\n\
+                      plt.text(0,1,r'2',color='#a81414',fontsize=8,rotation=45)\n\
+                      plt.text(1,1,r'3',color='#a81414',fontsize=8,rotation=45)\n\
+                      plt.text(0.5,0.5,r'0',color='#343434',ha='center',va='center',fontsize=10)\n\
+                      if plt.gca().name!='polar': plt.axis([-0.1,1.1,-0.1,1.1])\n";
         assert_eq!(canvas.buffer, b);
     }
 
@@ -1613,19 +1642,19 @@ mod tests {
                        ax3d().plot([1,1],[0,1],[0,0],color='#427ce5')\n\
                        ax3d().plot([0,1],[0,0],[0,0],color='#427ce5')\n\
                        ax3d().plot([0,1],[1,1],[0,0],color='#427ce5')\n\
-                       ax3d().text(0,0,0,'0',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(1,0,0,'1',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(0,1,0,'2',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(1,1,0,'3',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(0,0,0,r'0',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(1,0,0,r'1',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(0,1,0,r'2',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(1,1,0,r'3',color='#a81414',fontsize=8,rotation=45)\n\
                        ax3d().plot([0,0],[0,1],[1,1],color='#427ce5')\n\
                        ax3d().plot([1,1],[0,1],[1,1],color='#427ce5')\n\
                        ax3d().plot([0,1],[0,0],[1,1],color='#427ce5')\n\
                        ax3d().plot([0,1],[1,1],[1,1],color='#427ce5')\n\
-                       ax3d().text(0,0,1,'4',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(1,0,1,'5',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(0,1,1,'6',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(1,1,1,'7',color='#a81414',fontsize=8,rotation=45)\n\
-                       ax3d().text(0.5,0.5,0.5,'0',color='#343434',ha='center',va='center',fontsize=10)\n\
+                       ax3d().text(0,0,1,r'4',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(1,0,1,r'5',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(0,1,1,r'6',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(1,1,1,r'7',color='#a81414',fontsize=8,rotation=45)\n\
+                       ax3d().text(0.5,0.5,0.5,r'0',color='#343434',ha='center',va='center',fontsize=10)\n\
                        ax3d().plot([0,0],[0,0],[0,1],color='#427ce5')\n\
                        ax3d().plot([1,1],[0,0],[0,1],color='#427ce5')\n\
                        ax3d().plot([0,0],[1,1],[0,1],color='#427ce5')\n\
@@ -9,6 +9,12 @@
 /// The python definitions are:
 ///
 /// * `NaN` -- Variable to handle NaN values coming from Rust
+/// * `inf` -- Variable to handle infinite values coming from Rust (e.g., `-inf`)
+/// * `TIME_ZONE` -- Time zone of the dates (see [crate::Plot::set_time_zone()])
+/// * `time_zone` -- Returns the tzinfo of a time zone name (e.g., 'UTC' or 'Europe/Berlin')
+/// * `to_dates` -- Converts timestamps (seconds since the Unix epoch or ISO-8601 strings) to dates in the TIME_ZONE
+/// * `set_date_axis` -- Sets the AutoDateLocator and ConciseDateFormatter to an axis (e.g., `plt.gca().xaxis`)
+/// * `AXES` -- List of axes created by [crate::Plot::subplots()] (indexed by [crate::AxesId])
 /// * `EXTRA_ARTISTS` -- List of additional objects that must not be ignored when saving the figure
 /// * `add_to_ea` -- Adds an entity to the EXTRA_ARTISTS list to prevent them being ignored
 ///    when Matplotlib decides to calculate the bounding boxes. The Legend is an example of entity that could
@@ -33,11 +39,45 @@ import matplotlib.lines as lns
 import matplotlib.transforms as tra
 import mpl_toolkits.mplot3d
 import matplotlib.tri as plt_tri
+import matplotlib.dates as mdates
+import datetime as dtm
 from mpl_toolkits.axes_grid1 import make_axes_locatable
 
 # Variable to handle NaN values coming from Rust
 NaN = np.nan
 
+# Variable to handle infinite values coming from Rust
+inf = np.inf
+
+# Time zone of the dates
+TIME_ZONE = dtm.timezone.utc
+
+# Returns the tzinfo of a time zone name (e.g., 'UTC' or 'Europe/Berlin')
+def time_zone(name):
+    if name=='UTC': return dtm.timezone.utc
+    import zoneinfo
+    return zoneinfo.ZoneInfo(name)
+
+# Converts timestamps (seconds since the Unix epoch or ISO-8601 strings) to dates in the TIME_ZONE
+def to_dates(values):
+    res = []
+    for v in values:
+        if isinstance(v, str):
+            d = dtm.datetime.fromisoformat(v.replace('Z','+00:00'))
+            res.append(d.replace(tzinfo=TIME_ZONE) if d.tzinfo is None else d.astimezone(TIME_ZONE))
+        else:
+            res.append(dtm.datetime.fromtimestamp(v,tz=TIME_ZONE))
+    return np.array(res)
+
+# Sets the AutoDateLocator and ConciseDateFormatter to an axis (e.g., plt.gca().xaxis)
+def set_date_axis(axis):
+    loc = mdates.AutoDateLocator(tz=TIME_ZONE)
+    axis.set_major_locator(loc)
+    axis.set_major_formatter(mdates.ConciseDateFormatter(loc,tz=TIME_ZONE))
+
+# List of axes created by subplots (indexed by AxesId)
+AXES = []
+
 # List of additional objects to calculate bounding boxes
 EXTRA_ARTISTS = []
 
@@ -98,10 +138,9 @@ def set_equal_axes():
         ax.set_xlim3d([x - radius, x + radius])
         ax.set_ylim3d([y - radius, y + radius])
         ax.set_zlim3d([z - radius, z + radius])
-    except:
+    except AttributeError:
         import matplotlib
-        print('VERSION of MATPLOTLIB = {}'.format(matplotlib.__version__))
-        print('ERROR: set_box_aspect is missing in this version of Matplotlib')
+        raise RuntimeError('set_box_aspect is missing in Matplotlib {}'.format(matplotlib.__version__))
 
 # Function to ignore calls to plt such as the colorbar in an inset Axes
 def ignore_this(*args, **kwargs):
@@ -150,6 +189,6 @@ mod tests {
 
     #[test]
     fn constants_are_correct() {
-        assert_eq!(PYTHON_HEADER.len(), 2928);
+        assert_eq!(PYTHON_HEADER.len(), 4063);
     }
 }
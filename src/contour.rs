@@ -1,6 +1,9 @@
-use super::{generate_list_quoted, matrix_to_array, vector_to_array, AsMatrix, GraphMaker};
+use super::{
+    generate_list_quoted, matrix_to_array, quote, vector_to_array, AsMatrix, DataArrays, Formatter, GraphMaker,
+    Locator, StrError,
+};
 use crate::AsVector;
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Generates a contour plot
@@ -10,9 +13,9 @@ use std::fmt::Write;
 /// # Example
 ///
 /// ```
-/// use plotpy::{Contour, Plot, generate3d};
+/// use plotpy::{generate3d, Contour, Error, Plot};
 ///
-/// fn main() -> Result<(), &'static str> {
+/// fn main() -> Result<(), Error> {
 ///     // generate (x,y,z) matrices
 ///     let n = 21;
 ///     let (x, y, z) = generate3d(-2.0, 2.0, -2.0, 2.0, n, n, |x, y| x * x - y * y);
@@ -58,6 +61,7 @@ pub struct Contour {
     no_colorbar: bool,            // Skip drawing a colorbar
     colorbar_label: String,       // Colorbar label
     colorbar_axes: String,        // Axes into which the colorbar will be drawn
+    colorbar_ticks: String,       // Commands to set the locator and formatter of the colorbar ticks
     colorbar_location: String,    // Colorbar location
     colorbar_extra: String,       // Extra options for the colorbar
     number_format_cb: String,     // Number format for the labels in lines contour
@@ -79,6 +83,7 @@ pub struct Contour {
     tri_edges_line_width: f64,    // Triangulation edges line width
     tri_edges_line_style: String, // Triangulation edges line style
     buffer: String,               // buffer
+    arrays: DataArrays,           // data arrays written to the buffer
 }
 
 impl Contour {
@@ -95,6 +100,7 @@ impl Contour {
             no_colorbar: false,
             colorbar_label: String::new(),
             colorbar_axes: String::new(),
+            colorbar_ticks: String::new(),
             colorbar_location: String::new(),
             colorbar_extra: String::new(),
             number_format_cb: String::new(),
@@ -116,6 +122,7 @@ impl Contour {
             tri_edges_line_width: 0.5,
             tri_edges_line_style: "-".to_string(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -139,11 +146,11 @@ impl Contour {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "x", x);
-        matrix_to_array(&mut self.buffer, "y", y);
-        matrix_to_array(&mut self.buffer, "z", z);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "z", z);
         self.contour_or_tricontour(false);
     }
 
@@ -168,13 +175,13 @@ impl Contour {
     pub fn draw_tri<'a, T, U, C>(&mut self, x: &'a T, y: &'a T, z: &'a T, connectivity: &'a C)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
         C: AsMatrix<'a, usize>,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        vector_to_array(&mut self.buffer, "z", z);
-        matrix_to_array(&mut self.buffer, "con", connectivity);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "z", z);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "con", connectivity);
         write!(&mut self.buffer, "tri=plt_tri.Triangulation(x,y,triangles=con)\n").unwrap();
         self.contour_or_tricontour(true);
         if self.tri_show_edges {
@@ -195,7 +202,7 @@ impl Contour {
             generate_list_quoted(&mut self.buffer, "colors", &self.colors);
         }
         if self.levels.len() > 0 {
-            vector_to_array(&mut self.buffer, "levels", &self.levels);
+            vector_to_array(&mut self.buffer, &mut self.arrays, "levels", &self.levels);
         }
         let opt = self.options_filled();
         if !self.no_fill {
@@ -218,7 +225,10 @@ impl Contour {
                 write!(&mut self.buffer, "cb=plt.colorbar(cf{})\n", &opt_colorbar).unwrap();
             }
             if self.colorbar_label != "" {
-                write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+                write!(&mut self.buffer, "cb.ax.set_ylabel({})\n", quote(&self.colorbar_label)).unwrap();
+            }
+            if self.colorbar_ticks != "" {
+                write!(&mut self.buffer, "{}cb.update_ticks()\n", self.colorbar_ticks).unwrap();
             }
         }
         if self.with_selected {
@@ -309,6 +319,24 @@ impl Contour {
         self
     }
 
+    /// Sets the locator of the colorbar ticks
+    ///
+    /// **Note:** The locator is ignored (and an error is returned) if invalid.
+    pub fn set_colorbar_locator(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        let expression = locator.expression()?;
+        write!(&mut self.colorbar_ticks, "cb.locator={}\n", expression).unwrap();
+        Ok(self)
+    }
+
+    /// Sets the formatter of the colorbar ticks
+    ///
+    /// **Note:** The formatter is ignored (and an error is returned) if invalid.
+    pub fn set_colorbar_formatter(&mut self, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        let commands = formatter.commands("cb_formatter")?;
+        write!(&mut self.colorbar_ticks, "{}cb.formatter=cb_formatter\n", commands).unwrap();
+        Ok(self)
+    }
+
     /// Configure the axes into which the colorbar will be drawn
     ///
     /// # Arguments
@@ -570,8 +598,12 @@ impl GraphMaker for Contour {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -580,7 +612,7 @@ impl GraphMaker for Contour {
 #[cfg(test)]
 mod tests {
     use super::Contour;
-    use crate::GraphMaker;
+    use crate::{Formatter, GraphMaker, Locator};
 
     #[test]
     fn new_works() {
@@ -705,7 +737,7 @@ mod tests {
         let b: &str = "x=np.array([[-0.5,0,0.5,],[-0.5,0,0.5,],[-0.5,0,0.5,],])\n\
                        y=np.array([[-0.5,-0.5,-0.5,],[0,0,0,],[0.5,0.5,0.5,],])\n\
                        z=np.array([[0.5,0.25,0.5,],[0.25,0,0.25,],[0.5,0.25,0.5,],])\n\
-                       colors=['#f00','#0f0','#00f',]\n\
+                       colors=[r'#f00',r'#0f0',r'#00f',]\n\
                        levels=np.array([0.25,0.5,1,])\n\
                        cf=plt.contourf(x,y,z,colors=colors,levels=levels)\n\
                        cl=plt.contour(x,y,z,colors=['black'],levels=levels)\n\
@@ -717,4 +749,34 @@ mod tests {
         contour.clear_buffer();
         assert_eq!(contour.buffer, "");
     }
+
+    #[test]
+    fn colorbar_ticks_work() {
+        let mut contour = Contour::new();
+        assert_eq!(
+            contour
+                .set_colorbar_locator(&Locator::MaxN {
+                    nbins: 0,
+                    integer: false
+                })
+                .err(),
+            Some("the number of bins of the MaxN locator must be positive")
+        );
+        contour
+            .set_colorbar_locator(&Locator::Fixed(vec![0.0, 0.5]))
+            .unwrap()
+            .set_colorbar_formatter(&Formatter::Decimals(1))
+            .unwrap();
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 0.5], vec![0.5, 1.0]];
+        contour.set_no_lines(true).draw(&x, &y, &z);
+        assert!(contour.buffer.ends_with(
+            "cb=plt.colorbar(cf)\n\
+             cb.locator=tck.FixedLocator([0,0.5,])\n\
+             cb_formatter=tck.StrMethodFormatter(r'{x:.1f}')\n\
+             cb.formatter=cb_formatter\n\
+             cb.update_ticks()\n"
+        ));
+    }
 }
@@ -1,6 +1,7 @@
-use super::{AsMatrix, AsVector};
-use num_traits::Num;
+use super::{AsMatrix, AsTimestamp, AsVector};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
+use std::ops::Range;
 
 /// Generates a Python list
 pub(crate) fn generate_list<T>(buf: &mut String, name: &str, data: &[T])
@@ -14,53 +15,136 @@ where
     write!(buf, "]\n").unwrap();
 }
 
-/// Generates a Python list with quoted entries
+/// Generates a Python array of dates from timestamps (converted by `to_dates` in the Python header)
+pub(crate) fn timestamps_to_dates<T>(buf: &mut String, name: &str, data: &[T])
+where
+    T: AsTimestamp,
+{
+    write!(buf, "{}=to_dates([", name).unwrap();
+    for val in data.into_iter() {
+        write!(buf, "{},", val.timestamp_literal()).unwrap();
+    }
+    write!(buf, "])\n").unwrap();
+}
+
+/// Converts a text into a Python string literal
+///
+/// The text is written into raw strings (e.g., `r'$\alpha$'`); thus, mathtext and TeX commands
+/// pass through unchanged. The characters that cannot go into raw strings (single quotation marks,
+/// newlines and other control characters, and trailing backslashes) are written as adjacent escaped
+/// literals, which Python concatenates; e.g., `it's` becomes `r'it' "'" r's'`.
+///
+/// **Note:** Non-ASCII characters are kept as they are (the script is written as UTF-8).
+pub(crate) fn quote(text: &str) -> String {
+    let mut res = String::new();
+    let mut raw = String::new();
+    for c in text.chars() {
+        if c == '\'' || c.is_control() {
+            push_raw_literal(&mut res, &raw);
+            raw.clear();
+            let literal = match c {
+                '\'' => "\"'\"".to_string(),
+                '\n' => "'\\n'".to_string(),
+                '\r' => "'\\r'".to_string(),
+                '\t' => "'\\t'".to_string(),
+                _ => format!("'\\x{:02x}'", c as u32), // all control characters are below 0x100
+            };
+            push_literal(&mut res, &literal);
+        } else {
+            raw.push(c);
+        }
+    }
+    push_raw_literal(&mut res, &raw);
+    if res.is_empty() {
+        res.push_str("r''");
+    }
+    res
+}
+
+/// Appends a raw string literal to the result of [quote] (the trailing backslashes are escaped separately)
+fn push_raw_literal(res: &mut String, raw: &str) {
+    let trimmed = raw.trim_end_matches('\\');
+    if !trimmed.is_empty() {
+        push_literal(res, &format!("r'{}'", trimmed));
+    }
+    let count = raw.len() - trimmed.len();
+    if count > 0 {
+        push_literal(res, &format!("'{}'", "\\\\".repeat(count)));
+    }
+}
+
+/// Appends a literal to the result of [quote] (separated by a space)
+fn push_literal(res: &mut String, literal: &str) {
+    if !res.is_empty() {
+        res.push(' ');
+    }
+    res.push_str(literal);
+}
+
+/// Generates a Python list with quoted entries (see [quote])
 pub(crate) fn generate_list_quoted<T>(buf: &mut String, name: &str, data: &[T])
 where
     T: std::fmt::Display,
 {
     write!(buf, "{}=[", name).unwrap();
     for val in data.into_iter() {
-        write!(buf, "'{}',", val).unwrap();
+        write!(buf, "{},", quote(&val.to_string())).unwrap();
     }
     write!(buf, "]\n").unwrap();
 }
 
 /// Converts vector to a 1D NumPy array
-pub(crate) fn vector_to_array<'a, T, U>(buf: &mut String, name: &str, vector: &'a T)
+///
+/// The values are also recorded in `arrays` (see [DataArrays]).
+pub(crate) fn vector_to_array<'a, T, U>(buf: &mut String, arrays: &mut DataArrays, name: &str, vector: &'a T)
 where
     T: AsVector<'a, U>,
-    U: 'a + std::fmt::Display + Num,
+    U: 'a + std::fmt::Display + Num + ToPrimitive,
 {
-    write!(buf, "{}=np.array([", name).unwrap();
+    let start = buf.len();
     let m = vector.vec_size();
+    let mut values = Vec::with_capacity(m);
+    write!(buf, "{}=np.array([", name).unwrap();
     for i in 0..m {
-        write!(buf, "{},", vector.vec_at(i)).unwrap();
+        let val = vector.vec_at(i);
+        write!(buf, "{},", val).unwrap();
+        values.push(val.to_f64().unwrap_or(f64::NAN));
     }
     write!(buf, "])\n").unwrap();
+    arrays.push(name, start..buf.len(), values, DataKind::Vector);
 }
 
 /// Generates a nested Python list
-pub(crate) fn generate_nested_list<T>(buf: &mut String, name: &str, data: &Vec<Vec<T>>)
+///
+/// The values are also recorded in `arrays` (see [DataArrays]); however, the list is kept as text.
+pub(crate) fn generate_nested_list<T>(buf: &mut String, arrays: &mut DataArrays, name: &str, data: &Vec<Vec<T>>)
 where
-    T: std::fmt::Display + Num,
+    T: std::fmt::Display + Num + ToPrimitive,
 {
+    let start = buf.len();
+    let mut values = Vec::new();
     write!(buf, "{}=[", name).unwrap();
     for row in data.into_iter() {
         write!(buf, "[").unwrap();
         for val in row.into_iter() {
             write!(buf, "{},", val).unwrap();
+            values.push(val.to_f64().unwrap_or(f64::NAN));
         }
         write!(buf, "],").unwrap();
     }
     write!(buf, "]\n").unwrap();
+    arrays.push(name, start..buf.len(), values, DataKind::NestedList);
 }
 
 /// Generates a 3-deep nested Python list
-pub(crate) fn generate_nested_list_3<T>(buf: &mut String, name: &str, data: &Vec<Vec<Vec<T>>>)
+///
+/// The values are also recorded in `arrays` (see [DataArrays]); however, the list is kept as text.
+pub(crate) fn generate_nested_list_3<T>(buf: &mut String, arrays: &mut DataArrays, name: &str, data: &Vec<Vec<Vec<T>>>)
 where
-    T: std::fmt::Display + Num,
+    T: std::fmt::Display + Num + ToPrimitive,
 {
+    let start = buf.len();
+    let mut values = Vec::new();
     write!(buf, "{}=[", name).unwrap();
     for row in data.into_iter() {
         write!(buf, "[").unwrap();
@@ -68,30 +152,217 @@ where
             write!(buf, "[",).unwrap();
             for v in val.into_iter() {
                 write!(buf, "{},", v).unwrap();
+                values.push(v.to_f64().unwrap_or(f64::NAN));
             }
             write!(buf, "],").unwrap();
         }
         write!(buf, "],").unwrap();
     }
     write!(buf, "]\n").unwrap();
+    arrays.push(name, start..buf.len(), values, DataKind::NestedList3);
 }
 
 /// Converts a matrix to a 2D NumPy array
-pub(crate) fn matrix_to_array<'a, T, U>(buf: &mut String, name: &str, matrix: &'a T)
+///
+/// The values are also recorded in `arrays` (see [DataArrays]).
+pub(crate) fn matrix_to_array<'a, T, U>(buf: &mut String, arrays: &mut DataArrays, name: &str, matrix: &'a T)
 where
     T: AsMatrix<'a, U>,
-    U: 'a + std::fmt::Display + Num,
+    U: 'a + std::fmt::Display + Num + ToPrimitive,
 {
-    write!(buf, "{}=np.array([", name).unwrap();
+    let start = buf.len();
     let (m, n) = matrix.size();
+    let mut values = Vec::with_capacity(m * n);
+    write!(buf, "{}=np.array([", name).unwrap();
     for i in 0..m {
         write!(buf, "[").unwrap();
         for j in 0..n {
-            write!(buf, "{},", matrix.at(i, j)).unwrap();
+            let val = matrix.at(i, j);
+            write!(buf, "{},", val).unwrap();
+            values.push(val.to_f64().unwrap_or(f64::NAN));
         }
         write!(buf, "],").unwrap();
     }
     write!(buf, "])\n").unwrap();
+    arrays.push(name, start..buf.len(), values, DataKind::Matrix(m, n));
+}
+
+/// Holds the values of the NumPy arrays written to a buffer of Python commands
+///
+/// The functions converting data (e.g., [crate::Curve::draw()]) write the arrays to the buffer as text
+/// (e.g., `x=np.array([1.5,2.5,])`) and record here the position of each line in the buffer with the
+/// values. Thus, [crate::Plot] replaces the text by binary data (see [crate::Plot::set_binary_data()])
+/// and handles the non-finite values (see [crate::Plot::set_non_finite()]) without parsing the buffer.
+///
+/// See [crate::GraphMaker::get_arrays()].
+#[derive(Clone, Debug, Default)]
+pub struct DataArrays {
+    items: Vec<DataArray>,
+}
+
+/// Holds the values of one NumPy array (or nested list) written to a buffer
+#[derive(Clone, Debug)]
+struct DataArray {
+    name: String,        // variable name
+    range: Range<usize>, // position of the line (with the newline) in the buffer
+    values: Vec<f64>,    // all values (row-major)
+    kind: DataKind,      // how the values are written to the buffer
+}
+
+/// Defines how the values of a [DataArray] are written to the buffer
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DataKind {
+    Vector,               // 1D NumPy array
+    Matrix(usize, usize), // 2D NumPy array with (nrow, ncol)
+    NestedList,           // list of lists (the rows may have different lengths); kept as text
+    NestedList3,          // 3-deep nested list; kept as text
+}
+
+impl DataArrays {
+    /// Allocates a new (empty) instance
+    pub fn new() -> Self {
+        DataArrays { items: Vec::new() }
+    }
+
+    /// Records an array written to the buffer
+    fn push(&mut self, name: &str, range: Range<usize>, values: Vec<f64>, kind: DataKind) {
+        self.items.push(DataArray {
+            name: name.to_string(),
+            range,
+            values,
+            kind,
+        });
+    }
+
+    /// Clears all records (must be called when the buffer is cleared)
+    pub(crate) fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Moves all records by `offset` (e.g., after inserting text at the beginning of the buffer)
+    pub(crate) fn shift(&mut self, offset: usize) {
+        for item in &mut self.items {
+            item.range = (item.range.start + offset)..(item.range.end + offset);
+        }
+    }
+
+    /// Appends the commands of another buffer (and its records, if any) to `buf`
+    pub(crate) fn append(&mut self, buf: &mut String, other_buf: &str, other: Option<&DataArrays>) {
+        if let Some(arrays) = other {
+            let offset = buf.len();
+            self.items.extend(arrays.items.iter().map(|item| DataArray {
+                range: (item.range.start + offset)..(item.range.end + offset),
+                ..item.clone()
+            }));
+        }
+        buf.push_str(other_buf);
+    }
+
+    /// Appends the commands of another buffer to `buf` converting the text between the arrays by `convert`
+    ///
+    /// The lines holding the arrays are copied unchanged; thus, the records remain valid.
+    pub(crate) fn append_converted<F>(
+        &mut self,
+        buf: &mut String,
+        other_buf: &str,
+        other: Option<&DataArrays>,
+        convert: F,
+    ) where
+        F: Fn(&str) -> String,
+    {
+        let mut pos = 0;
+        if let Some(arrays) = other {
+            for item in &arrays.items {
+                buf.push_str(&convert(&other_buf[pos..item.range.start]));
+                let start = buf.len();
+                buf.push_str(&other_buf[item.range.clone()]);
+                self.items.push(DataArray {
+                    range: start..buf.len(),
+                    ..item.clone()
+                });
+                pos = item.range.end;
+            }
+        }
+        buf.push_str(&convert(&other_buf[pos..]));
+    }
+
+    /// Returns the name of the first array with non-finite values (NaN or ±inf), if any
+    pub(crate) fn find_non_finite(&self) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|item| item.values.iter().any(|v| !v.is_finite()))
+            .map(|item| item.name.as_str())
+    }
+
+    /// Masks the arrays with non-finite values (NaN or ±inf)
+    ///
+    /// A line such as `x=np.ma.masked_invalid(x)` is inserted after each array with non-finite values;
+    /// the non-finite values are removed from the rows of the nested lists instead (e.g., histogram data).
+    ///
+    /// Returns the modified Python commands and the records of the arrays in the modified commands.
+    pub(crate) fn mask_non_finite(&self, buf: &str) -> (String, DataArrays) {
+        let mut res = String::with_capacity(buf.len());
+        let mut arrays = DataArrays::new();
+        let mut pos = 0;
+        for item in &self.items {
+            res.push_str(&buf[pos..item.range.end]);
+            arrays.items.push(DataArray {
+                range: (res.len() - item.range.len())..res.len(),
+                ..item.clone()
+            });
+            if item.values.iter().any(|v| !v.is_finite()) {
+                if item.kind == DataKind::NestedList {
+                    write!(
+                        &mut res,
+                        "{0}=[np.asarray(r)[np.isfinite(r)] for r in {0}]\n",
+                        item.name
+                    )
+                    .unwrap();
+                } else {
+                    write!(&mut res, "{0}=np.ma.masked_invalid({0})\n", item.name).unwrap();
+                }
+            }
+            pos = item.range.end;
+        }
+        res.push_str(&buf[pos..]);
+        (res, arrays)
+    }
+
+    /// Replaces the arrays in the buffer by slices of binary data
+    ///
+    /// Each line such as `x=np.array([1.5,2.5,])` is replaced by `x=data_name[a:b]`, where
+    /// `data_name` is a 1D NumPy array (e.g., loaded by `np.load`) holding the values appended to `data`.
+    /// Matrices are reshaped and arrays with integer entries are converted to integers, thus the
+    /// resulting arrays are the same as the ones obtained from the text. The nested lists are kept as text.
+    ///
+    /// Returns the modified Python commands.
+    pub(crate) fn replace_by_binary(&self, buf: &str, data_name: &str, data: &mut Vec<f64>) -> String {
+        const MAX_EXACT_INT: f64 = 9007199254740992.0; // 2⁵³
+        let mut res = String::with_capacity(buf.len());
+        let mut pos = 0;
+        for item in &self.items {
+            let shape = match item.kind {
+                DataKind::Vector => None,
+                DataKind::Matrix(m, n) => Some((m, n)),
+                DataKind::NestedList | DataKind::NestedList3 => continue,
+            };
+            res.push_str(&buf[pos..item.range.start]);
+            let start = data.len();
+            data.extend_from_slice(&item.values);
+            write!(&mut res, "{}={}[{}:{}]", item.name, data_name, start, data.len()).unwrap();
+            if let Some((m, n)) = shape {
+                write!(&mut res, ".reshape({},{})", m, n).unwrap();
+            }
+            let integer = item.values.iter().all(|v| v.fract() == 0.0 && v.abs() <= MAX_EXACT_INT);
+            if integer && !item.values.is_empty() {
+                res.push_str(".astype(int)");
+            }
+            res.push('\n');
+            pos = item.range.end;
+        }
+        res.push_str(&buf[pos..]);
+        res
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -128,21 +399,43 @@ mod tests {
         generate_list_quoted(&mut buf, "z", z);
         assert_eq!(
             buf,
-            "x=['red','green','blue',]\n\
-             y=['cyan','magenta','white',]\n\
-             z=['#f00','#0f0','#00f',]\n"
+            "x=[r'red',r'green',r'blue',]\n\
+             y=[r'cyan',r'magenta',r'white',]\n\
+             z=[r'#f00',r'#0f0',r'#00f',]\n"
+        );
+        let mut buf = String::new();
+        generate_list_quoted(&mut buf, "labels", &["it's", "$\\alpha$"]);
+        assert_eq!(buf, "labels=[r'it' \"'\" r's',r'$\\alpha$',]\n");
+    }
+
+    #[test]
+    fn quote_works() {
+        assert_eq!(quote(""), "r''");
+        assert_eq!(quote("Hello World"), "r'Hello World'");
+        assert_eq!(
+            quote("$\\alpha_1 \\cdot \\varepsilon$"),
+            "r'$\\alpha_1 \\cdot \\varepsilon$'"
         );
+        assert_eq!(quote("\"quoted\""), "r'\"quoted\"'");
+        assert_eq!(quote("it's"), "r'it' \"'\" r's'");
+        assert_eq!(quote("''"), "\"'\" \"'\"");
+        assert_eq!(quote("C:\\temp\\"), "r'C:\\temp' '\\\\'");
+        assert_eq!(quote("\\\\"), "'\\\\\\\\'");
+        assert_eq!(quote("first\nsecond"), "r'first' '\\n' r'second'");
+        assert_eq!(quote("a\r\tb\u{0}\u{85}"), "r'a' '\\r' '\\t' r'b' '\\x00' '\\x85'");
+        assert_eq!(quote("température 10µm ✓"), "r'température 10µm ✓'");
     }
 
     #[test]
     fn vector_to_array_works() {
         let mut buf = String::new();
+        let mut arrays = DataArrays::new();
         let x: Vec<f64> = vec![0.1, 0.2, 0.3];
         let y: [f64; 3] = [1.0, 2.0, 3.0];
         let z: &[f64] = &[10.0, 20.0, 30.0];
-        vector_to_array(&mut buf, "x", &x);
-        vector_to_array(&mut buf, "y", &y);
-        vector_to_array(&mut buf, "z", &z);
+        vector_to_array(&mut buf, &mut arrays, "x", &x);
+        vector_to_array(&mut buf, &mut arrays, "y", &y);
+        vector_to_array(&mut buf, &mut arrays, "z", &z);
         assert_eq!(
             buf,
             "x=np.array([0.1,0.2,0.3,])\n\
@@ -151,34 +444,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn non_finite_values_work() {
+        let mut buf = String::new();
+        let mut arrays = DataArrays::new();
+        let x = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        let a = vec![vec![1.0, f64::INFINITY], vec![f64::NAN]];
+        let b = vec![vec![vec![f32::NEG_INFINITY]]];
+        generate_list(&mut buf, "x", &x);
+        vector_to_array(&mut buf, &mut arrays, "y", &x);
+        generate_nested_list(&mut buf, &mut arrays, "a", &a);
+        generate_nested_list_3(&mut buf, &mut arrays, "b", &b);
+        matrix_to_array(&mut buf, &mut arrays, "c", &vec![vec![1.0, f64::INFINITY]]);
+        assert_eq!(
+            buf,
+            "x=[NaN,inf,-inf,]\n\
+             y=np.array([NaN,inf,-inf,])\n\
+             a=[[1,inf,],[NaN,],]\n\
+             b=[[[-inf,],],]\n\
+             c=np.array([[1,inf,],])\n"
+        );
+        let mut data = Vec::new();
+        let res = arrays.replace_by_binary(&buf, "DATA", &mut data);
+        assert_eq!(
+            res,
+            "x=[NaN,inf,-inf,]\n\
+             y=DATA[0:3]\n\
+             a=[[1,inf,],[NaN,],]\n\
+             b=[[[-inf,],],]\n\
+             c=DATA[3:5].reshape(1,2)\n"
+        );
+        assert!(data[0].is_nan());
+        assert_eq!(&data[1..], &[f64::INFINITY, f64::NEG_INFINITY, 1.0, f64::INFINITY]);
+    }
+
     #[test]
     fn generate_nested_list_works() {
         let mut buf = String::new();
+        let mut arrays = DataArrays::new();
         let a = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0], vec![6.0, 7.0, 8.0, 9.0]];
-        generate_nested_list(&mut buf, "a", &a);
+        generate_nested_list(&mut buf, &mut arrays, "a", &a);
         assert_eq!(buf, "a=[[1,2,3,],[4,5,],[6,7,8,9,],]\n");
     }
 
     #[test]
     fn generate_nested_list_3_works() {
         let mut buf = String::new();
+        let mut arrays = DataArrays::new();
         let a = vec![
             vec![vec![1.0, 0.0, 0.0, 1.0], vec![0.0, 1.0, 0.0, 1.0]], // Row 0: Red, Green
             vec![vec![0.0, 0.0, 1.0, 1.0], vec![1.0, 1.0, 1.0, 0.5]], // Row 1: Blue, White (semi-transparent)
         ];
-        generate_nested_list_3(&mut buf, "a", &a);
+        generate_nested_list_3(&mut buf, &mut arrays, "a", &a);
         assert_eq!(buf, "a=[[[1,0,0,1,],[0,1,0,1,],],[[0,0,1,1,],[1,1,1,0.5,],],]\n");
     }
 
     #[test]
     fn matrix_to_array_works() {
         let mut buf = String::new();
+        let mut arrays = DataArrays::new();
         let a: Vec<Vec<f64>> = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
         let b: [[f64; 3]; 3] = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
         let c: &[&[f64]] = &[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0], &[7.0, 8.0, 9.0]];
-        matrix_to_array(&mut buf, "a", &a);
-        matrix_to_array(&mut buf, "b", &b);
-        matrix_to_array(&mut buf, "c", &c);
+        matrix_to_array(&mut buf, &mut arrays, "a", &a);
+        matrix_to_array(&mut buf, &mut arrays, "b", &b);
+        matrix_to_array(&mut buf, &mut arrays, "c", &c);
         assert_eq!(
             buf,
             "a=np.array([[1,2,3,],[4,5,6,],[7,8,9,],])\n\
@@ -186,4 +516,120 @@ mod tests {
              c=np.array([[1,2,3,],[4,5,6,],[7,8,9,],])\n"
         );
     }
+
+    #[test]
+    fn replace_by_binary_works() {
+        let mut buf = String::new();
+        let mut arrays = DataArrays::new();
+        let x: Vec<f64> = vec![0.1, 0.2, 0.3];
+        let y: Vec<usize> = vec![1, 2, 3];
+        let a: Vec<Vec<f64>> = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.5]];
+        let e: Vec<f64> = Vec::new();
+        buf.push_str("plt.figure()\n");
+        vector_to_array(&mut buf, &mut arrays, "x", &x);
+        vector_to_array(&mut buf, &mut arrays, "y", &y);
+        matrix_to_array(&mut buf, &mut arrays, "a", &a);
+        vector_to_array(&mut buf, &mut arrays, "e", &e);
+        buf.push_str("plt.plot(x,y)\n");
+        let mut data = Vec::new();
+        let res = arrays.replace_by_binary(&buf, "DATA", &mut data);
+        assert_eq!(
+            res,
+            "plt.figure()\n\
+             x=DATA[0:3]\n\
+             y=DATA[3:6].astype(int)\n\
+             a=DATA[6:12].reshape(2,3)\n\
+             e=DATA[12:12]\n\
+             plt.plot(x,y)\n"
+        );
+        assert_eq!(data, &[0.1, 0.2, 0.3, 1.0, 2.0, 3.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.5]);
+    }
+
+    #[test]
+    fn replace_by_binary_is_bit_exact() {
+        let x: Vec<f64> = vec![1.0 / 3.0, -f64::MIN_POSITIVE, 1e300, 123456789.123456789, f64::EPSILON];
+        let y: Vec<f32> = vec![0.1, 1.0 / 3.0];
+        let mut buf = String::new();
+        let mut arrays = DataArrays::new();
+        vector_to_array(&mut buf, &mut arrays, "x", &x);
+        vector_to_array(&mut buf, &mut arrays, "y", &y);
+        let mut data = Vec::new();
+        arrays.replace_by_binary(&buf, "DATA", &mut data);
+        for i in 0..x.len() {
+            assert_eq!(data[i].to_bits(), x[i].to_bits());
+        }
+        assert_eq!(data[5], 0.1_f32 as f64);
+        assert_eq!(data[6], (1.0_f32 / 3.0) as f64);
+    }
+
+    #[test]
+    fn append_and_shift_work() {
+        let mut other_buf = String::new();
+        let mut other = DataArrays::new();
+        other_buf.push_str("plt.gca().plot()\n");
+        vector_to_array(&mut other_buf, &mut other, "x", &[1.5, 2.5]);
+        other_buf.push_str("plt.gca().plot(x)\n");
+        let mut buf = "# begin\n".to_string();
+        let mut arrays = DataArrays::new();
+        arrays.append(&mut buf, &other_buf, Some(&other));
+        arrays.append(&mut buf, "# text only\n", None);
+        arrays.append_converted(&mut buf, &other_buf, Some(&other), |s| s.replace("plt.gca()", "zoom"));
+        let mut data = Vec::new();
+        assert_eq!(
+            arrays.replace_by_binary(&buf, "DATA", &mut data),
+            "# begin\n\
+             plt.gca().plot()\n\
+             x=DATA[0:2]\n\
+             plt.gca().plot(x)\n\
+             # text only\n\
+             zoom.plot()\n\
+             x=DATA[2:4]\n\
+             zoom.plot(x)\n"
+        );
+        assert_eq!(data, &[1.5, 2.5, 1.5, 2.5]);
+        buf.insert_str(0, "# top\n");
+        arrays.shift(6);
+        let res = arrays.replace_by_binary(&buf, "DATA", &mut Vec::new());
+        assert!(res.starts_with("# top\n# begin\nplt.gca().plot()\nx=DATA[0:2]\n"));
+        arrays.clear();
+        assert_eq!(arrays.replace_by_binary(&buf, "DATA", &mut Vec::new()), buf);
+    }
+
+    #[test]
+    fn find_and_mask_non_finite_work() {
+        let mut buf = String::new();
+        let mut arrays = DataArrays::new();
+        vector_to_array(&mut buf, &mut arrays, "x", &[1.0, 2.0, 3.0]);
+        vector_to_array(&mut buf, &mut arrays, "y", &[1.0, f64::INFINITY, 3.0]);
+        generate_nested_list(&mut buf, &mut arrays, "values", &vec![vec![1.0, 2.0], vec![f64::NAN]]);
+        generate_nested_list_3(&mut buf, &mut arrays, "rgb", &vec![vec![vec![0.5, f64::NAN, 0.5]]]);
+        buf.push_str("plt.plot(x,y)\n");
+        assert_eq!(arrays.find_non_finite(), Some("y"));
+        let (res, masked) = arrays.mask_non_finite(&buf);
+        assert_eq!(
+            res,
+            "x=np.array([1,2,3,])\n\
+             y=np.array([1,inf,3,])\n\
+             y=np.ma.masked_invalid(y)\n\
+             values=[[1,2,],[NaN,],]\n\
+             values=[np.asarray(r)[np.isfinite(r)] for r in values]\n\
+             rgb=[[[0.5,NaN,0.5,],],]\n\
+             rgb=np.ma.masked_invalid(rgb)\n\
+             plt.plot(x,y)\n"
+        );
+        assert_eq!(
+            masked.replace_by_binary(&res, "DATA", &mut Vec::new()),
+            "x=DATA[0:3].astype(int)\n\
+             y=DATA[3:6]\n\
+             y=np.ma.masked_invalid(y)\n\
+             values=[[1,2,],[NaN,],]\n\
+             values=[np.asarray(r)[np.isfinite(r)] for r in values]\n\
+             rgb=[[[0.5,NaN,0.5,],],]\n\
+             rgb=np.ma.masked_invalid(rgb)\n\
+             plt.plot(x,y)\n"
+        );
+        let mut finite = DataArrays::new();
+        vector_to_array(&mut String::new(), &mut finite, "x", &[1.0, 2.0]);
+        assert_eq!(finite.find_non_finite(), None);
+    }
 }
@@ -1,6 +1,6 @@
-use super::{vector_to_array, AsVector, GraphMaker};
+use super::{quote, timestamps_to_dates, vector_to_array, AsTimestamp, AsVector, DataArrays, GraphMaker};
 use crate::quote_marker;
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Holds either the second point coordinates of a ray or the slope of the ray
@@ -33,10 +33,10 @@ pub enum RayEndpoint {
 /// ## Using methods to set the points
 ///
 /// ```
-/// use plotpy::{Curve, Plot, StrError};
+/// use plotpy::{Curve, Error, Plot};
 /// use std::f64::consts::PI;
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // configure curve
 ///     let mut curve = Curve::new();
 ///     curve.set_line_width(2.0);
@@ -70,9 +70,9 @@ pub enum RayEndpoint {
 /// ## Using Vector with point data
 ///
 /// ```
-/// use plotpy::{linspace, Curve, Plot, StrError};
+/// use plotpy::{linspace, Curve, Error, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // generate (x,y) points
 ///     let x = linspace(-1.0, 1.0, 21);
 ///     let y: Vec<_> = x.iter().map(|v| 1.0 / (1.0 + f64::exp(-5.0 * *v))).collect();
@@ -110,10 +110,10 @@ pub enum RayEndpoint {
 /// ## (twinx) Plot two vertical axes with different scales
 ///
 /// ```
-/// use plotpy::{linspace, Curve, Plot, StrError};
+/// use plotpy::{linspace, Curve, Error, Plot};
 /// use std::f64::consts::PI;
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data
 ///     let np = 201;
 ///     let mut x = vec![0.0; np];
@@ -173,6 +173,7 @@ pub struct Curve {
     stop_clip: bool,           // Stop clipping features within margins
     extra: String,             // Extra commands (comma separated)
     buffer: String,            // buffer
+    arrays: DataArrays,        // data arrays written to the buffer
 }
 
 impl Curve {
@@ -194,6 +195,7 @@ impl Curve {
             stop_clip: false,
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -285,23 +287,68 @@ impl Curve {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
         let opt = self.options();
         write!(&mut self.buffer, "plt.plot(x,y{})\n", &opt).unwrap();
     }
 
+    /// Draws curve with dates along the x-axis
+    ///
+    /// The x-axis is configured with the `AutoDateLocator` and `ConciseDateFormatter`.
+    /// See [AsTimestamp] and [crate::Plot::set_time_zone()].
+    ///
+    /// # Input
+    ///
+    /// * `dates` - timestamps (e.g., seconds since the Unix epoch or ISO-8601 strings)
+    /// * `y` - ordinate values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let dates = ["2024-03-15T08:00:00", "2024-03-15T12:00:00", "2024-03-15T16:00:00"];
+    ///     let mut curve = Curve::new();
+    ///     curve.set_marker_style("o").draw_dates(&dates, &[12.5, 18.0, 15.5]);
+    ///     let mut plot = Plot::new();
+    ///     plot.set_time_zone("Europe/Berlin").add(&curve).set_label_y("temperature");
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_curve_dates.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn draw_dates<'a, S, T, U>(&mut self, dates: &[S], y: &'a T)
+    where
+        S: AsTimestamp,
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
+    {
+        timestamps_to_dates(&mut self.buffer, "x", dates);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        let opt = self.options();
+        write!(
+            &mut self.buffer,
+            "plt.plot(x,y{})\nset_date_axis(plt.gca().xaxis)\n",
+            &opt
+        )
+        .unwrap();
+    }
+
     /// Draws curve on a previously drawn figure with the same x
     ///
     /// * `y` - ordinate values on the right-hand side
+    ///
+    /// **Note:** See also [crate::Plot::twin_x()], which returns a handle to the twin axes such that
+    /// its range, ticks, labels, and scale can be configured as any other axes.
     pub fn draw_with_twin_x<'a, T, U>(&mut self, y: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "y2", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y2", y);
         let opt = self.options();
         write!(
             &mut self.buffer,
@@ -324,11 +371,11 @@ impl Curve {
     pub fn draw_3d<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        vector_to_array(&mut self.buffer, "z", z);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "z", z);
         let opt = self.options();
         write!(&mut self.buffer, "ax3d().plot(x,y,z{})\n", &opt).unwrap();
     }
@@ -466,7 +513,7 @@ impl Curve {
 
         // label
         if self.label != "" {
-            write!(&mut opt, ",label=r'{}'", self.label).unwrap();
+            write!(&mut opt, ",label={}", quote(&self.label)).unwrap();
         }
 
         // lines
@@ -523,8 +570,12 @@ impl GraphMaker for Curve {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -632,6 +683,22 @@ mod tests {
         assert_eq!(curve.buffer, "");
     }
 
+    #[test]
+    fn draw_dates_works() {
+        let mut curve = Curve::new();
+        curve.draw_dates(&["2024-03-15", "2024-03-16T12:00:00Z"], &[1.0, 2.0]);
+        curve.draw_dates(&[1710460800_i64, 1710590400], &[1.0, 2.0]);
+        let b: &str = "x=to_dates([r'2024-03-15',r'2024-03-16T12:00:00Z',])\n\
+                       y=np.array([1,2,])\n\
+                       plt.plot(x,y)\n\
+                       set_date_axis(plt.gca().xaxis)\n\
+                       x=to_dates([1710460800,1710590400,])\n\
+                       y=np.array([1,2,])\n\
+                       plt.plot(x,y)\n\
+                       set_date_axis(plt.gca().xaxis)\n";
+        assert_eq!(curve.buffer, b);
+    }
+
     #[test]
     fn draw_3d_works() {
         let x = &[1.0, 2.0, 3.0, 4.0, 5.0];
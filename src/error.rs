@@ -0,0 +1,257 @@
+use super::StrError;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Defines the errors returned by Plot and related functions
+///
+/// The Python output (stdout and stderr combined) is also written to the log file
+/// next to the figure (same name as the figure, but with the `.log` extension).
+#[derive(Debug)]
+pub enum Error {
+    /// The Python interpreter could not be started (e.g., it is not installed)
+    Spawn {
+        /// The Python executable
+        python_exe: String,
+        /// The underlying error
+        source: io::Error,
+    },
+
+    /// Python exited with a non-zero status, but without raising an exception
+    Exit {
+        /// The exit code (None if terminated by a signal)
+        code: Option<i32>,
+        /// The combined stdout and stderr
+        output: String,
+    },
+
+    /// Python did not finish within the time limit and has been killed
+    ///
+    /// See [crate::Plot::set_timeout()].
+    Timeout {
+        /// The time limit
+        timeout: Duration,
+        /// The combined stdout and stderr written before the process was killed
+        output: String,
+    },
+
+    /// Python raised an exception; e.g., an error in Matplotlib
+    Python {
+        /// The exception type; e.g., `ValueError`
+        kind: String,
+        /// The exception message
+        message: String,
+        /// The complete traceback text
+        traceback: String,
+    },
+
+    /// Python wrote to stdout or stderr (e.g., warnings) although it has not failed
+    ///
+    /// This error is only returned in strict mode (see [crate::Plot::set_strict()]).
+    Output {
+        /// The combined stdout and stderr
+        output: String,
+    },
+
+    /// The Python environment does not support a feature used by the plot
+    ///
+    /// This error is only returned if the environment is given (see [crate::Plot::set_python_env()]).
+    Unsupported {
+        /// The feature; e.g., `set_equal_axes in 3D`
+        feature: String,
+        /// The requirement; e.g., `matplotlib >= 3.3`
+        requirement: String,
+    },
+
+    /// A data array has non-finite values (NaN or ±inf)
+    ///
+    /// This error is only returned if the [crate::NonFinite::Reject] policy is set (see [crate::Plot::set_non_finite()]).
+    NonFinite {
+        /// The name of the array in the Python script; e.g., `x`
+        name: String,
+    },
+
+    /// The axes limits are not finite (NaN or ±inf)
+    ///
+    /// This error is only returned if the [crate::NonFinite::Reject] policy is set (see [crate::Plot::set_non_finite()]).
+    NonFiniteLimits {
+        /// The function of [crate::Plot] setting the limits; e.g., `set_range`
+        function: String,
+    },
+
+    /// A rendered image differs from the baseline image (see [crate::testing::compare_png()])
+    ///
+    /// Requires the `testing` feature.
+    #[cfg(feature = "testing")]
+    ImageMismatch {
+        /// The root-mean-square difference of the pixel values (NaN if the sizes differ)
+        rms: f64,
+        /// The tolerance
+        tolerance: f64,
+        /// The message from Matplotlib (empty if the sizes match)
+        message: String,
+    },
+
+    /// Reading or writing a file failed (e.g., the script, the log file, or the figure)
+    Io {
+        /// The path of the file or directory
+        path: PathBuf,
+        /// The underlying error
+        source: io::Error,
+    },
+
+    /// The input arguments are invalid
+    InvalidInput(&'static str),
+}
+
+impl Error {
+    /// Returns a closure that converts an io::Error into Error::Io with the given path
+    pub(crate) fn io(path: &Path) -> impl FnOnce(io::Error) -> Error + '_ {
+        move |source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Creates an error from the output of Python
+    ///
+    /// Returns [Error::Python] if the output contains a traceback, [Error::Exit] if the
+    /// exit status indicates failure, or [Error::Output] otherwise.
+    pub(crate) fn from_python_output(success: bool, code: Option<i32>, output: &str) -> Error {
+        const TRACEBACK: &str = "Traceback (most recent call last):";
+        if let Some(start) = output.rfind(TRACEBACK) {
+            let traceback = output[start..].trim_end();
+            let last = traceback
+                .lines()
+                .skip(1)
+                .filter(|l| !l.is_empty() && !l.starts_with(char::is_whitespace))
+                .last()
+                .unwrap_or("");
+            let (kind, message) = match last.split_once(": ") {
+                Some((kind, message)) => (kind, message),
+                None => (last.trim_end_matches(':'), ""),
+            };
+            return Error::Python {
+                kind: kind.to_string(),
+                message: message.to_string(),
+                traceback: traceback.to_string(),
+            };
+        }
+        if success {
+            Error::Output {
+                output: output.to_string(),
+            }
+        } else {
+            Error::Exit {
+                code,
+                output: output.to_string(),
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Spawn { python_exe, source } => write!(f, "cannot run {}: {}", python_exe, source),
+            Error::Exit { code: Some(code), .. } => write!(f, "python3 failed with exit code {}", code),
+            Error::Exit { code: None, .. } => write!(f, "python3 was terminated by a signal"),
+            Error::Timeout { timeout, .. } => write!(f, "python3 timed out after {} s", timeout.as_secs_f64()),
+            Error::Python { kind, message, .. } => write!(f, "python3 raised {}: {}", kind, message),
+            Error::Output { .. } => write!(f, "python3 issued warnings; please see the log file"),
+            Error::Unsupported { feature, requirement } => {
+                write!(f, "{} is not supported: it requires {}", feature, requirement)
+            }
+            Error::NonFinite { name } => write!(f, "the data array {} has non-finite values (NaN or ±inf)", name),
+            Error::NonFiniteLimits { function } => {
+                write!(f, "the axes limits given to {} are not finite (NaN or ±inf)", function)
+            }
+            #[cfg(feature = "testing")]
+            Error::ImageMismatch {
+                rms,
+                tolerance,
+                message,
+            } => {
+                if rms.is_nan() {
+                    write!(f, "images differ: {}", message)
+                } else {
+                    write!(f, "images differ: RMS = {} > tolerance = {}", rms, tolerance)
+                }
+            }
+            Error::Io { path, source } => write!(f, "cannot access {}: {}", path.display(), source),
+            Error::InvalidInput(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Spawn { source, .. } | Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<StrError> for Error {
+    fn from(message: StrError) -> Self {
+        Error::InvalidInput(message)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use std::path::Path;
+
+    #[test]
+    fn from_python_output_captures_exceptions() {
+        let output = "some warning\n\
+                      Traceback (most recent call last):\n\
+                      \x20\x20File \"/tmp/plotpy/a.py\", line 9, in <module>\n\
+                      \x20\x20\x20\x20plt.subplot(1,1,0)\n\
+                      ValueError: num must be an integer with 1 <= num <= 1, not 0\n";
+        match Error::from_python_output(false, Some(1), output) {
+            Error::Python {
+                kind,
+                message,
+                traceback,
+            } => {
+                assert_eq!(kind, "ValueError");
+                assert_eq!(message, "num must be an integer with 1 <= num <= 1, not 0");
+                assert!(traceback.starts_with("Traceback"));
+                assert!(traceback.ends_with("not 0"));
+            }
+            _ => panic!("Python error expected"),
+        }
+        let err = Error::from_python_output(
+            false,
+            Some(1),
+            "Traceback (most recent call last):\nKeyboardInterrupt\n",
+        );
+        assert_eq!(err.to_string(), "python3 raised KeyboardInterrupt: ");
+    }
+
+    #[test]
+    fn from_python_output_captures_other_failures() {
+        let err = Error::from_python_output(false, Some(2), "cannot open file");
+        assert_eq!(err.to_string(), "python3 failed with exit code 2");
+        let err = Error::from_python_output(false, None, "");
+        assert_eq!(err.to_string(), "python3 was terminated by a signal");
+        let err = Error::from_python_output(true, Some(0), "UserWarning: hello");
+        assert_eq!(err.to_string(), "python3 issued warnings; please see the log file");
+    }
+
+    #[test]
+    fn display_and_conversion_work() {
+        let err: Error = "ndim must be 2 or 3".into();
+        assert_eq!(err.to_string(), "ndim must be 2 or 3");
+        let source = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+        let err = Error::io(Path::new("/tmp/plotpy/a.py"))(source);
+        assert_eq!(err.to_string(), "cannot access /tmp/plotpy/a.py: not found");
+        assert!(std::error::Error::source(&err).is_some());
+    }
+}
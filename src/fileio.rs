@@ -1,43 +1,129 @@
-use super::StrError;
+use super::Error;
+use std::env;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use std::process::Command;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
-/// Writes a python file and call python3 on it
+/// Writes a python file
 ///
 /// # Arguments
 ///
 /// * `python_commands` - Python commands to be written to file
-/// * `output_dir` - Output directory to be created
-/// * `filename_py` - Filename with extension .py
-pub(crate) fn call_python3(python_exe: &str, python_commands: &String, path: &Path) -> Result<String, StrError> {
+/// * `path` - Path of the file with extension .py (the directory is created if needed)
+pub(crate) fn write_python_file(python_commands: &str, path: &Path) -> Result<(), Error> {
     // create directory
     if let Some(p) = path.parent() {
-        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        fs::create_dir_all(p).map_err(Error::io(p))?;
     }
 
-    // combine header with commands
-    let mut contents = String::new();
-    contents.push_str(python_commands);
+    // write file
+    let mut file = File::create(path).map_err(Error::io(path))?;
+    file.write_all(python_commands.as_bytes()).map_err(Error::io(path))?;
+
+    // force sync
+    file.sync_all().map_err(Error::io(path))?;
+    Ok(())
+}
+
+/// Writes a 1D array of f64 to a NumPy (.npy) binary file
+///
+/// The values are written in little-endian order; thus, they are recovered bit-exactly by `np.load`.
+///
+/// See <https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html>
+///
+/// # Arguments
+///
+/// * `data` - The values
+/// * `path` - Path of the file with extension .npy (the directory is created if needed)
+pub(crate) fn write_npy_file(data: &[f64], path: &Path) -> Result<(), Error> {
+    // create directory
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(Error::io(p))?;
+    }
+
+    // header (padded with spaces such that the data starts at a multiple of 64 bytes)
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({},), }}",
+        data.len()
+    );
+    let preamble_len = 10; // magic string (6) + version (2) + header length (2)
+    let total = preamble_len + header.len() + 1;
+    let padding = (64 - total % 64) % 64;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    // contents
+    let mut contents: Vec<u8> = Vec::with_capacity(preamble_len + header.len() + 8 * data.len());
+    contents.extend_from_slice(b"\x93NUMPY\x01\x00");
+    contents.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    contents.extend_from_slice(header.as_bytes());
+    for value in data {
+        contents.extend_from_slice(&value.to_le_bytes());
+    }
 
     // write file
-    let mut file = File::create(path).map_err(|_| "cannot create file")?;
-    file.write_all(contents.as_bytes()).map_err(|_| "cannot write file")?;
+    let mut file = File::create(path).map_err(Error::io(path))?;
+    file.write_all(&contents).map_err(Error::io(path))?;
 
     // force sync
-    file.sync_all().map_err(|_| "cannot sync file")?;
+    file.sync_all().map_err(Error::io(path))?;
+    Ok(())
+}
+
+/// Holds the results of running a python script
+pub(crate) struct PythonOutput {
+    /// Python exited successfully
+    pub success: bool,
+
+    /// The exit code (None if terminated by a signal)
+    pub code: Option<i32>,
+
+    /// The combined stdout and stderr
+    pub output: String,
+}
+
+/// Holds the options to run the python3 subprocess
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProcessOptions {
+    /// Wall-clock time after which the process is killed (None means no limit)
+    pub timeout: Option<Duration>,
+
+    /// Extra environment variables (the other ones are inherited)
+    pub env: Vec<(String, String)>,
+
+    /// Working directory (None means the current directory)
+    pub working_dir: Option<PathBuf>,
+}
+
+/// Writes a python file and call python3 on it
+///
+/// # Arguments
+///
+/// * `python_exe` - The Python executable
+/// * `python_commands` - Python commands to be written to file
+/// * `path` - Path of the file with extension .py (the directory is created if needed)
+/// * `options` - Timeout, environment, and working directory
+pub(crate) fn call_python3(
+    python_exe: &str,
+    python_commands: &String,
+    path: &Path,
+    options: &ProcessOptions,
+) -> Result<PythonOutput, Error> {
+    // write file
+    write_python_file(python_commands, path)?;
 
     // execute file
-    let output = Command::new(python_exe)
-        .arg(path)
-        .output()
-        .map_err(|_| "cannot run python3")?;
+    let mut command = Command::new(python_exe);
+    command.arg(absolute_path(path, options));
+    let (status, stdout, stderr) = execute(python_exe, command, None, options)?;
 
     // results
-    let out = String::from_utf8(output.stdout).unwrap();
-    let err = String::from_utf8(output.stderr).unwrap();
+    let out = String::from_utf8_lossy(&stdout);
+    let err = String::from_utf8_lossy(&stderr);
     let mut results = String::new();
     if out.len() > 0 {
         results.push_str(&out);
@@ -47,16 +133,167 @@ pub(crate) fn call_python3(python_exe: &str, python_commands: &String, path: &Pa
     }
 
     // done
-    Ok(results)
+    Ok(PythonOutput {
+        success: status.success(),
+        code: status.code(),
+        output: results,
+    })
+}
+
+/// Calls python3 and captures its stdout separately
+///
+/// The stdout is returned as raw bytes (e.g., the binary data of a figure) and only the
+/// stderr is returned in [PythonOutput::output].
+///
+/// # Arguments
+///
+/// * `python_exe` - The Python executable
+/// * `python_commands` - Python commands
+/// * `path` - If not None, the commands are written to this file (with extension .py);
+///   otherwise, the commands are passed via the stdin of python3 and no file is written.
+/// * `options` - Timeout, environment, and working directory
+pub(crate) fn call_python3_capture(
+    python_exe: &str,
+    python_commands: &str,
+    path: Option<&Path>,
+    options: &ProcessOptions,
+) -> Result<(PythonOutput, Vec<u8>), Error> {
+    // command
+    let mut command = Command::new(python_exe);
+    let input = match path {
+        Some(p) => {
+            write_python_file(python_commands, p)?;
+            command.arg(absolute_path(p, options));
+            None
+        }
+        None => {
+            command.arg("-");
+            Some(python_commands.as_bytes().to_vec())
+        }
+    };
+
+    // execute
+    let (status, stdout, stderr) = execute(python_exe, command, input, options)?;
+
+    // done
+    Ok((
+        PythonOutput {
+            success: status.success(),
+            code: status.code(),
+            output: String::from_utf8_lossy(&stderr).to_string(),
+        },
+        stdout,
+    ))
+}
+
+/// Returns the absolute path if a working directory is given (otherwise, returns the path as is)
+///
+/// Thus, the paths given to Plot are relative to the directory of the Rust program, not the
+/// working directory of python3. The path is returned as is if the current directory is unavailable.
+pub(crate) fn absolute_path(path: &Path, options: &ProcessOptions) -> PathBuf {
+    if options.working_dir.is_none() || path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match env::current_dir() {
+        Ok(current) => current.join(path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Spawns the command, feeds the stdin, and collects the stdout and stderr
+///
+/// The pipes are handled by separate threads; thus, the process can be killed if the timeout is reached.
+fn execute(
+    python_exe: &str,
+    mut command: Command,
+    input: Option<Vec<u8>>,
+    options: &ProcessOptions,
+) -> Result<(ExitStatus, Vec<u8>, Vec<u8>), Error> {
+    // configure
+    for (key, value) in &options.env {
+        command.env(key, value);
+    }
+    if let Some(dir) = &options.working_dir {
+        command.current_dir(dir);
+    }
+    let stdin = if input.is_some() { Stdio::piped() } else { Stdio::null() };
+
+    // spawn
+    let spawn_error = |source| Error::Spawn {
+        python_exe: python_exe.to_string(),
+        source,
+    };
+    let mut child = command
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_error)?;
+
+    // handle pipes (a failure to write stdin is reported by python3 itself)
+    let writer = match (child.stdin.take(), input) {
+        (Some(mut pipe), Some(data)) => Some(thread::spawn(move || {
+            let _ = pipe.write_all(&data);
+        })),
+        _ => None,
+    };
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    // wait
+    let status = match options.timeout {
+        None => child.wait().map_err(spawn_error)?,
+        Some(timeout) => {
+            let start = Instant::now();
+            loop {
+                if let Some(status) = child.try_wait().map_err(spawn_error)? {
+                    break status;
+                }
+                if start.elapsed() >= timeout {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    let out = stdout.join().unwrap_or_default();
+                    let err = stderr.join().unwrap_or_default();
+                    return Err(Error::Timeout {
+                        timeout,
+                        output: String::from_utf8_lossy(&out).to_string() + &String::from_utf8_lossy(&err),
+                    });
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+    };
+
+    // done
+    if let Some(handle) = writer {
+        let _ = handle.join();
+    }
+    let out = stdout.join().unwrap_or_default();
+    let err = stderr.join().unwrap_or_default();
+    Ok((status, out, err))
+}
+
+/// Reads a pipe until the end in a separate thread
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut data = Vec::new();
+        if let Some(mut p) = pipe {
+            let _ = p.read_to_end(&mut data);
+        }
+        data
+    })
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::call_python3;
+    use super::{call_python3, call_python3_capture, write_npy_file, ProcessOptions};
+    use crate::Error;
+    use std::env;
     use std::fs;
     use std::path::Path;
+    use std::time::{Duration, Instant};
 
     const OUT_DIR: &str = "/tmp/plotpy/unit_tests";
 
@@ -64,22 +301,22 @@ mod tests {
     fn call_python3_works() {
         let commands = "print(\"Python says: Hello World!\")".to_string();
         let path = Path::new("call_python3_works.py");
-        let output = call_python3("python3", &commands, &path).unwrap();
+        let output = call_python3("python3", &commands, &path, &ProcessOptions::default()).unwrap();
         let data = fs::read_to_string(&path).map_err(|_| "cannot read test file").unwrap();
         let correct = "print(\"Python says: Hello World!\")".to_string();
         assert_eq!(data, correct);
-        assert_eq!(output, "Python says: Hello World!\n");
+        assert_eq!(output.output, "Python says: Hello World!\n");
     }
 
     #[test]
     fn call_python3_create_dir_works() {
         let commands = "print(\"Python says: Hello World!\")".to_string();
         let path = Path::new(OUT_DIR).join("call_python3_works.py");
-        let output = call_python3("python3", &commands, &path).unwrap();
+        let output = call_python3("python3", &commands, &path, &ProcessOptions::default()).unwrap();
         let data = fs::read_to_string(&path).map_err(|_| "cannot read test file").unwrap();
         let correct = "print(\"Python says: Hello World!\")".to_string();
         assert_eq!(data, correct);
-        assert_eq!(output, "Python says: Hello World!\n");
+        assert_eq!(output.output, "Python says: Hello World!\n");
     }
 
     #[test]
@@ -87,17 +324,102 @@ mod tests {
         let path = Path::new(OUT_DIR).join("call_python3_twice_works.py");
         // first
         let commands_first = "print(\"Python says: Hello World!\")".to_string();
-        let output_first = call_python3("python3", &commands_first, &path).unwrap();
+        let output_first = call_python3("python3", &commands_first, &path, &ProcessOptions::default()).unwrap();
         let data_first = fs::read_to_string(&path).map_err(|_| "cannot read test file").unwrap();
         let correct_first = "print(\"Python says: Hello World!\")".to_string();
         assert_eq!(data_first, correct_first);
-        assert_eq!(output_first, "Python says: Hello World!\n");
+        assert_eq!(output_first.output, "Python says: Hello World!\n");
         // second
         let commands_second = "print(\"Python says: Hello World! again\")".to_string();
-        let output_second = call_python3("python3", &commands_second, &path).unwrap();
+        let output_second = call_python3("python3", &commands_second, &path, &ProcessOptions::default()).unwrap();
         let data_second = fs::read_to_string(&path).map_err(|_| "cannot read test file").unwrap();
         let correct_second = "print(\"Python says: Hello World! again\")".to_string();
         assert_eq!(data_second, correct_second);
-        assert_eq!(output_second, "Python says: Hello World! again\n");
+        assert_eq!(output_second.output, "Python says: Hello World! again\n");
+    }
+
+    #[test]
+    fn call_python3_captures_errors() {
+        let commands = "import sys\nsys.exit(3)".to_string();
+        let path = Path::new(OUT_DIR).join("call_python3_captures_errors.py");
+        let output = call_python3("python3", &commands, &path, &ProcessOptions::default()).unwrap();
+        assert_eq!(output.success, false);
+        assert_eq!(output.code, Some(3));
+        let err = call_python3("__wrong_python_exe__", &commands, &path, &ProcessOptions::default())
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("cannot run __wrong_python_exe__"));
+    }
+
+    #[test]
+    fn call_python3_capture_works() {
+        let commands = "import sys\nsys.stdout.buffer.write(bytes([0,159,255]))\nprint('note', file=sys.stderr)";
+        let (result, data) = call_python3_capture("python3", commands, None, &ProcessOptions::default()).unwrap();
+        assert_eq!(result.success, true);
+        assert_eq!(result.output, "note\n");
+        assert_eq!(data, &[0, 159, 255]);
+
+        let path = Path::new(OUT_DIR).join("call_python3_capture_works.py");
+        let (result, data) =
+            call_python3_capture("python3", commands, Some(&path), &ProcessOptions::default()).unwrap();
+        assert_eq!(result.success, true);
+        assert_eq!(data, &[0, 159, 255]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), commands);
+    }
+
+    #[test]
+    fn call_python3_handles_timeout() {
+        let commands = "import time\nprint('started', flush=True)\ntime.sleep(10)".to_string();
+        let path = Path::new(OUT_DIR).join("call_python3_handles_timeout.py");
+        let options = ProcessOptions {
+            timeout: Some(Duration::from_millis(500)),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let err = call_python3("python3", &commands, &path, &options).err().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(err.to_string(), "python3 timed out after 0.5 s");
+        match err {
+            Error::Timeout { output, .. } => assert_eq!(output, "started\n"),
+            _ => panic!("Timeout error expected"),
+        }
+        let commands = "print('fast')".to_string();
+        let output = call_python3("python3", &commands, &path, &options).unwrap();
+        assert_eq!(output.output, "fast\n");
+    }
+
+    #[test]
+    fn call_python3_handles_env_and_working_dir() {
+        let commands = "import os\nprint(os.environ['PLOTPY_TEST'], os.getcwd())";
+        let path = Path::new(OUT_DIR).join("call_python3_handles_env_and_working_dir.py");
+        let options = ProcessOptions {
+            env: vec![("PLOTPY_TEST".to_string(), "hello".to_string())],
+            working_dir: Some(env::temp_dir()),
+            ..Default::default()
+        };
+        let output = call_python3("python3", &commands.to_string(), &path, &options).unwrap();
+        let dir = env::temp_dir().canonicalize().unwrap();
+        assert_eq!(output.output, format!("hello {}\n", dir.display()));
+        let (result, _) = call_python3_capture("python3", commands, None, &options).unwrap();
+        assert_eq!(result.success, true);
+    }
+
+    #[test]
+    fn write_npy_file_works() {
+        let path = Path::new(OUT_DIR).join("write_npy_file_works.npy");
+        write_npy_file(&[0.1, -2.0, f64::NAN], &path).unwrap();
+        let data = fs::read(&path).map_err(|_| "cannot read test file").unwrap();
+        assert_eq!(data.len(), 128 + 3 * 8);
+        assert_eq!(&data[0..8], b"\x93NUMPY\x01\x00");
+        assert_eq!(u16::from_le_bytes([data[8], data[9]]), 118);
+        let header = String::from_utf8(data[10..128].to_vec()).unwrap();
+        assert_eq!(
+            header.trim_end(),
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (3,), }"
+        );
+        assert_eq!(header.chars().last(), Some('\n'));
+        assert_eq!(&data[128..136], &0.1_f64.to_le_bytes());
+        assert_eq!(&data[136..144], &(-2.0_f64).to_le_bytes());
+        assert!(f64::from_le_bytes(data[144..152].try_into().unwrap()).is_nan());
     }
 }
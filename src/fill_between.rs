@@ -1,5 +1,5 @@
-use super::{vector_to_array, AsVector, GraphMaker};
-use num_traits::Num;
+use super::{timestamps_to_dates, vector_to_array, AsTimestamp, AsVector, DataArrays, GraphMaker};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Fills the area between two curves
@@ -7,9 +7,9 @@ use std::fmt::Write;
 /// # Examples
 ///
 /// ```
-/// use plotpy::{Curve, FillBetween, Plot, StrError, linspace};
+/// use plotpy::{linspace, Curve, Error, FillBetween, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data and curve
 ///     let x = linspace(-1.0, 2.0, 21);
 ///     let y: Vec<_> = x.iter().map(|&x| x * x).collect();
@@ -38,6 +38,7 @@ pub struct FillBetween {
     interpolate: bool,
     extra: String,
     buffer: String,
+    arrays: DataArrays,
 }
 
 impl FillBetween {
@@ -49,6 +50,7 @@ impl FillBetween {
             interpolate: false,
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -61,14 +63,14 @@ impl FillBetween {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y1: &'a T, y2: Option<&'a T>)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
         let opt = self.options();
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y1", y1);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y1", y1);
         match y2 {
             Some(y2) => {
-                vector_to_array(&mut self.buffer, "y2", y2);
+                vector_to_array(&mut self.buffer, &mut self.arrays, "y2", y2);
                 write!(&mut self.buffer, "plt.fill_between(x,y1,y2{})\n", &opt).unwrap();
             }
             None => {
@@ -77,6 +79,35 @@ impl FillBetween {
         }
     }
 
+    /// Draws the filled area between two curves with dates along the x-axis
+    ///
+    /// The x-axis is configured with the `AutoDateLocator` and `ConciseDateFormatter`.
+    /// See [AsTimestamp] and [crate::Plot::set_time_zone()].
+    ///
+    /// * `dates` - timestamps (e.g., seconds since the Unix epoch or ISO-8601 strings)
+    /// * `y1` - y values of the first curve
+    /// * `y2` - optional y values of the second curve. If None, fills area between y1 and x-axis
+    pub fn draw_dates<'a, S, T, U>(&mut self, dates: &[S], y1: &'a T, y2: Option<&'a T>)
+    where
+        S: AsTimestamp,
+        T: AsVector<'a, U>,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
+    {
+        let opt = self.options();
+        timestamps_to_dates(&mut self.buffer, "x", dates);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y1", y1);
+        match y2 {
+            Some(y2) => {
+                vector_to_array(&mut self.buffer, &mut self.arrays, "y2", y2);
+                write!(&mut self.buffer, "plt.fill_between(x,y1,y2{})\n", &opt).unwrap();
+            }
+            None => {
+                write!(&mut self.buffer, "plt.fill_between(x,y1{})\n", &opt).unwrap();
+            }
+        }
+        self.buffer.push_str("set_date_axis(plt.gca().xaxis)\n");
+    }
+
     /// Sets the condition to select the area to be filled.
     ///
     /// For example: "y2>=y1" or "y2<=y1"
@@ -150,8 +181,12 @@ impl GraphMaker for FillBetween {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -170,4 +205,21 @@ mod tests {
         assert_eq!(fill_between.extra, "");
         assert_eq!(fill_between.buffer.len(), 0);
     }
+
+    #[test]
+    fn draw_dates_works() {
+        let mut fill_between = FillBetween::new();
+        fill_between.draw_dates(&["2024-03-15", "2024-03-16"], &[1.0, 2.0], None);
+        fill_between.draw_dates(&["2024-03-15", "2024-03-16"], &[1.0, 2.0], Some(&[3.0, 4.0]));
+        let b: &str = "x=to_dates([r'2024-03-15',r'2024-03-16',])\n\
+                       y1=np.array([1,2,])\n\
+                       plt.fill_between(x,y1)\n\
+                       set_date_axis(plt.gca().xaxis)\n\
+                       x=to_dates([r'2024-03-15',r'2024-03-16',])\n\
+                       y1=np.array([1,2,])\n\
+                       y2=np.array([3,4,])\n\
+                       plt.fill_between(x,y1,y2)\n\
+                       set_date_axis(plt.gca().xaxis)\n";
+        assert_eq!(fill_between.buffer, b);
+    }
 }
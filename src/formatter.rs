@@ -0,0 +1,228 @@
+use super::{generate_list_quoted, quote, StrError};
+use std::fmt::Write;
+
+/// Defines the labels of the ticks along an axis
+///
+/// See [crate::Plot::set_formatter_x()] and [crate::Contour::set_colorbar_formatter()] (and related functions).
+///
+/// [See Matplotlib's documentation](https://matplotlib.org/stable/api/ticker_api.html)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Formatter {
+    /// Writes the values with engineering prefixes (e.g., `1.5 kHz`, `10 MHz`, or `2 µs`)
+    Engineering {
+        /// The unit after the prefix (may be empty)
+        unit: String,
+        /// The number of digits after the decimal point (chosen automatically if None)
+        places: Option<usize>,
+    },
+
+    /// Writes the values as percentages of `xmax`; e.g., `xmax = 1.0` shows 0.25 as `25%`
+    Percent {
+        /// The value corresponding to 100% (positive)
+        xmax: f64,
+        /// The number of digits after the decimal point (chosen automatically if None)
+        decimals: Option<usize>,
+    },
+
+    /// Writes the values in scientific notation when they are outside the power limits
+    Scientific {
+        /// Uses an offset for the values (e.g., `1e3 + ...`) when it improves the labels
+        use_offset: bool,
+        /// The values within `10^min` and `10^max` are written without a power of ten; e.g., `(-3, 4)`
+        power_limits: (i32, i32),
+        /// Writes the power of ten with mathtext (e.g., `×10^3`) instead of `1e3`
+        math_text: bool,
+    },
+
+    /// Writes the values with a fixed number of digits after the decimal point
+    Decimals(usize),
+
+    /// Writes the given labels in the order of the ticks
+    ///
+    /// The ticks should be placed with [crate::Locator::Fixed] at the positions of the labels.
+    /// See [Formatter::from_fn()].
+    Labels(Vec<String>),
+}
+
+impl Formatter {
+    /// Returns the labels computed by a function at the given tick positions
+    ///
+    /// Use this formatter together with [crate::Locator::Fixed] and the same positions;
+    /// e.g., via [crate::Plot::set_ticks_x_fn()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::Formatter;
+    ///
+    /// let formatter = Formatter::from_fn(&[0.0, 0.5, 1.0], |x| format!("{}h", x * 24.0));
+    /// assert_eq!(formatter, Formatter::Labels(vec!["0h".into(), "12h".into(), "24h".into()]));
+    /// ```
+    pub fn from_fn<F>(positions: &[f64], f: F) -> Self
+    where
+        F: Fn(f64) -> String,
+    {
+        Formatter::Labels(positions.iter().map(|x| f(*x)).collect())
+    }
+
+    /// Returns the Python commands creating the formatter with the given variable name
+    pub(crate) fn commands(&self, name: &str) -> Result<String, StrError> {
+        let mut res = String::new();
+        match self {
+            Formatter::Engineering { unit, places } => {
+                write!(&mut res, "{}=tck.EngFormatter(unit={}", name, quote(unit)).unwrap();
+                if let Some(p) = places {
+                    write!(&mut res, ",places={}", p).unwrap();
+                }
+                res.push_str(")\n");
+            }
+            Formatter::Percent { xmax, decimals } => {
+                if !(xmax.is_finite() && *xmax > 0.0) {
+                    return Err("the xmax of the Percent formatter must be positive");
+                }
+                write!(&mut res, "{}=tck.PercentFormatter(xmax={}", name, xmax).unwrap();
+                if let Some(d) = decimals {
+                    write!(&mut res, ",decimals={}", d).unwrap();
+                }
+                res.push_str(")\n");
+            }
+            Formatter::Scientific {
+                use_offset,
+                power_limits,
+                math_text,
+            } => {
+                if power_limits.0 > power_limits.1 {
+                    return Err("the power limits of the Scientific formatter must be given as (min, max)");
+                }
+                let use_offset = if *use_offset { "True" } else { "False" };
+                let math_text = if *math_text { "True" } else { "False" };
+                write!(
+                    &mut res,
+                    "{}=tck.ScalarFormatter(useOffset={},useMathText={})\n\
+                     {}.set_powerlimits(({},{}))\n",
+                    name, use_offset, math_text, name, power_limits.0, power_limits.1
+                )
+                .unwrap();
+            }
+            Formatter::Decimals(decimals) => {
+                write!(
+                    &mut res,
+                    "{}=tck.StrMethodFormatter({})\n",
+                    name,
+                    quote(&format!("{{x:.{}f}}", decimals))
+                )
+                .unwrap();
+            }
+            Formatter::Labels(labels) => {
+                generate_list_quoted(&mut res, "labels", labels);
+                write!(&mut res, "{}=tck.FixedFormatter(labels)\n", name).unwrap();
+            }
+        }
+        Ok(res)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Formatter;
+
+    #[test]
+    fn derive_works() {
+        let formatter = Formatter::Decimals(2);
+        assert_eq!(formatter.clone(), formatter);
+        assert_eq!(format!("{:?}", formatter), "Decimals(2)");
+    }
+
+    #[test]
+    fn from_fn_works() {
+        let formatter = Formatter::from_fn(&[1.0, 2.0], |x| format!("#{}", x));
+        assert_eq!(formatter, Formatter::Labels(vec!["#1".to_string(), "#2".to_string()]));
+    }
+
+    #[test]
+    fn commands_work() {
+        assert_eq!(
+            Formatter::Engineering {
+                unit: "Hz".to_string(),
+                places: None
+            }
+            .commands("fmt")
+            .unwrap(),
+            "fmt=tck.EngFormatter(unit=r'Hz')\n"
+        );
+        assert_eq!(
+            Formatter::Engineering {
+                unit: String::new(),
+                places: Some(1)
+            }
+            .commands("fmt")
+            .unwrap(),
+            "fmt=tck.EngFormatter(unit=r'',places=1)\n"
+        );
+        assert_eq!(
+            Formatter::Percent {
+                xmax: 1.0,
+                decimals: None
+            }
+            .commands("fmt")
+            .unwrap(),
+            "fmt=tck.PercentFormatter(xmax=1)\n"
+        );
+        assert_eq!(
+            Formatter::Percent {
+                xmax: 100.0,
+                decimals: Some(0)
+            }
+            .commands("fmt")
+            .unwrap(),
+            "fmt=tck.PercentFormatter(xmax=100,decimals=0)\n"
+        );
+        assert_eq!(
+            Formatter::Scientific {
+                use_offset: false,
+                power_limits: (-2, 3),
+                math_text: true
+            }
+            .commands("fmt")
+            .unwrap(),
+            "fmt=tck.ScalarFormatter(useOffset=False,useMathText=True)\n\
+             fmt.set_powerlimits((-2,3))\n"
+        );
+        assert_eq!(
+            Formatter::Decimals(3).commands("fmt").unwrap(),
+            "fmt=tck.StrMethodFormatter(r'{x:.3f}')\n"
+        );
+        assert_eq!(
+            Formatter::Labels(vec!["low".to_string(), "high".to_string()])
+                .commands("fmt")
+                .unwrap(),
+            "labels=[r'low',r'high',]\n\
+             fmt=tck.FixedFormatter(labels)\n"
+        );
+    }
+
+    #[test]
+    fn commands_capture_errors() {
+        assert_eq!(
+            Formatter::Percent {
+                xmax: 0.0,
+                decimals: None
+            }
+            .commands("fmt")
+            .err(),
+            Some("the xmax of the Percent formatter must be positive")
+        );
+        assert_eq!(
+            Formatter::Scientific {
+                use_offset: true,
+                power_limits: (3, -3),
+                math_text: false
+            }
+            .commands("fmt")
+            .err(),
+            Some("the power limits of the Scientific formatter must be given as (min, max)")
+        );
+    }
+}
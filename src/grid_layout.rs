@@ -0,0 +1,403 @@
+use super::StrError;
+use std::fmt::Write;
+use std::ops::{Bound, Range, RangeBounds};
+
+/// Defines a grid for subplots spanning several cells (a typed GridSpec)
+///
+/// The layout is given to [crate::Plot::grid_axes()], which creates an axes spanning the cells
+/// addressed by Rust ranges, or to [crate::Plot::subplot_mosaic()] if the layout is created from
+/// an ASCII mosaic (see [GridLayout::from_mosaic()]). The layout is checked by these functions.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{Curve, Error, GridLayout, Plot};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut curve = Curve::new();
+///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+///
+///     // a wide axes on top of two axes
+///     let mut layout = GridLayout::new(2, 2);
+///     layout.set_height_ratios(&[2.0, 1.0]).set_hspace(0.4);
+///     let mut plot = Plot::new();
+///     let top = plot.grid_axes(&layout, 0..1, 0..2)?;
+///     let left = plot.grid_axes(&layout, 1..2, 0..1)?;
+///     let right = plot.grid_axes(&layout, 1..2, 1..)?;
+///     plot.add_to(top, &curve).set_title("top");
+///     plot.add_to(right, &curve).set_title("right");
+///     plot.set_axes(left).set_title("left");
+///     plot.save("/tmp/plotpy/doc_tests/doc_grid_layout.svg")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridLayout {
+    nrow: usize,                                     // number of rows
+    ncol: usize,                                     // number of columns
+    width_ratios: Vec<f64>,                          // relative widths of the columns
+    height_ratios: Vec<f64>,                         // relative heights of the rows
+    wspace: Option<f64>,                             // horizontal space between columns (fraction of the average width)
+    hspace: Option<f64>,                             // vertical space between rows (fraction of the average height)
+    panels: Vec<(char, Range<usize>, Range<usize>)>, // named cells given by a mosaic (name, rows, columns)
+}
+
+impl GridLayout {
+    /// Allocates a new instance with the given number of rows and columns
+    pub fn new(nrow: usize, ncol: usize) -> Self {
+        GridLayout {
+            nrow,
+            ncol,
+            width_ratios: Vec::new(),
+            height_ratios: Vec::new(),
+            wspace: None,
+            hspace: None,
+            panels: Vec::new(),
+        }
+    }
+
+    /// Allocates a new instance from an ASCII mosaic such as `"AAB;CDB"`
+    ///
+    /// Each character names a panel and each row is separated by a semicolon (or a newline).
+    /// The cells with the same name must form a rectangle; the cells marked by a dot (`.`) are left empty.
+    /// For example, `"AAB;CDB"` defines a grid with two rows and three columns, where `A` spans
+    /// the first two columns of the first row and `B` spans the two rows of the last column.
+    ///
+    /// See [crate::Plot::subplot_mosaic()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, GridLayout, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+    ///     let mut layout = GridLayout::from_mosaic("AAB;CDB")?;
+    ///     layout.set_width_ratios(&[1.0, 1.0, 2.0]);
+    ///     let mut plot = Plot::new();
+    ///     let axes = plot.subplot_mosaic(&layout)?;
+    ///     plot.add_to(axes[&'B'], &curve).set_title("B");
+    ///     plot.set_axes(axes[&'A']).set_title("A");
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_grid_layout_mosaic.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_mosaic(mosaic: &str) -> Result<Self, StrError> {
+        let rows: Vec<Vec<char>> = mosaic
+            .split([';', '\n'])
+            .map(|row| row.trim())
+            .filter(|row| !row.is_empty())
+            .map(|row| row.chars().collect())
+            .collect();
+        if rows.is_empty() {
+            return Err("the mosaic must have at least one row");
+        }
+        let ncol = rows[0].len();
+        if rows.iter().any(|row| row.len() != ncol) {
+            return Err("all rows of the mosaic must have the same number of columns");
+        }
+        if rows.iter().flatten().any(|c| c.is_whitespace()) {
+            return Err("the mosaic must not have spaces within the rows");
+        }
+        let mut layout = GridLayout::new(rows.len(), ncol);
+        for (i, row) in rows.iter().enumerate() {
+            for (j, name) in row.iter().enumerate() {
+                if *name == '.' || layout.panels.iter().any(|(n, _, _)| n == name) {
+                    continue;
+                }
+                let mut i_end = i + 1;
+                while i_end < rows.len() && rows[i_end][j] == *name {
+                    i_end += 1;
+                }
+                let mut j_end = j + 1;
+                while j_end < ncol && row[j_end] == *name {
+                    j_end += 1;
+                }
+                let count = rows.iter().flatten().filter(|c| *c == name).count();
+                let rectangle = rows[i..i_end].iter().all(|r| r[j..j_end].iter().all(|c| c == name));
+                if !rectangle || count != (i_end - i) * (j_end - j) {
+                    return Err("the cells of each panel in the mosaic must form a rectangle");
+                }
+                layout.panels.push((*name, i..i_end, j..j_end));
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Sets the relative widths of the columns (one value for each column)
+    pub fn set_width_ratios(&mut self, ratios: &[f64]) -> &mut Self {
+        self.width_ratios = ratios.to_vec();
+        self
+    }
+
+    /// Sets the relative heights of the rows (one value for each row)
+    pub fn set_height_ratios(&mut self, ratios: &[f64]) -> &mut Self {
+        self.height_ratios = ratios.to_vec();
+        self
+    }
+
+    /// Sets the horizontal space between columns as a fraction of the average column width
+    pub fn set_wspace(&mut self, value: f64) -> &mut Self {
+        self.wspace = Some(value);
+        self
+    }
+
+    /// Sets the vertical space between rows as a fraction of the average row height
+    pub fn set_hspace(&mut self, value: f64) -> &mut Self {
+        self.hspace = Some(value);
+        self
+    }
+
+    /// Returns the number of rows and columns
+    pub fn size(&self) -> (usize, usize) {
+        (self.nrow, self.ncol)
+    }
+
+    /// Returns the names of the panels given by the mosaic (empty if not created by [GridLayout::from_mosaic()])
+    pub fn names(&self) -> Vec<char> {
+        self.panels.iter().map(|(name, _, _)| *name).collect()
+    }
+
+    /// Returns the panels given by the mosaic (name, rows, and columns)
+    pub(crate) fn panels(&self) -> &[(char, Range<usize>, Range<usize>)] {
+        &self.panels
+    }
+
+    /// Checks the layout
+    pub(crate) fn validate(&self) -> Result<(), StrError> {
+        if self.nrow == 0 || self.ncol == 0 {
+            return Err("the grid layout must have at least one row and one column");
+        }
+        if !self.width_ratios.is_empty() && self.width_ratios.len() != self.ncol {
+            return Err("the number of width ratios must equal the number of columns");
+        }
+        if !self.height_ratios.is_empty() && self.height_ratios.len() != self.nrow {
+            return Err("the number of height ratios must equal the number of rows");
+        }
+        if self
+            .width_ratios
+            .iter()
+            .chain(self.height_ratios.iter())
+            .any(|r| !(r.is_finite() && *r > 0.0))
+        {
+            return Err("the width and height ratios must be positive");
+        }
+        if [self.wspace, self.hspace]
+            .iter()
+            .flatten()
+            .any(|s| !(s.is_finite() && *s >= 0.0))
+        {
+            return Err("the spaces between rows and columns must not be negative");
+        }
+        Ok(())
+    }
+
+    /// Converts the ranges of rows and columns to slices of the grid
+    ///
+    /// Returns an error if the ranges are empty or out of bounds.
+    pub(crate) fn cell<R, C>(&self, rows: R, cols: C) -> Result<(Range<usize>, Range<usize>), StrError>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        const OUT_OF_BOUNDS: &str = "the ranges of rows and columns must be within the grid layout";
+        let rows = to_range(rows, self.nrow).ok_or(OUT_OF_BOUNDS)?;
+        let cols = to_range(cols, self.ncol).ok_or(OUT_OF_BOUNDS)?;
+        if rows.start >= rows.end || cols.start >= cols.end {
+            return Err("the ranges of rows and columns must not be empty");
+        }
+        if rows.end > self.nrow || cols.end > self.ncol {
+            return Err(OUT_OF_BOUNDS);
+        }
+        Ok((rows, cols))
+    }
+
+    /// Returns the python command creating the GridSpec named `name`
+    pub(crate) fn commands(&self, name: &str) -> String {
+        let mut buffer = String::new();
+        write!(
+            &mut buffer,
+            "{}=plt.gcf().add_gridspec({},{}",
+            name, self.nrow, self.ncol
+        )
+        .unwrap();
+        if !self.width_ratios.is_empty() {
+            let ratios: Vec<_> = self.width_ratios.iter().map(|r| r.to_string()).collect();
+            write!(&mut buffer, ",width_ratios=[{}]", ratios.join(",")).unwrap();
+        }
+        if !self.height_ratios.is_empty() {
+            let ratios: Vec<_> = self.height_ratios.iter().map(|r| r.to_string()).collect();
+            write!(&mut buffer, ",height_ratios=[{}]", ratios.join(",")).unwrap();
+        }
+        if let Some(value) = self.wspace {
+            write!(&mut buffer, ",wspace={}", value).unwrap();
+        }
+        if let Some(value) = self.hspace {
+            write!(&mut buffer, ",hspace={}", value).unwrap();
+        }
+        buffer.push_str(")\n");
+        buffer
+    }
+}
+
+/// Converts range bounds to a range with the given (exclusive) upper limit for unbounded ranges
+///
+/// Returns None if a bound overflows (e.g., `..=usize::MAX`).
+fn to_range<R>(range: R, len: usize) -> Option<Range<usize>>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(i) => *i,
+        Bound::Excluded(i) => i.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(i) => i.checked_add(1)?,
+        Bound::Excluded(i) => *i,
+        Bound::Unbounded => len,
+    };
+    Some(start..end)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::GridLayout;
+    use std::ops::Bound;
+
+    #[test]
+    fn new_works() {
+        let layout = GridLayout::new(2, 3);
+        assert_eq!(layout.size(), (2, 3));
+        assert_eq!(layout.width_ratios.len(), 0);
+        assert_eq!(layout.height_ratios.len(), 0);
+        assert_eq!(layout.wspace, None);
+        assert_eq!(layout.hspace, None);
+        assert_eq!(layout.names().len(), 0);
+    }
+
+    #[test]
+    fn commands_work() {
+        let mut layout = GridLayout::new(2, 3);
+        assert_eq!(layout.commands("GRID_0"), "GRID_0=plt.gcf().add_gridspec(2,3)\n");
+        layout
+            .set_width_ratios(&[2.0, 1.0, 1.5])
+            .set_height_ratios(&[1.0, 3.0])
+            .set_wspace(0.0)
+            .set_hspace(0.25);
+        assert_eq!(layout.validate(), Ok(()));
+        assert_eq!(
+            layout.commands("GRID_1"),
+            "GRID_1=plt.gcf().add_gridspec(2,3,width_ratios=[2,1,1.5],height_ratios=[1,3],wspace=0,hspace=0.25)\n"
+        );
+    }
+
+    #[test]
+    fn validate_captures_errors() {
+        assert_eq!(
+            GridLayout::new(0, 1).validate().err(),
+            Some("the grid layout must have at least one row and one column")
+        );
+        let mut layout = GridLayout::new(2, 3);
+        layout.set_width_ratios(&[1.0, 2.0]);
+        assert_eq!(
+            layout.validate().err(),
+            Some("the number of width ratios must equal the number of columns")
+        );
+        layout.set_width_ratios(&[]).set_height_ratios(&[1.0]);
+        assert_eq!(
+            layout.validate().err(),
+            Some("the number of height ratios must equal the number of rows")
+        );
+        layout.set_height_ratios(&[1.0, f64::NAN]);
+        assert_eq!(
+            layout.validate().err(),
+            Some("the width and height ratios must be positive")
+        );
+        layout.set_height_ratios(&[1.0, 0.0]);
+        assert_eq!(
+            layout.validate().err(),
+            Some("the width and height ratios must be positive")
+        );
+        layout.set_height_ratios(&[1.0, 1.0]).set_wspace(-0.1);
+        assert_eq!(
+            layout.validate().err(),
+            Some("the spaces between rows and columns must not be negative")
+        );
+    }
+
+    #[test]
+    fn cell_works() {
+        let layout = GridLayout::new(2, 3);
+        assert_eq!(layout.cell(0..1, 1..3), Ok((0..1, 1..3)));
+        assert_eq!(layout.cell(1..=1, ..), Ok((1..2, 0..3)));
+        assert_eq!(layout.cell(.., 2..), Ok((0..2, 2..3)));
+        assert_eq!(
+            layout.cell(1..1, 0..1).err(),
+            Some("the ranges of rows and columns must not be empty")
+        );
+        assert_eq!(
+            layout.cell(0..1, 2..4).err(),
+            Some("the ranges of rows and columns must be within the grid layout")
+        );
+        assert_eq!(
+            layout.cell(0..=usize::MAX, 0..1).err(),
+            Some("the ranges of rows and columns must be within the grid layout")
+        );
+        let excluded = (Bound::Excluded(usize::MAX), Bound::Unbounded);
+        assert_eq!(
+            layout.cell(0..1, excluded).err(),
+            Some("the ranges of rows and columns must be within the grid layout")
+        );
+    }
+
+    #[test]
+    fn from_mosaic_works() {
+        let layout = GridLayout::from_mosaic("AAB;CDB").unwrap();
+        assert_eq!(layout.size(), (2, 3));
+        assert_eq!(layout.names(), &['A', 'B', 'C', 'D']);
+        assert_eq!(
+            layout.panels(),
+            &[
+                ('A', 0..1, 0..2),
+                ('B', 0..2, 2..3),
+                ('C', 1..2, 0..1),
+                ('D', 1..2, 1..2)
+            ]
+        );
+        let layout = GridLayout::from_mosaic("\n  x.\n  xy\n").unwrap();
+        assert_eq!(layout.size(), (2, 2));
+        assert_eq!(layout.panels(), &[('x', 0..2, 0..1), ('y', 1..2, 1..2)]);
+    }
+
+    #[test]
+    fn from_mosaic_captures_errors() {
+        assert_eq!(
+            GridLayout::from_mosaic(" ; ").err(),
+            Some("the mosaic must have at least one row")
+        );
+        assert_eq!(
+            GridLayout::from_mosaic("AB;C").err(),
+            Some("all rows of the mosaic must have the same number of columns")
+        );
+        assert_eq!(
+            GridLayout::from_mosaic("A B;CDE").err(),
+            Some("the mosaic must not have spaces within the rows")
+        );
+        assert_eq!(
+            GridLayout::from_mosaic("AB;BA").err(),
+            Some("the cells of each panel in the mosaic must form a rectangle")
+        );
+        assert_eq!(
+            GridLayout::from_mosaic("AA;AB").err(),
+            Some("the cells of each panel in the mosaic must form a rectangle")
+        );
+        assert_eq!(
+            GridLayout::from_mosaic("ABA").err(),
+            Some("the cells of each panel in the mosaic must form a rectangle")
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use super::{generate_list_quoted, generate_nested_list, GraphMaker};
-use num_traits::Num;
+use super::{generate_list_quoted, generate_nested_list, DataArrays, GraphMaker};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Generates a Histogram plot
@@ -9,9 +9,9 @@ use std::fmt::Write;
 /// # Example
 ///
 /// ```
-/// use plotpy::{Histogram, Plot, StrError};
+/// use plotpy::{Error, Histogram, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // set values
 ///     let values = vec![
 ///         vec![1, 1, 1, 2, 2, 2, 2, 2, 3, 3, 4, 5, 6], // first series
@@ -58,6 +58,7 @@ pub struct Histogram {
     number_bins: usize,  // Number of bins
     extra: String,       // Extra commands (comma separated)
     buffer: String,      // buffer
+    arrays: DataArrays,  // data arrays written to the buffer
 }
 
 impl Histogram {
@@ -72,6 +73,7 @@ impl Histogram {
             number_bins: 0,
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -83,11 +85,11 @@ impl Histogram {
     /// * `labels` -- holds the labels
     pub fn draw<T, U>(&mut self, values: &Vec<Vec<T>>, labels: &[U])
     where
-        T: std::fmt::Display + Num,
+        T: std::fmt::Display + Num + ToPrimitive,
         U: std::fmt::Display,
     {
         let opt = self.options();
-        generate_nested_list(&mut self.buffer, "values", values);
+        generate_nested_list(&mut self.buffer, &mut self.arrays, "values", values);
         generate_list_quoted(&mut self.buffer, "labels", labels);
         if self.colors.len() > 0 {
             generate_list_quoted(&mut self.buffer, "colors", self.colors.as_slice());
@@ -185,8 +187,12 @@ impl GraphMaker for Histogram {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -239,8 +245,8 @@ mod tests {
         histogram.set_colors(&vec!["red", "green"]);
         histogram.draw(&values, &labels);
         let b: &str = "values=[[1,1,1,2,2,2,2,2,3,3,],[5,6,7,8,],]\n\
-                       labels=['first','second',]\n\
-                       colors=['red','green',]\n\
+                       labels=[r'first',r'second',]\n\
+                       colors=[r'red',r'green',]\n\
                        plt.hist(values,label=labels,color=colors)\n";
         assert_eq!(histogram.buffer, b);
         histogram.clear_buffer();
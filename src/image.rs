@@ -1,5 +1,5 @@
-use super::{generate_nested_list_3, matrix_to_array, AsMatrix, GraphMaker};
-use num_traits::Num;
+use super::{generate_nested_list_3, matrix_to_array, AsMatrix, DataArrays, GraphMaker};
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Generates an image plot (imshow)
@@ -9,9 +9,9 @@ use std::fmt::Write;
 /// # Examples
 ///
 /// ```
-/// use plotpy::{Image, Plot, StrError};
+/// use plotpy::{Error, Image, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // set values
 ///     let data = [
 ///         [0.8, 2.4, 2.5, 3.9, 0.0, 4.0, 0.0],
@@ -42,6 +42,7 @@ pub struct Image {
     colormap_name: String, // Colormap name
     extra: String,         // Extra commands (comma separated)
     buffer: String,        // buffer
+    arrays: DataArrays,    // data arrays written to the buffer
 }
 
 impl Image {
@@ -51,6 +52,7 @@ impl Image {
             colormap_name: String::new(),
             extra: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -64,9 +66,9 @@ impl Image {
     pub fn draw<'a, T, U>(&mut self, data: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "data", data);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "data", data);
         let opt = self.options();
         write!(&mut self.buffer, "plt.imshow(data{})\n", &opt).unwrap();
     }
@@ -81,9 +83,9 @@ impl Image {
     /// See <https://matplotlib.org/stable/api/_as_gen/matplotlib.pyplot.imshow.html>
     pub fn draw_rgb_or_rgba<T>(&mut self, data: &Vec<Vec<Vec<T>>>)
     where
-        T: std::fmt::Display + Num,
+        T: std::fmt::Display + Num + ToPrimitive,
     {
-        generate_nested_list_3(&mut self.buffer, "data", data);
+        generate_nested_list_3(&mut self.buffer, &mut self.arrays, "data", data);
         let opt = self.options();
         write!(&mut self.buffer, "plt.imshow(data{})\n", &opt).unwrap();
     }
@@ -141,8 +143,12 @@ impl GraphMaker for Image {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -1,4 +1,4 @@
-use super::GraphMaker;
+use super::{quote, DataArrays, GraphMaker};
 use std::fmt::Write;
 
 /// Implements the capability to add inset Axes to existing Axes.
@@ -6,9 +6,9 @@ use std::fmt::Write;
 /// # Examples
 ///
 /// ```
-/// use plotpy::{Curve, InsetAxes, Plot, StrError};
+/// use plotpy::{Curve, Error, InsetAxes, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // draw curve
 ///     let mut curve = Curve::new();
 ///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
@@ -49,6 +49,7 @@ pub struct InsetAxes {
     indicator_disabled: bool,
     title: String,
     buffer: String,
+    arrays: DataArrays,
 }
 
 impl InsetAxes {
@@ -87,6 +88,7 @@ impl InsetAxes {
             indicator_disabled: false,
             title: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -156,29 +158,33 @@ impl InsetAxes {
     /// ```
     pub fn add(&mut self, graph: &dyn GraphMaker) -> &mut Self {
         // Note: the order of replacements is important
-        let buf = graph
-            .get_buffer()
-            .replace("plt.gca()", "zoom")
-            .replace("plt.barh", "zoom.barh")
-            .replace("plt.bar", "zoom.bar")
-            .replace("plt.boxplot", "zoom.boxplot")
-            .replace("plt.contourf", "zoom.contourf")
-            .replace("plt.contour", "zoom.contour")
-            .replace("plt.clabel", "zoom.clabel")
-            .replace("plt.colorbar", "ignore_this")
-            .replace("cb.ax.set_ylabel", "ignore_this")
-            .replace("plt.fill_between", "zoom.fill_between")
-            .replace("plt.imshow", "zoom.imshow")
-            .replace("plt.legend", "zoom.legend")
-            .replace("plt.hist", "zoom.hist")
-            .replace("plt.plot", "zoom.plot")
-            .replace("plt.quiver", "zoom.quiver")
-            .replace("plt.text", "zoom.text")
-            .replace("plt.streamplot", "zoom.streamplot")
-            .replace("plt.tricontour", "zoom.tricontour")
-            .replace("plt.tricontourf", "zoom.tricontourf")
-            .replace("plt.triplot", "zoom.triplot");
-        self.buffer.push_str(&buf);
+        let convert = |buf: &str| {
+            buf.replace("plt.gca()", "zoom")
+                .replace("plt.barh", "zoom.barh")
+                .replace("plt.bar", "zoom.bar")
+                .replace("plt.boxplot", "zoom.boxplot")
+                .replace("plt.contourf", "zoom.contourf")
+                .replace("plt.contour", "zoom.contour")
+                .replace("plt.clabel", "zoom.clabel")
+                .replace("plt.colorbar", "ignore_this")
+                .replace("cb.ax.set_ylabel", "ignore_this")
+                .replace("cb.locator=", "cb_locator=")
+                .replace("cb.formatter=", "cb_formatter=")
+                .replace("cb.update_ticks", "ignore_this")
+                .replace("plt.fill_between", "zoom.fill_between")
+                .replace("plt.imshow", "zoom.imshow")
+                .replace("plt.legend", "zoom.legend")
+                .replace("plt.hist", "zoom.hist")
+                .replace("plt.plot", "zoom.plot")
+                .replace("plt.quiver", "zoom.quiver")
+                .replace("plt.text", "zoom.text")
+                .replace("plt.streamplot", "zoom.streamplot")
+                .replace("plt.tricontour", "zoom.tricontour")
+                .replace("plt.tricontourf", "zoom.tricontourf")
+                .replace("plt.triplot", "zoom.triplot")
+        };
+        self.arrays
+            .append_converted(&mut self.buffer, graph.get_buffer(), graph.get_arrays(), convert);
         self
     }
 
@@ -201,28 +207,24 @@ impl InsetAxes {
     pub fn draw(&mut self, u0: f64, v0: f64, width: f64, height: f64) {
         let opt1 = self.options_for_axes();
         let opt2 = self.options_for_indicator();
-        if let Some((xmin, xmax, ymin, ymax)) = self.range {
-            self.buffer.insert_str(
-                0,
-                &format!(
-                    "zoom=plt.gca().inset_axes([{},{},{},{}],xlim=({},{}),ylim=({},{}){})\n",
-                    u0, v0, width, height, xmin, xmax, ymin, ymax, opt1,
-                ),
-            );
+        let axes = if let Some((xmin, xmax, ymin, ymax)) = self.range {
+            format!(
+                "zoom=plt.gca().inset_axes([{},{},{},{}],xlim=({},{}),ylim=({},{}){})\n",
+                u0, v0, width, height, xmin, xmax, ymin, ymax, opt1,
+            )
         } else {
-            self.buffer.insert_str(
-                0,
-                &format!(
-                    "zoom=plt.gca().inset_axes([{},{},{},{}]{})\n",
-                    u0, v0, width, height, opt1,
-                ),
-            );
-        }
+            format!(
+                "zoom=plt.gca().inset_axes([{},{},{},{}]{})\n",
+                u0, v0, width, height, opt1,
+            )
+        };
+        self.buffer.insert_str(0, &axes);
+        self.arrays.shift(axes.len());
         if !self.axes_visible {
             write!(&mut self.buffer, "zoom.set_xticks([])\nzoom.set_yticks([])\n").unwrap();
         }
         if !self.title.is_empty() {
-            write!(&mut self.buffer, "zoom.set_title(r'{}')\n", self.title).unwrap();
+            write!(&mut self.buffer, "zoom.set_title({})\n", quote(&self.title)).unwrap();
         }
         if !self.indicator_disabled {
             write!(&mut self.buffer, "plt.gca().indicate_inset_zoom(zoom{})\n", opt2,).unwrap();
@@ -322,8 +324,12 @@ impl GraphMaker for InsetAxes {
     }
 
     /// Clears the buffer, removing all stored commands.
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -332,7 +338,7 @@ impl GraphMaker for InsetAxes {
 #[cfg(test)]
 mod tests {
     use super::InsetAxes;
-    use crate::GraphMaker;
+    use crate::{Contour, Formatter, GraphMaker, Locator};
 
     #[test]
     fn test_new() {
@@ -382,6 +388,34 @@ mod tests {
         assert!(options.contains("alpha=0.5"));
     }
 
+    #[test]
+    fn test_add_neutralizes_colorbar() {
+        let mut contour = Contour::new();
+        contour
+            .set_colorbar_label("temperature")
+            .set_colorbar_locator(&Locator::Fixed(vec![0.0, 0.5]))
+            .unwrap()
+            .set_colorbar_formatter(&Formatter::Decimals(1))
+            .unwrap();
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 0.5], vec![0.5, 1.0]];
+        contour.draw(&x, &y, &z);
+        let mut inset = InsetAxes::new();
+        inset.add(&contour);
+        assert!(inset.buffer.contains("zoom.contourf("));
+        assert!(!inset.buffer.contains("plt.colorbar"));
+        assert!(!inset.buffer.contains("cb."));
+        assert!(inset.buffer.ends_with(
+            "cb=ignore_this(cf)\n\
+             ignore_this(r'temperature')\n\
+             cb_locator=tck.FixedLocator([0,0.5,])\n\
+             cb_formatter=tck.StrMethodFormatter(r'{x:.1f}')\n\
+             cb_formatter=cb_formatter\n\
+             ignore_this()\n"
+        ));
+    }
+
     #[test]
     fn test_draw_basic() {
         let mut inset = InsetAxes::new();
@@ -8,9 +8,9 @@ use std::fmt::Write;
 /// # Example
 ///
 /// ```
-/// use plotpy::{linspace, Curve, Legend, Plot, StrError};
+/// use plotpy::{linspace, Curve, Error, Legend, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // generate (x,y) points
 ///     let x  = linspace(0.0, 5.0, 6);
 ///     let y1: Vec<_> = x.iter().map(|v| 0.5 * *v).collect();
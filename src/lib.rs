@@ -20,7 +20,8 @@
 //!
 //! When calling [Plot::save()] or [Plot::show()], if an error occurs, we generate a log
 //! file in the same output directory with the same filename as the figure (and python script),
-//! but with the `.log` extension.
+//! but with the `.log` extension. The returned [Error] distinguishes, for instance, a missing
+//! Python interpreter from an exception raised by Matplotlib.
 //!
 //! The typical use of this library is by allocating structures such as [Canvas], [Curve], [Contour],
 //! [Histogram], [Surface], [Text] (and more) and then passing them to [Plot] for the generation
@@ -39,9 +40,9 @@
 //! # Example
 //!
 //! ```
-//! use plotpy::{generate3d, Plot, StrError, Surface};
+//! use plotpy::{generate3d, Error, Plot, Surface};
 //!
-//! fn main() -> Result<(), StrError> {
+//! fn main() -> Result<(), Error> {
 //!     let mut surface = Surface::new();
 //!     surface
 //!         .set_with_wireframe(true)
@@ -68,15 +69,28 @@
 //! ```
 //!
 //! ![example_main.svg](https://raw.githubusercontent.com/cpmech/plotpy/main/figures/example_main.svg)
+//!
+//! # Features
+//!
+//! * `testing` -- enables the `plotpy::testing` module with helpers for snapshot tests: comparing the
+//!   generated Python commands with golden files and comparing PNG figures with baseline images
+//! * `chrono` -- implements [AsTimestamp] for the date and time types of the `chrono` crate
 
 /// Defines a type alias for the error type as a static string
+///
+/// **Note:** This type is used by functions that only check their input. See also [Error].
 pub type StrError = &'static str;
 
 // modules
+mod animation;
 mod as_matrix;
+mod as_timestamp;
 mod as_vector;
 mod auxiliary;
+mod axes_id;
+mod axis_transform;
 mod barplot;
+mod batch;
 mod boxplot;
 mod canvas;
 mod constants;
@@ -84,44 +98,75 @@ mod contour;
 mod conversions;
 mod curve;
 mod dark_mode;
+mod error;
 mod fileio;
 mod fill_between;
+mod formatter;
+mod grid_layout;
 mod histogram;
 mod image;
 mod inset_axes;
 mod legend;
+mod locator;
+mod non_finite;
+mod pdf_document;
 mod plot;
+mod python_env;
+mod python_session;
+mod scale;
 mod slope_icon;
 mod stream;
+mod style;
 mod super_title_params;
 mod surface;
 mod surface_geometry;
 mod text;
+mod zero_location;
+
+#[cfg(feature = "testing")]
+pub mod testing;
 
 // re-export
+pub use animation::*;
 pub use as_matrix::*;
+pub use as_timestamp::*;
 pub use as_vector::*;
 pub use auxiliary::*;
+pub use axes_id::*;
+pub use axis_transform::*;
 pub use barplot::*;
+pub use batch::*;
 pub use boxplot::*;
 pub use canvas::*;
 pub use constants::*;
 pub use contour::*;
+pub use conversions::DataArrays;
 use conversions::*;
 pub use curve::*;
 pub use dark_mode::*;
+pub use error::*;
 use fileio::*;
 pub use fill_between::*;
+pub use formatter::*;
+pub use grid_layout::*;
 pub use histogram::*;
 pub use image::*;
 pub use inset_axes::*;
 pub use legend::*;
+pub use locator::*;
+pub use non_finite::*;
+pub use pdf_document::*;
 pub use plot::*;
+pub use python_env::*;
+pub use python_session::*;
+pub use scale::*;
 pub use slope_icon::*;
 pub use stream::*;
+pub use style::*;
 pub use super_title_params::*;
 pub use surface::*;
 pub use text::*;
+pub use zero_location::*;
 
 // run code from README file
 #[cfg(doctest)]
@@ -0,0 +1,222 @@
+use super::StrError;
+use std::fmt::Write;
+
+/// Defines the positions of the ticks along an axis
+///
+/// See [crate::Plot::set_locator_x()], [crate::Plot::set_minor_locator_x()], and
+/// [crate::Contour::set_colorbar_locator()] (and related functions).
+///
+/// [See Matplotlib's documentation](https://matplotlib.org/stable/api/ticker_api.html)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Locator {
+    /// Places the ticks at the given positions
+    Fixed(Vec<f64>),
+
+    /// Finds up to `nbins + 1` ticks at nice positions
+    MaxN {
+        /// The maximum number of intervals (positive)
+        nbins: usize,
+        /// Only places ticks at integer values
+        integer: bool,
+    },
+
+    /// Places the ticks at each integer multiple of `base` plus `offset`
+    ///
+    /// **Note:** A non-zero offset requires Matplotlib 3.8 or newer.
+    Multiple {
+        /// The step between ticks (positive)
+        base: f64,
+        /// The offset added to each multiple
+        offset: f64,
+    },
+
+    /// Places the ticks at `subs[j] * base^i` for log scales
+    Log {
+        /// The base of the logarithm (greater than one); e.g., 10.0
+        base: f64,
+        /// The multiples of the integer powers of the base; e.g., `[1.0, 2.0, 5.0]` (`[1.0]` if empty)
+        subs: Vec<f64>,
+    },
+
+    /// Places evenly spaced minor ticks between the major ticks
+    AutoMinor {
+        /// The number of subdivisions of the major interval (chosen automatically if zero)
+        ndivs: usize,
+    },
+}
+
+impl Locator {
+    /// Returns the Python expression creating the locator
+    pub(crate) fn expression(&self) -> Result<String, StrError> {
+        let mut res = String::new();
+        match self {
+            Locator::Fixed(positions) => {
+                res.push_str("tck.FixedLocator([");
+                for p in positions {
+                    write!(&mut res, "{},", p).unwrap();
+                }
+                res.push_str("])");
+            }
+            Locator::MaxN { nbins, integer } => {
+                if *nbins == 0 {
+                    return Err("the number of bins of the MaxN locator must be positive");
+                }
+                let integer = if *integer { "True" } else { "False" };
+                write!(&mut res, "tck.MaxNLocator(nbins={},integer={})", nbins, integer).unwrap();
+            }
+            Locator::Multiple { base, offset } => {
+                if !base.is_finite() || *base <= 0.0 {
+                    return Err("the base of the Multiple locator must be positive");
+                }
+                if !offset.is_finite() {
+                    return Err("the offset of the Multiple locator must be finite");
+                }
+                if *offset == 0.0 {
+                    write!(&mut res, "tck.MultipleLocator({})", base).unwrap();
+                } else {
+                    write!(&mut res, "tck.MultipleLocator({},offset={})", base, offset).unwrap();
+                }
+            }
+            Locator::Log { base, subs } => {
+                if !(base.is_finite() && *base > 1.0) {
+                    return Err("the base of the Log locator must be greater than one");
+                }
+                write!(&mut res, "tck.LogLocator(base={}", base).unwrap();
+                if !subs.is_empty() {
+                    res.push_str(",subs=[");
+                    for s in subs {
+                        write!(&mut res, "{},", s).unwrap();
+                    }
+                    res.push(']');
+                }
+                res.push(')');
+            }
+            Locator::AutoMinor { ndivs } => {
+                if *ndivs == 0 {
+                    res.push_str("tck.AutoMinorLocator()");
+                } else {
+                    write!(&mut res, "tck.AutoMinorLocator({})", ndivs).unwrap();
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Locator;
+
+    #[test]
+    fn derive_works() {
+        let locator = Locator::Fixed(vec![1.0, 2.0]);
+        assert_eq!(locator.clone(), locator);
+        assert_eq!(format!("{:?}", locator), "Fixed([1.0, 2.0])");
+    }
+
+    #[test]
+    fn expression_works() {
+        assert_eq!(
+            Locator::Fixed(vec![0.0, 0.5, 1.0]).expression().unwrap(),
+            "tck.FixedLocator([0,0.5,1,])"
+        );
+        assert_eq!(
+            Locator::MaxN {
+                nbins: 4,
+                integer: true
+            }
+            .expression()
+            .unwrap(),
+            "tck.MaxNLocator(nbins=4,integer=True)"
+        );
+        assert_eq!(
+            Locator::Multiple { base: 0.5, offset: 0.0 }.expression().unwrap(),
+            "tck.MultipleLocator(0.5)"
+        );
+        assert_eq!(
+            Locator::Multiple { base: 2.0, offset: 1.0 }.expression().unwrap(),
+            "tck.MultipleLocator(2,offset=1)"
+        );
+        assert_eq!(
+            Locator::Log {
+                base: 10.0,
+                subs: Vec::new()
+            }
+            .expression()
+            .unwrap(),
+            "tck.LogLocator(base=10)"
+        );
+        assert_eq!(
+            Locator::Log {
+                base: 10.0,
+                subs: vec![1.0, 2.0, 5.0]
+            }
+            .expression()
+            .unwrap(),
+            "tck.LogLocator(base=10,subs=[1,2,5,])"
+        );
+        assert_eq!(
+            Locator::AutoMinor { ndivs: 0 }.expression().unwrap(),
+            "tck.AutoMinorLocator()"
+        );
+        assert_eq!(
+            Locator::AutoMinor { ndivs: 4 }.expression().unwrap(),
+            "tck.AutoMinorLocator(4)"
+        );
+    }
+
+    #[test]
+    fn expression_captures_errors() {
+        assert_eq!(
+            Locator::MaxN {
+                nbins: 0,
+                integer: false
+            }
+            .expression()
+            .err(),
+            Some("the number of bins of the MaxN locator must be positive")
+        );
+        assert_eq!(
+            Locator::Multiple { base: 0.0, offset: 0.0 }.expression().err(),
+            Some("the base of the Multiple locator must be positive")
+        );
+        assert_eq!(
+            Locator::Multiple {
+                base: f64::NAN,
+                offset: 0.0
+            }
+            .expression()
+            .err(),
+            Some("the base of the Multiple locator must be positive")
+        );
+        assert_eq!(
+            Locator::Multiple {
+                base: 1.0,
+                offset: f64::NAN
+            }
+            .expression()
+            .err(),
+            Some("the offset of the Multiple locator must be finite")
+        );
+        assert_eq!(
+            Locator::Multiple {
+                base: 1.0,
+                offset: f64::INFINITY
+            }
+            .expression()
+            .err(),
+            Some("the offset of the Multiple locator must be finite")
+        );
+        assert_eq!(
+            Locator::Log {
+                base: 1.0,
+                subs: Vec::new()
+            }
+            .expression()
+            .err(),
+            Some("the base of the Log locator must be greater than one")
+        );
+    }
+}
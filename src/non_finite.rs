@@ -0,0 +1,107 @@
+use super::{DataArrays, Error};
+
+/// Defines how the non-finite values (NaN, +inf, and -inf) of the data are handled
+///
+/// The data arrays (e.g., from [crate::AsVector] and [crate::AsMatrix]) are written into the
+/// Python script as `NaN`, `inf`, and `-inf`, which are defined in [crate::PYTHON_HEADER].
+/// The policy is applied to the values recorded when drawing (see [DataArrays]); thus, the data
+/// of graph entities not returning their arrays (see [crate::GraphMaker::get_arrays()]) are kept.
+/// See [crate::Plot::set_non_finite()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFinite {
+    /// Passes the values to Matplotlib as they are (default)
+    ///
+    /// Matplotlib does not draw NaN values; however, infinite values may affect, for instance,
+    /// the automatic axes limits.
+    #[default]
+    Keep,
+
+    /// Masks the non-finite values such that they are not drawn
+    ///
+    /// The arrays are converted by `np.ma.masked_invalid`; and the non-finite values are removed
+    /// from the samples given as nested lists (e.g., [crate::Histogram] and [crate::Boxplot] data).
+    Mask,
+
+    /// Returns [Error::NonFinite] (or [Error::NonFiniteLimits] for the axes limits) before running Python
+    Reject,
+}
+
+impl NonFinite {
+    /// Checks the data arrays recorded by the functions converting data (see [DataArrays])
+    ///
+    /// Returns [Error::NonFinite] if the policy is Reject and an array has non-finite values.
+    pub(crate) fn check(&self, arrays: &DataArrays) -> Result<(), Error> {
+        if *self != NonFinite::Reject {
+            return Ok(());
+        }
+        match arrays.find_non_finite() {
+            Some(name) => Err(Error::NonFinite { name: name.to_string() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Masks the data arrays with non-finite values if the policy is Mask
+    ///
+    /// Returns the modified Python commands and the records of the arrays in the modified commands
+    /// (None if the policy is not Mask).
+    pub(crate) fn apply(&self, buf: &str, arrays: &DataArrays) -> Option<(String, DataArrays)> {
+        if *self != NonFinite::Mask {
+            return None;
+        }
+        Some(arrays.mask_non_finite(buf))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::NonFinite;
+    use crate::conversions::{generate_list, generate_nested_list, vector_to_array};
+    use crate::DataArrays;
+
+    #[test]
+    fn derive_and_default_work() {
+        let policy = NonFinite::default();
+        assert_eq!(policy, NonFinite::Keep);
+        assert_eq!(format!("{:?}", policy.clone()), "Keep");
+    }
+
+    #[test]
+    fn check_and_apply_work() {
+        let mut buf = String::new();
+        let mut arrays = DataArrays::new();
+        vector_to_array(&mut buf, &mut arrays, "x", &[1.0, f64::INFINITY, 3.0]);
+        vector_to_array(&mut buf, &mut arrays, "y", &[1.0, 2.0, 3.0]);
+        generate_nested_list(
+            &mut buf,
+            &mut arrays,
+            "values",
+            &vec![vec![1.0, 2.0, 3.0], vec![f64::NAN]],
+        );
+        generate_list(&mut buf, "positions", &[f64::INFINITY]);
+        buf.push_str("plt.plot(x,y)\n");
+        assert_eq!(NonFinite::Keep.check(&arrays).is_ok(), true);
+        assert_eq!(NonFinite::Mask.check(&arrays).is_ok(), true);
+        assert!(NonFinite::Keep.apply(&buf, &arrays).is_none());
+        assert!(NonFinite::Reject.apply(&buf, &arrays).is_none());
+        let (res, _) = NonFinite::Mask.apply(&buf, &arrays).unwrap();
+        assert_eq!(
+            res,
+            "x=np.array([1,inf,3,])\n\
+             x=np.ma.masked_invalid(x)\n\
+             y=np.array([1,2,3,])\n\
+             values=[[1,2,3,],[NaN,],]\n\
+             values=[np.asarray(r)[np.isfinite(r)] for r in values]\n\
+             positions=[inf,]\n\
+             plt.plot(x,y)\n"
+        );
+        assert_eq!(
+            NonFinite::Reject.check(&arrays).err().unwrap().to_string(),
+            "the data array x has non-finite values (NaN or ±inf)"
+        );
+        let mut finite = DataArrays::new();
+        vector_to_array(&mut String::new(), &mut finite, "y", &[1.0, 2.0, 3.0]);
+        assert_eq!(NonFinite::Reject.check(&finite).is_ok(), true);
+    }
+}
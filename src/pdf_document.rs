@@ -0,0 +1,383 @@
+use super::{quote, Error, Plot, PythonEnv, Requirements};
+use crate::PYTHON_HEADER;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Generates a multi-page PDF document with one Plot per page
+///
+/// The pages are saved by `matplotlib.backends.backend_pdf.PdfPages`. Each page is drawn on a new
+/// figure; thus, each page keeps its own figure size (e.g., [Plot::set_figure_size_inches()]) and
+/// its own options for saving (e.g., [Plot::set_save_tight()] and [Plot::set_save_transparent()]).
+/// The style of each page (see [Plot::set_style()]) does not affect the other pages.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{linspace, Curve, Error, PdfDocument, Plot};
+///
+/// fn main() -> Result<(), Error> {
+///     let x = linspace(0.0, 1.0, 11);
+///     let mut document = PdfDocument::new();
+///     document.set_title("Report").set_author("Plotpy");
+///     for i in 0..3 {
+///         let y: Vec<_> = x.iter().map(|v| f64::powi(*v, i + 1)).collect();
+///         let mut curve = Curve::new();
+///         curve.draw(&x, &y);
+///         let mut plot = Plot::new();
+///         plot.add(&curve).set_title(&format!("page {}", i + 1));
+///         document.add_page(&plot)?;
+///     }
+///     document.save("/tmp/plotpy/doc_tests/doc_pdf_document.pdf")
+/// }
+/// ```
+pub struct PdfDocument {
+    pages: Vec<(String, String, String)>, // the style, plotting commands, and savefig options of each page
+    metadata: Vec<(String, String)>,      // the document information; e.g., Title and Author
+    requirements: Requirements,           // features used by the pages (checked against the Python environment)
+    runner: Plot,                         // runs python3 and handles the output
+}
+
+impl PdfDocument {
+    /// Allocates a new instance
+    pub fn new() -> Self {
+        PdfDocument {
+            pages: Vec::new(),
+            metadata: Vec::new(),
+            requirements: Requirements::default(),
+            runner: Plot::new(),
+        }
+    }
+
+    /// Adds a page drawn by a Plot
+    ///
+    /// **Note:** The plotting commands and options of the Plot are copied; thus, later changes to
+    /// the Plot do not affect the page.
+    ///
+    /// Returns an error if the Plot does not pass its checks; e.g., [Error::NonFinite] with the
+    /// [crate::NonFinite::Reject] policy (see [Plot::set_non_finite()]).
+    pub fn add_page(&mut self, plot: &Plot) -> Result<&mut Self, Error> {
+        plot.check_commands()?;
+        self.pages.push((
+            plot.get_style_commands(),
+            plot.get_commands_before_save(),
+            plot.savefig_page_options("pdf"),
+        ));
+        self.requirements.merge(&plot.get_requirements());
+        Ok(self)
+    }
+
+    /// Sets the title of the document
+    pub fn set_title(&mut self, title: &str) -> &mut Self {
+        self.set_metadata("Title", title)
+    }
+
+    /// Sets the author of the document
+    pub fn set_author(&mut self, author: &str) -> &mut Self {
+        self.set_metadata("Author", author)
+    }
+
+    /// Sets the subject of the document
+    pub fn set_subject(&mut self, subject: &str) -> &mut Self {
+        self.set_metadata("Subject", subject)
+    }
+
+    /// Sets the keywords of the document
+    pub fn set_keywords(&mut self, keywords: &str) -> &mut Self {
+        self.set_metadata("Keywords", keywords)
+    }
+
+    /// Sets an entry of the document information
+    ///
+    /// # Input
+    ///
+    /// * `key` -- a key accepted by PdfPages; e.g., `Title`, `Author`, `Subject`, `Keywords`, or `Creator`
+    /// * `value` -- the value
+    pub fn set_metadata(&mut self, key: &str, value: &str) -> &mut Self {
+        self.metadata.retain(|(k, _)| k != key);
+        self.metadata.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Makes identical inputs generate byte-identical documents (see [Plot::set_deterministic()])
+    ///
+    /// When enabled, the creation date is removed from the document (unless set by [PdfDocument::set_metadata()])
+    /// and the environment variables `SOURCE_DATE_EPOCH` and `PYTHONHASHSEED` are set to zero.
+    pub fn set_deterministic(&mut self, flag: bool) -> &mut Self {
+        self.runner.set_deterministic(flag);
+        self
+    }
+
+    /// Sets the python3 executable command (default = `python3`)
+    pub fn set_python_exe(&mut self, python_exe: &str) -> &mut Self {
+        self.runner.set_python_exe(python_exe);
+        self
+    }
+
+    /// Sets the option to show python errors on the console (default = false)
+    pub fn set_show_errors(&mut self, option: bool) -> &mut Self {
+        self.runner.set_show_errors(option);
+        self
+    }
+
+    /// Sets a wall-clock time limit for Python (see [Plot::set_timeout()])
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.runner.set_timeout(timeout);
+        self
+    }
+
+    /// Sets the Python environment to check the features used by the pages (see [Plot::set_python_env()])
+    pub fn set_python_env(&mut self, env: PythonEnv) -> &mut Self {
+        self.runner.set_python_env(env);
+        self
+    }
+
+    /// Returns the warnings (or other output) issued by Python during the last successful run
+    pub fn get_diagnostics(&self) -> Vec<String> {
+        self.runner.get_diagnostics()
+    }
+
+    /// Calls Python and saves the python script and the PDF document
+    ///
+    /// # Input
+    ///
+    /// * `path` -- may be a String, &str, or Path
+    ///
+    /// **Note:** The python script (and log file) is written next to the document as in [Plot::save()]
+    pub fn save<S>(&self, path: &S) -> Result<(), Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let commands = self.generate_commands(Path::new(path))?;
+        self.runner.check_requirements(&self.requirements)?;
+        self.runner.run_commands(&commands, Path::new(path))
+    }
+
+    /// Generates the complete Python script
+    fn generate_commands(&self, path: &Path) -> Result<String, Error> {
+        if self.pages.is_empty() {
+            return Err(Error::InvalidInput("the document must have at least one page"));
+        }
+        let mut buffer =
+            String::from("#### >>>> file generated by plotpy <<<< ####\n\nimport matplotlib\nmatplotlib.use('Agg')\n");
+        buffer.push_str(PYTHON_HEADER);
+        buffer.push_str("from matplotlib.backends.backend_pdf import PdfPages\n");
+        let mut metadata: Vec<_> = self
+            .metadata
+            .iter()
+            .map(|(key, value)| format!("'{}':{}", key, quote(value)))
+            .collect();
+        if self.runner.get_deterministic() && !self.metadata.iter().any(|(key, _)| key == "CreationDate") {
+            metadata.push("'CreationDate':None".to_string());
+        }
+        write!(
+            &mut buffer,
+            "RC_ORIGINAL=dict(plt.rcParams.copy())\nfn={}\nPDF_PAGES=PdfPages(fn,metadata={{{}}})\n",
+            quote(&path.to_string_lossy()),
+            metadata.join(",")
+        )
+        .unwrap();
+        for (index, (style, commands, options)) in self.pages.iter().enumerate() {
+            write!(
+                &mut buffer,
+                "\n########## page {} ##########\n\
+                 dict.update(plt.rcParams,RC_ORIGINAL)\n{}\
+                 plt.figure()\nAXES.clear()\nEXTRA_ARTISTS.clear()\nTHREE_D.clear()\nTHREE_D_ACTIVE=(1,1,1)\n\
+                 {}\nPDF_PAGES.savefig(plt.gcf(){})\nplt.close('all')\n",
+                index + 1,
+                style,
+                commands,
+                options
+            )
+            .unwrap();
+        }
+        buffer.push_str("\nPDF_PAGES.close()\n");
+        Ok(buffer)
+    }
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PdfDocument;
+    use crate::{Curve, NonFinite, Plot, PythonEnv, Style};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn new_works() {
+        let document = PdfDocument::new();
+        assert_eq!(document.pages.len(), 0);
+        assert_eq!(document.metadata.len(), 0);
+        let document = PdfDocument::default();
+        assert_eq!(document.pages.len(), 0);
+    }
+
+    #[test]
+    fn set_metadata_works() {
+        let mut document = PdfDocument::new();
+        document
+            .set_title("Report")
+            .set_author("Jane's team")
+            .set_subject("Tests")
+            .set_keywords("a, b")
+            .set_title("Final report");
+        assert_eq!(
+            document.metadata,
+            &[
+                ("Author".to_string(), "Jane's team".to_string()),
+                ("Subject".to_string(), "Tests".to_string()),
+                ("Keywords".to_string(), "a, b".to_string()),
+                ("Title".to_string(), "Final report".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_commands_works() {
+        let mut document = PdfDocument::new();
+        let path = Path::new("/tmp/plotpy/unit_tests/pdf_document.pdf");
+        assert_eq!(
+            document.generate_commands(path).err().unwrap().to_string(),
+            "the document must have at least one page"
+        );
+        let mut first = Plot::new();
+        first.set_title("first").set_figure_size_inches(4.0, 3.0);
+        let mut second = Plot::new();
+        second
+            .set_title("second")
+            .set_save_tight(false)
+            .set_save_transparent(true)
+            .set_style(Style::new().set_font_size(9.0));
+        document
+            .set_author("Jane's team")
+            .add_page(&first)
+            .unwrap()
+            .add_page(&second)
+            .unwrap();
+        let commands = document.generate_commands(path).unwrap();
+        assert!(commands.contains(
+            "RC_ORIGINAL=dict(plt.rcParams.copy())\n\
+             fn=r'/tmp/plotpy/unit_tests/pdf_document.pdf'\n\
+             PDF_PAGES=PdfPages(fn,metadata={'Author':r'Jane' \"'\" r's team'})\n"
+        ));
+        assert!(commands.contains(
+            "plt.title(r'first')\nplt.gcf().set_size_inches(4,3)\n\n\
+             PDF_PAGES.savefig(plt.gcf(),bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS)\n"
+        ));
+        assert!(commands.contains("plt.title(r'second')\n\nPDF_PAGES.savefig(plt.gcf(),transparent=True)\n"));
+        assert!(commands.contains(
+            "dict.update(plt.rcParams,RC_ORIGINAL)\n\
+             plt.rcParams.update({\n    r'font.size':9,\n})\n\
+             plt.figure()\n"
+        ));
+        assert_eq!(commands.matches("plt.figure()\n").count(), 2);
+        assert_eq!(commands.matches("dict.update(plt.rcParams,RC_ORIGINAL)\n").count(), 2);
+        assert!(commands.ends_with("plt.close('all')\n\nPDF_PAGES.close()\n"));
+    }
+
+    #[test]
+    fn set_deterministic_works() {
+        let mut page = Plot::new();
+        page.set_deterministic(true);
+        let mut document = PdfDocument::new();
+        document
+            .set_deterministic(true)
+            .set_title("Report")
+            .add_page(&page)
+            .unwrap();
+        let path = Path::new("/tmp/plotpy/unit_tests/pdf_document_deterministic.pdf");
+        let commands = document.generate_commands(path).unwrap();
+        assert!(commands.contains("PDF_PAGES=PdfPages(fn,metadata={'Title':r'Report','CreationDate':None})\n"));
+        assert!(
+            commands.contains("\nPDF_PAGES.savefig(plt.gcf(),bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS)\n")
+        );
+        document.set_metadata("CreationDate", "D:20240101000000Z");
+        let commands = document.generate_commands(path).unwrap();
+        assert_eq!(commands.matches("'CreationDate'").count(), 1);
+        document.set_deterministic(false);
+        let commands = document.generate_commands(path).unwrap();
+        assert!(commands
+            .contains("PDF_PAGES=PdfPages(fn,metadata={'Title':r'Report','CreationDate':r'D:20240101000000Z'})\n"));
+    }
+
+    #[test]
+    fn set_deterministic_forwards_env() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = Path::new("/tmp/plotpy/unit_tests");
+        let fake_python = dir.join("pdf_document_fake_python.sh");
+        let env_path = dir.join("pdf_document_fake_python.txt");
+        fs::create_dir_all(dir).unwrap();
+        fs::write(&fake_python, format!("#!/bin/sh\nenv > {}\n", env_path.display())).unwrap();
+        fs::set_permissions(&fake_python, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut document = PdfDocument::new();
+        document
+            .set_python_exe(&fake_python.to_string_lossy())
+            .set_deterministic(true)
+            .add_page(&Plot::new())
+            .unwrap();
+        document.save(&dir.join("pdf_document_fake_python.pdf")).unwrap();
+        let env = fs::read_to_string(&env_path).unwrap();
+        assert!(env.lines().any(|line| line == "SOURCE_DATE_EPOCH=0"));
+        assert!(env.lines().any(|line| line == "PYTHONHASHSEED=0"));
+        document.set_deterministic(false);
+        document.save(&dir.join("pdf_document_fake_python.pdf")).unwrap();
+        let env = fs::read_to_string(&env_path).unwrap();
+        assert!(!env.lines().any(|line| line == "SOURCE_DATE_EPOCH=0"));
+    }
+
+    #[test]
+    fn set_python_env_works() {
+        let mut env = PythonEnv::probe("python3").unwrap();
+        env.numpy_version = Some("1.26.0".to_string());
+        env.matplotlib_version = Some("3.8.0".to_string());
+        env.has_latex = false;
+        let mut style = Style::new();
+        style.set_usetex(true);
+        let mut plot = Plot::new();
+        plot.set_style(&style);
+        let mut document = PdfDocument::new();
+        document
+            .set_python_env(env)
+            .add_page(&Plot::new())
+            .unwrap()
+            .add_page(&plot)
+            .unwrap();
+        let err = document
+            .save("/tmp/plotpy/unit_tests/pdf_document_env.pdf")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "text.usetex is not supported: it requires LaTeX");
+    }
+
+    #[test]
+    fn add_page_captures_errors() {
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 1.0], &[0.0, f64::INFINITY]);
+        let mut plot = Plot::new();
+        plot.add(&curve).set_non_finite(NonFinite::Reject);
+        let mut document = PdfDocument::new();
+        assert_eq!(
+            document.add_page(&plot).err().unwrap().to_string(),
+            "the data array y has non-finite values (NaN or ±inf)"
+        );
+        let mut plot = Plot::new();
+        plot.set_non_finite(NonFinite::Reject).set_xrange(0.0, f64::INFINITY);
+        assert_eq!(
+            document.add_page(&plot).err().unwrap().to_string(),
+            "the axes limits given to set_xrange are not finite (NaN or ±inf)"
+        );
+        assert_eq!(document.pages.len(), 0);
+        plot.set_non_finite(NonFinite::Keep);
+        assert!(document.add_page(&plot).is_ok());
+        assert_eq!(document.pages.len(), 1);
+    }
+}
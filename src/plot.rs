@@ -1,11 +1,20 @@
-use super::{call_python3, generate_list_quoted, vector_to_array, AsVector, Legend, StrError, SuperTitleParams};
+use super::{
+    absolute_path, call_python3, call_python3_capture, generate_list_quoted, quote, vector_to_array, write_npy_file,
+    write_python_file, AsTimestamp, AsVector, AxesId, AxisTransform, DataArrays, Error, Formatter, GridLayout, Legend,
+    Locator, NonFinite, ProcessOptions, PythonEnv, PythonOutput, PythonSession, Requirements, Scale, StrError, Style,
+    SuperTitleParams, ZeroLocation,
+};
 use crate::PYTHON_HEADER;
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::fmt::Write;
 use std::fs::{self, File};
 use std::io::Write as IoWrite;
-use std::path::Path;
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 
 const DEFAULT_PYTHON_EXE: &str = "python3";
 
@@ -14,6 +23,14 @@ pub trait GraphMaker {
     /// Returns the text buffer with Python3 commands
     fn get_buffer<'a>(&'a self) -> &'a String;
 
+    /// Returns the data arrays written to the text buffer, if any (see [DataArrays])
+    ///
+    /// The default implementation returns None; thus, the data arrays remain in the Python script as text
+    /// and the policy set by [Plot::set_non_finite()] does not apply to them.
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        None
+    }
+
     /// Clear the text buffer with Python commands
     fn clear_buffer(&mut self);
 }
@@ -25,9 +42,9 @@ pub trait GraphMaker {
 /// ## Drawing curves
 ///
 /// ```
-/// use plotpy::{linspace, Curve, Plot, StrError};
+/// use plotpy::{linspace, Curve, Error, Plot};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // generate (x,y) points
 ///     let n = 11;
 ///     let x = linspace(-1.0, 1.0, n);
@@ -93,9 +110,9 @@ pub trait GraphMaker {
 /// See [Matplotlib example](https://matplotlib.org/stable/gallery/images_contours_and_fields/image_annotated_heatmap.html)
 ///
 /// ```
-/// use plotpy::{Image, Plot, Text, StrError};
+/// use plotpy::{Error, Image, Plot, Text};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // data
 ///     let vegetables = [
 ///         "cucumber",
@@ -157,12 +174,28 @@ pub trait GraphMaker {
 ///
 /// See also integration tests in the [tests directory](https://github.com/cpmech/plotpy/tree/main/tests)
 pub struct Plot {
-    show_errors: bool,              // show python errors, if any
-    buffer: String,                 // buffer
-    save_tight: bool,               // option for savefig: enable bbox_inches='tight'
-    save_pad_inches: Option<f64>,   // option for savefig: add some padding when save_tight==true
-    save_transparent: Option<bool>, // option for savefig: make it transparent
-    python_exe: String,             // `python3` or simply `python` (e.g., on Windows)
+    show_errors: bool,                  // show python errors, if any
+    buffer: String,                     // buffer
+    arrays: DataArrays,                 // data arrays written to the buffer
+    save_tight: bool,                   // option for savefig: enable bbox_inches='tight'
+    save_pad_inches: Option<f64>,       // option for savefig: add some padding when save_tight==true
+    save_transparent: Option<bool>,     // option for savefig: make it transparent
+    save_dpi: BTreeMap<String, usize>,  // option for savefig: dpi for each format (extension)
+    python_exe: String,                 // `python3` or simply `python` (e.g., on Windows)
+    binary_data: bool,                  // write the NumPy arrays to a binary .npy file
+    strict: bool,                       // treat python warnings (any output) as errors
+    diagnostics: Mutex<Vec<String>>,    // warnings (or other output) of the last successful run
+    render_keep_files: Option<PathBuf>, // path of the python script written by render
+    process: ProcessOptions,            // timeout, environment, and working directory of python3
+    python_env: Option<PythonEnv>,      // environment used to check the features before running python3
+    requirements: Requirements,         // features used by the python commands (checked against python_env)
+    style: Style,                       // default appearance (applied at the top of the script)
+    deterministic: bool,                // generate identical files for identical inputs
+    deterministic_env: Vec<String>,     // environment variables set by set_deterministic (removed when disabled)
+    non_finite: NonFinite,              // how the non-finite values of the data are handled
+    non_finite_limits: Option<String>,  // first function given non-finite axes limits (rejected by NonFinite::Reject)
+    axes_count: usize,                  // number of axes in the AXES list of the python script
+    grid_layouts: Vec<GridLayout>,      // layouts written to the python script as GRID_0, GRID_1, ...
 }
 
 impl Plot {
@@ -171,16 +204,33 @@ impl Plot {
         Plot {
             show_errors: false,
             buffer: String::new(),
+            arrays: DataArrays::new(),
             save_tight: true,
             save_pad_inches: None,
             save_transparent: None,
+            save_dpi: BTreeMap::new(),
             python_exe: DEFAULT_PYTHON_EXE.to_string(),
+            binary_data: false,
+            strict: false,
+            diagnostics: Mutex::new(Vec::new()),
+            render_keep_files: None,
+            process: ProcessOptions::default(),
+            python_env: None,
+            requirements: Requirements::default(),
+            style: Style::new(),
+            deterministic: false,
+            deterministic_env: Vec::new(),
+            non_finite: NonFinite::Keep,
+            non_finite_limits: None,
+            axes_count: 0,
+            grid_layouts: Vec::new(),
         }
     }
 
     /// Adds new graph entity
     pub fn add(&mut self, graph: &dyn GraphMaker) -> &mut Self {
-        self.buffer.push_str(graph.get_buffer());
+        self.arrays
+            .append(&mut self.buffer, graph.get_buffer(), graph.get_arrays());
         self
     }
 
@@ -205,6 +255,50 @@ impl Plot {
         self
     }
 
+    /// Writes the data arrays to a binary file instead of the python script
+    ///
+    /// When enabled, the NumPy arrays generated from [AsVector] and [crate::AsMatrix] data are saved
+    /// to a `.npy` file next to the python script (same name as the figure, but with the `.npy` extension)
+    /// and loaded by `np.load`. This option makes the python script much smaller and faster to run
+    /// when there is a lot of data; e.g., contours or surfaces with fine grids.
+    ///
+    /// # Notes
+    ///
+    /// 1. The values are recovered bit-exactly by Python
+    /// 2. The script refers to the `.npy` file by its path; thus, both files must be kept together
+    pub fn set_binary_data(&mut self, flag: bool) -> &mut Self {
+        self.binary_data = flag;
+        self
+    }
+
+    /// Sets how the non-finite values (NaN, +inf, and -inf) of the data are handled (default = Keep)
+    ///
+    /// The policy applies to the data arrays of all graph entities (e.g., Curve, Contour, Histogram)
+    /// when the script is generated; e.g., by [Plot::save()]. The Reject policy also applies to the
+    /// axes limits (e.g., [Plot::set_range()]), which returns [Error::NonFiniteLimits].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, NonFinite, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[1.0, 2.0, 3.0], &[1.0, f64::INFINITY, 3.0]);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve).set_non_finite(NonFinite::Reject);
+    ///     let err = plot.save("/tmp/plotpy/doc_tests/doc_set_non_finite.svg").err().unwrap();
+    ///     assert_eq!(err.to_string(), "the data array y has non-finite values (NaN or ±inf)");
+    ///     plot.set_non_finite(NonFinite::Mask);
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_set_non_finite.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_non_finite(&mut self, policy: NonFinite) -> &mut Self {
+        self.non_finite = policy;
+        self
+    }
+
     /// Calls Python and saves the python script and figure
     ///
     /// # Input
@@ -215,13 +309,67 @@ impl Plot {
     ///
     /// 1. You may want to call [Plot::set_show_errors()] to enable the
     ///    display of Python errors (if any)
-    pub fn save<S>(&self, figure_path: &S) -> Result<(), StrError>
+    /// 2. Python warnings do not make this function fail; see [Plot::get_diagnostics()] and [Plot::set_strict()]
+    pub fn save<S>(&self, figure_path: &S) -> Result<(), Error>
     where
         S: AsRef<OsStr> + ?Sized,
     {
         self.run(figure_path, false)
     }
 
+    /// Calls Python once and saves the python script and the figure in several formats
+    ///
+    /// The generated script calls `savefig` once for each path; the format is given by the extension.
+    ///
+    /// # Input
+    ///
+    /// * `figure_paths` -- may be Strings, &strs, or Paths
+    ///
+    /// # Notes
+    ///
+    /// 1. The python script (and log file) has the same name as the first figure, but with the `.py` extension
+    /// 2. The DPI of each format may be set by [Plot::set_save_dpi()]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve).set_save_dpi("png", 300);
+    ///     plot.save_multiple(&[
+    ///         "/tmp/plotpy/doc_tests/doc_save_multiple.pdf",
+    ///         "/tmp/plotpy/doc_tests/doc_save_multiple.svg",
+    ///         "/tmp/plotpy/doc_tests/doc_save_multiple.png",
+    ///     ])?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn save_multiple<S>(&self, figure_paths: &[S]) -> Result<(), Error>
+    where
+        S: AsRef<OsStr>,
+    {
+        let paths: Vec<&Path> = figure_paths.iter().map(Path::new).collect();
+        if paths.is_empty() {
+            return Err(Error::InvalidInput("figure_paths must not be empty"));
+        }
+        self.run_with_stem(&paths, paths[0], false)
+    }
+
+    /// Sets the resolution (dots per inch) used by savefig for a format
+    ///
+    /// # Input
+    ///
+    /// * `format` -- the format given by the extension of the figure; e.g., "png"
+    /// * `dpi` -- the resolution
+    pub fn set_save_dpi(&mut self, format: &str, dpi: usize) -> &mut Self {
+        self.save_dpi.insert(format.to_lowercase(), dpi);
+        self
+    }
+
     /// Calls Python, saves the python script and figure, and shows the plot window
     ///
     /// # Input
@@ -233,14 +381,14 @@ impl Plot {
     /// 1. You may want to call [Plot::set_show_errors()] to enable the
     ///    display of Python errors (if any)
     /// 2. This function will also save a figure as [Plot::save()] does
-    pub fn show<S>(&self, figure_path: &S) -> Result<(), StrError>
+    pub fn show<S>(&self, figure_path: &S) -> Result<(), Error>
     where
         S: AsRef<OsStr> + ?Sized,
     {
         self.run(figure_path, true)
     }
 
-    /// Calls Python, saves the python script and figure, and shows the result in a Jupyter notebook
+    /// Calls Python and shows the result in a Jupyter notebook
     ///
     /// **Important:** This function requires [evcxr_jupyter](https://github.com/evcxr/evcxr).
     ///
@@ -252,21 +400,84 @@ impl Plot {
     ///
     /// 1. You may want to call [Plot::set_show_errors()] to enable the
     ///    display of Python errors (if any)
-    /// 2. This function will also save a figure as [Plot::save()] does
+    /// 2. The figure is rendered by [Plot::render()] in the SVG format and also written to `figure_path`
     /// 3. This method only works in a Jupyter Notebook
-    pub fn show_in_jupyter<S>(&self, figure_path: &S) -> Result<(), StrError>
+    pub fn show_in_jupyter<S>(&self, figure_path: &S) -> Result<(), Error>
     where
         S: AsRef<OsStr> + ?Sized,
     {
-        self.run(figure_path, false)?;
+        let figure = self.render("svg")?;
         let fig_path = Path::new(figure_path);
-        match fs::read_to_string(fig_path) {
-            Ok(figure) => println!("EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT", figure),
-            Err(_) => return Err("Failed to read the SVG figure, please check it."),
+        if let Some(p) = fig_path.parent() {
+            fs::create_dir_all(p).map_err(Error::io(p))?;
         }
+        fs::write(fig_path, &figure).map_err(Error::io(fig_path))?;
+        println!(
+            "EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT",
+            String::from_utf8_lossy(&figure)
+        );
         Ok(())
     }
 
+    /// Calls Python and returns the figure as bytes (without writing any file)
+    ///
+    /// # Input
+    ///
+    /// * `format` -- the figure format such as "png", "svg", or "pdf"
+    ///
+    /// # Notes
+    ///
+    /// 1. The python script is passed to Python via stdin; thus, no script or log file is written,
+    ///    unless [Plot::set_render_keep_files()] is called
+    /// 2. The output of `print` commands is treated as the output of warnings (see [Plot::get_diagnostics()])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve);
+    ///     let png = plot.render("png")?;
+    ///     assert_eq!(&png[1..4], b"PNG");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn render(&self, format: &str) -> Result<Vec<u8>, Error> {
+        self.check_commands()?;
+        let path = self.render_keep_files.as_deref();
+        let commands = format!(
+            "#### >>>> file generated by plotpy <<<< ####\n\nimport matplotlib\nmatplotlib.use('Agg')\n\
+             {}import io, sys\nSTDOUT=sys.stdout.buffer\nsys.stdout=sys.stderr\n\
+             {}{}{}\nbuf=io.BytesIO()\nplt.savefig(buf,format={}{})\nSTDOUT.write(buf.getvalue())\n",
+            PYTHON_HEADER,
+            self.preamble_commands(),
+            self.data_commands(path)?,
+            self.style.get_presave_commands(),
+            quote(format),
+            self.savefig_options(format)
+        );
+        let (result, figure) = self.call_python_capture(&commands, path)?;
+        self.handle_output(path, &result)?;
+        Ok(figure)
+    }
+
+    /// Keeps the python script (and log file) written by [Plot::render()]
+    ///
+    /// # Input
+    ///
+    /// * `script_path` -- the path of the python script; the log file has the same name but with the `.log` extension
+    pub fn set_render_keep_files<S>(&mut self, script_path: &S) -> &mut Self
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        self.render_keep_files = Some(Path::new(script_path).to_path_buf());
+        self
+    }
+
     /// Clears the current axes
     pub fn clear_current_axes(&mut self) -> &mut Self {
         self.buffer.push_str("plt.gca().cla()\n");
@@ -292,9 +503,10 @@ impl Plot {
             &mut self.buffer,
             "plt.gca().set_axisbelow(True)\n\
              plt.grid(linestyle='--',color='grey',zorder=-1000)\n\
-             plt.gca().set_xlabel(r'{}')\n\
-             plt.gca().set_ylabel(r'{}')\n",
-            xlabel, ylabel
+             plt.gca().set_xlabel({})\n\
+             plt.gca().set_ylabel({})\n",
+            quote(xlabel),
+            quote(ylabel)
         )
         .unwrap();
         self
@@ -306,9 +518,10 @@ impl Plot {
             &mut self.buffer,
             "plt.gca().set_axisbelow(True)\n\
              plt.grid(linestyle='--',color='grey',zorder=-1000)\n\
-             plt.gca().set_xlabel(r'{}')\n\
-             plt.gca().set_ylabel(r'{}')\n",
-            xlabel, ylabel
+             plt.gca().set_xlabel({})\n\
+             plt.gca().set_ylabel({})\n",
+            quote(xlabel),
+            quote(ylabel)
         )
         .unwrap();
         self.legend()
@@ -320,6 +533,25 @@ impl Plot {
         self
     }
 
+    /// Enables the strict mode where python warnings are treated as errors
+    ///
+    /// By default, the success of [Plot::save()] and [Plot::show()] is based on the exit status
+    /// of Python; thus, warnings (e.g., a Matplotlib `UserWarning`) or the output of `print`
+    /// commands are collected by [Plot::get_diagnostics()]. In strict mode, any output
+    /// makes these functions return [Error::Output].
+    pub fn set_strict(&mut self, flag: bool) -> &mut Self {
+        self.strict = flag;
+        self
+    }
+
+    /// Returns the warnings (or other output) written by Python during the last run
+    ///
+    /// Each entry holds one message (e.g., one warning) including its indented continuation lines.
+    /// The complete output is also written to the log file next to the figure.
+    pub fn get_diagnostics(&self) -> Vec<String> {
+        self.diagnostics.lock().unwrap().clone()
+    }
+
     /// Configures 3D subplots
     ///
     /// # Input
@@ -329,9 +561,53 @@ impl Plot {
     /// * `index` -- activate current 3D subplot; **indices start at one** (1-based)
     pub fn set_subplot_3d(&mut self, row: usize, col: usize, index: usize) -> &mut Self {
         write!(&mut self.buffer, "\nsubplot_3d({},{},{})\n", row, col, index).unwrap();
+        self.requirements.three_d = true;
         self
     }
 
+    /// Adds a subplot with polar axes and makes it the current one
+    ///
+    /// The x and y values of the 2D graphs (e.g., [crate::Curve], [crate::FillBetween], [crate::Contour],
+    /// [crate::Text], and [crate::Canvas]) are the angle θ (in radians) and the radius r, respectively.
+    /// See [Plot::set_polar_zero_location()] and related functions.
+    ///
+    /// # Input
+    ///
+    /// * `row` -- number of rows in the subplot grid
+    /// * `col` -- number of columns in the subplot grid
+    /// * `index` -- position of the subplot; **indices start at one** (1-based)
+    ///
+    /// # Output
+    ///
+    /// Returns the handle of the new axes (see [Plot::set_axes()] and [Plot::add_to()]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{linspace, Curve, Error, Plot, ZeroLocation};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let theta = linspace(0.0, 2.0 * std::f64::consts::PI, 201);
+    ///     let r: Vec<_> = theta.iter().map(|t| f64::abs(f64::cos(2.0 * t))).collect();
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&theta, &r);
+    ///     let mut plot = Plot::new();
+    ///     let polar = plot.set_subplot_polar(1, 1, 1);
+    ///     plot.add_to(polar, &curve)
+    ///         .set_polar_zero_location(ZeroLocation::N, 0.0)
+    ///         .set_polar_clockwise(true)
+    ///         .set_polar_ticks_multiple_of_pi(4);
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_subplot_polar.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_subplot_polar(&mut self, row: usize, col: usize, index: usize) -> AxesId {
+        self.new_axes(&format!(
+            "plt.gcf().add_subplot({},{},{},projection='polar')",
+            row, col, index
+        ))
+    }
+
     /// Configures subplots
     ///
     /// # Input
@@ -346,6 +622,8 @@ impl Plot {
 
     /// Configures subplots using GridSpec
     ///
+    /// **Note:** See [GridLayout] and [Plot::grid_axes()] for a typed alternative (checked in Rust).
+    ///
     /// # Input
     ///
     /// * `grid_handle` -- an identifier for GridSpec to be used later with [Plot::set_subplot_grid]
@@ -382,6 +660,245 @@ impl Plot {
         self
     }
 
+    /// Creates a grid of subplots and returns their handles
+    ///
+    /// The handles are given to [Plot::set_axes()] or [Plot::add_to()] to select the axes receiving the
+    /// next commands (e.g., the graphs, ranges, ticks, and labels); thus, the axes may be configured in any order.
+    /// After this call, the first axes is the current one.
+    ///
+    /// # Input
+    ///
+    /// * `nrow` -- number of rows in the grid
+    /// * `ncol` -- number of columns in the grid
+    ///
+    /// # Output
+    ///
+    /// Returns the `nrow * ncol` handles row by row; i.e., the axes at `(i, j)` is `axes[i * ncol + j]`.
+    ///
+    /// **Note:** The axes are 2D; use [Plot::set_subplot_3d()] for 3D plots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+    ///     let mut plot = Plot::new();
+    ///     let axes = plot.subplots(2, 2);
+    ///     plot.add_to(axes[3], &curve).set_title("last");
+    ///     plot.set_axes(axes[0]).set_title("first").set_range(0.0, 1.0, 0.0, 1.0);
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_subplots.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn subplots(&mut self, nrow: usize, ncol: usize) -> Vec<AxesId> {
+        let first = self.axes_count;
+        write!(
+            &mut self.buffer,
+            "\nAXES+=list(plt.gcf().subplots({},{},squeeze=False).flat)\nplt.sca(AXES[{}])\n",
+            nrow, ncol, first
+        )
+        .unwrap();
+        self.axes_count += nrow * ncol;
+        (first..self.axes_count).map(AxesId::new).collect()
+    }
+
+    /// Creates an axes spanning some cells of a grid layout and returns its handle
+    ///
+    /// The new axes becomes the current one. The same layout may be given to several calls; each
+    /// call adds an axes to the same grid. See [GridLayout] and [Plot::subplots()].
+    ///
+    /// # Input
+    ///
+    /// * `layout` -- the grid layout
+    /// * `rows` -- the **zero-based** range of rows; e.g., `0..2`, `1..=1`, or `..`
+    /// * `cols` -- the **zero-based** range of columns; e.g., `0..1`, `2..`, or `..`
+    ///
+    /// **Note:** Nothing is written to the python script if the layout or the ranges are invalid.
+    pub fn grid_axes<R, C>(&mut self, layout: &GridLayout, rows: R, cols: C) -> Result<AxesId, StrError>
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        layout.validate()?;
+        let (rows, cols) = layout.cell(rows, cols)?;
+        let index = match self.grid_layouts.iter().position(|l| l == layout) {
+            Some(index) => index,
+            None => {
+                let index = self.grid_layouts.len();
+                self.buffer.push_str(&layout.commands(&format!("GRID_{}", index)));
+                self.grid_layouts.push(layout.clone());
+                index
+            }
+        };
+        let spec = format!(
+            "GRID_{}[{}:{},{}:{}]",
+            index, rows.start, rows.end, cols.start, cols.end
+        );
+        Ok(self.new_axes(&format!("plt.gcf().add_subplot({})", spec)))
+    }
+
+    /// Creates the axes of a grid layout given by an ASCII mosaic and returns their handles by name
+    ///
+    /// The axes are created in the order of their first appearance in the mosaic (row by row) and the
+    /// last one becomes the current axes. See [GridLayout::from_mosaic()].
+    pub fn subplot_mosaic(&mut self, layout: &GridLayout) -> Result<BTreeMap<char, AxesId>, StrError> {
+        if layout.panels().is_empty() {
+            return Err("the grid layout must be created from a mosaic");
+        }
+        let mut axes = BTreeMap::new();
+        for (name, rows, cols) in layout.panels() {
+            axes.insert(*name, self.grid_axes(layout, rows.clone(), cols.clone())?);
+        }
+        Ok(axes)
+    }
+
+    /// Creates a twin axes sharing the x-axis of the current axes and returns its handle
+    ///
+    /// The twin axes has an independent y-axis drawn on the right-hand side. The twin axes becomes the
+    /// current one; thus, the next graphs and the range, ticks, labels, and scale commands (e.g.,
+    /// [Plot::set_range()], [Plot::set_label_y()], and [Plot::set_log_y()]) apply to the twin axes.
+    /// Use [Plot::set_axes()] to go back and forth between the axes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut temperature = Curve::new();
+    ///     let mut pressure = Curve::new();
+    ///     temperature.draw(&[0.0, 1.0, 2.0], &[20.0, 25.0, 23.0]);
+    ///     pressure.set_line_color("red").draw(&[0.0, 1.0, 2.0], &[1.0, 10.0, 100.0]);
+    ///     let mut plot = Plot::new();
+    ///     let main = plot.current_axes();
+    ///     plot.add(&temperature).set_label_y("temperature");
+    ///     let twin = plot.twin_x();
+    ///     plot.add_to(twin, &pressure)
+    ///         .set_log_y(true)
+    ///         .set_label_y("pressure")
+    ///         .set_label_y_color("red");
+    ///     plot.set_axes(main).set_label_x("time");
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_twin_x.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn twin_x(&mut self) -> AxesId {
+        self.new_axes("plt.gca().twinx()")
+    }
+
+    /// Creates a twin axes sharing the y-axis of the current axes and returns its handle
+    ///
+    /// The twin axes has an independent x-axis drawn on the top side. See [Plot::twin_x()].
+    pub fn twin_y(&mut self) -> AxesId {
+        self.new_axes("plt.gca().twiny()")
+    }
+
+    /// Returns the handle of the current axes
+    ///
+    /// The current axes is created if needed. A new handle is returned even if the current axes already
+    /// has one (both handles refer to the same axes). This is useful to go back to the main axes after
+    /// [Plot::twin_x()] or [Plot::twin_y()].
+    pub fn current_axes(&mut self) -> AxesId {
+        let axes = AxesId::new(self.axes_count);
+        self.buffer.push_str("\nAXES.append(plt.gca())\n");
+        self.axes_count += 1;
+        axes
+    }
+
+    /// Adds a secondary x-axis (on the top) to the current axes
+    ///
+    /// The secondary axis shows the values of the x-axis converted by the given transform; e.g.,
+    /// the frequency corresponding to the wavelength or the temperature in kelvin. The ticks of the
+    /// secondary axis follow the range and scale of the primary axis.
+    ///
+    /// # Input
+    ///
+    /// * `transform` -- the conversion of the values
+    /// * `label` -- the label of the secondary axis (ignored if empty)
+    ///
+    /// **Note:** Nothing is written to the python script if the transform is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{AxisTransform, Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 90.0, 180.0], &[0.0, 1.0, 0.0]);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve)
+    ///         .set_label_x("angle [deg]")
+    ///         .set_secondary_x_axis(AxisTransform::DegreesToRadians, "angle [rad]")?;
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_secondary_x_axis.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_secondary_x_axis(&mut self, transform: AxisTransform, label: &str) -> Result<&mut Self, StrError> {
+        self.secondary_axis("x", "top", transform, label)
+    }
+
+    /// Adds a secondary y-axis (on the right-hand side) to the current axes
+    ///
+    /// See [Plot::set_secondary_x_axis()].
+    pub fn set_secondary_y_axis(&mut self, transform: AxisTransform, label: &str) -> Result<&mut Self, StrError> {
+        self.secondary_axis("y", "right", transform, label)
+    }
+
+    /// Writes the commands to add a secondary axis
+    fn secondary_axis(
+        &mut self,
+        axis: &str,
+        location: &str,
+        transform: AxisTransform,
+        label: &str,
+    ) -> Result<&mut Self, StrError> {
+        let (forward, inverse) = transform.functions()?;
+        write!(
+            &mut self.buffer,
+            "sec=plt.gca().secondary_{}axis('{}',functions=({},{}))\n",
+            axis, location, forward, inverse
+        )
+        .unwrap();
+        if label != "" {
+            write!(&mut self.buffer, "sec.set_{}label({})\n", axis, quote(label)).unwrap();
+        }
+        Ok(self)
+    }
+
+    /// Appends a new axes (given by a python expression) to the AXES list and makes it the current one
+    fn new_axes(&mut self, expression: &str) -> AxesId {
+        let axes = AxesId::new(self.axes_count);
+        write!(
+            &mut self.buffer,
+            "\nAXES.append({})\nplt.sca(AXES[{}])\n",
+            expression,
+            axes.index()
+        )
+        .unwrap();
+        self.axes_count += 1;
+        axes
+    }
+
+    /// Makes the given axes the current one
+    ///
+    /// The next commands (e.g., [Plot::add()], [Plot::set_range()], and [Plot::set_labels()]) apply to this axes.
+    /// See [Plot::subplots()].
+    pub fn set_axes(&mut self, axes: AxesId) -> &mut Self {
+        write!(&mut self.buffer, "\nplt.sca(AXES[{}])\n", axes.index()).unwrap();
+        self
+    }
+
+    /// Adds new graph entity to the given axes
+    ///
+    /// The axes remains the current one afterwards. See [Plot::subplots()].
+    pub fn add_to(&mut self, axes: AxesId, graph: &dyn GraphMaker) -> &mut Self {
+        self.set_axes(axes).add(graph)
+    }
+
     /// Sets the rotation of ticks along the x-axis
     pub fn set_rotation_ticks_x(&mut self, rotation: f64) -> &mut Self {
         write!(
@@ -412,14 +929,10 @@ impl Plot {
 
     /// Adds a title to the plot or sub-plot
     ///
-    /// # Notes
-    ///
-    /// 1. Single quotation marks are replaced by the [UTF-8 Right Single Quotation Mark](https://www.compart.com/en/unicode/U+2019)
-    ///    because the Python script already uses the single quotation mark. Note that cannot use the raw notation `"""` because
-    ///    otherwise some TeX formula wouldn't work; notably the ones starting with `\v` such as `\varepsilon`
+    /// **Note:** The title may have any character, including quotation marks, backslashes,
+    /// and newlines; the TeX formulae (e.g., `$\varepsilon$`) are passed to Matplotlib unchanged.
     pub fn set_title(&mut self, title: &str) -> &mut Self {
-        let t = title.replace("'", "’");
-        write!(&mut self.buffer, "plt.title(r'{}')\n", t).unwrap();
+        write!(&mut self.buffer, "plt.title({})\n", quote(title)).unwrap();
         self
     }
 
@@ -427,17 +940,14 @@ impl Plot {
     ///
     /// # Notes
     ///
-    /// 1. Single quotation marks are replaced by the [UTF-8 Right Single Quotation Mark](https://www.compart.com/en/unicode/U+2019)
-    ///    because the Python script already uses the single quotation mark. Note that cannot use the raw notation `"""` because
-    ///    otherwise some TeX formula wouldn't work; notably the ones starting with `\v` such as `\varepsilon`
-    /// 2. The TeX handling in the super-title string is more limited than in the title string
-    ///    because we cannot use Python's raw string notation (`r''`) here. The reason is that we want
-    ///    the super-title to be wrapped if it is too long and only non-raw strings can do that.
+    /// 1. The title may have any character as in [Plot::set_title()]
+    /// 2. The sequence `\n` (backslash followed by `n`) is also converted to a newline; thus, the TeX
+    ///    commands starting with `\n` (e.g., `\nu`) cannot be used in the super-title
     pub fn set_super_title(&mut self, title: &str, params: Option<&SuperTitleParams>) -> &mut Self {
-        let t = title.replace("'", "’").replace("\\n", "'+'\\n'+r'");
+        let t = quote(&title.replace("\\n", "\n"));
         match params {
-            Some(p) => write!(&mut self.buffer, "st=plt.suptitle(r'{}'{})\n", t, p.options()).unwrap(),
-            None => write!(&mut self.buffer, "st=plt.suptitle(r'{}')\n", t).unwrap(),
+            Some(p) => write!(&mut self.buffer, "st=plt.suptitle({}{})\n", t, p.options()).unwrap(),
+            None => write!(&mut self.buffer, "st=plt.suptitle({})\n", t).unwrap(),
         }
         write!(&mut self.buffer, "add_to_ea(st)\n").unwrap();
         self
@@ -470,6 +980,7 @@ impl Plot {
     pub fn set_equal_axes(&mut self, equal: bool) -> &mut Self {
         if equal {
             self.buffer.push_str("set_equal_axes()\n");
+            self.requirements.equal_axes = true;
         } else {
             self.buffer.push_str("plt.gca().axes.set_aspect('auto')\n");
         }
@@ -542,12 +1053,14 @@ impl Plot {
             xmin, xmax, ymin, ymax, zmin, zmax,
         )
         .unwrap();
+        self.record_limits("set_range_3d", &[xmin, xmax, ymin, ymax, zmin, zmax]);
         self
     }
 
     /// Sets axes limits
     pub fn set_range(&mut self, xmin: f64, xmax: f64, ymin: f64, ymax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.axis([{},{},{},{}])\n", xmin, xmax, ymin, ymax).unwrap();
+        self.record_limits("set_range", &[xmin, xmax, ymin, ymax]);
         self
     }
 
@@ -559,60 +1072,133 @@ impl Plot {
             limits[0], limits[1], limits[2], limits[3]
         )
         .unwrap();
+        self.record_limits("set_range_from_vec", &limits[0..4]);
         self
     }
 
     /// Sets minimum x
     pub fn set_xmin(&mut self, xmin: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_xlim(left={})\n", xmin).unwrap();
+        self.record_limits("set_xmin", &[xmin]);
         self
     }
 
     /// Sets maximum x
     pub fn set_xmax(&mut self, xmax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_xlim(right={})\n", xmax).unwrap();
+        self.record_limits("set_xmax", &[xmax]);
         self
     }
 
     /// Sets minimum y
     pub fn set_ymin(&mut self, ymin: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_ylim(bottom={})\n", ymin).unwrap();
+        self.record_limits("set_ymin", &[ymin]);
         self
     }
 
     /// Sets maximum y
     pub fn set_ymax(&mut self, ymax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_ylim(top={})\n", ymax).unwrap();
+        self.record_limits("set_ymax", &[ymax]);
         self
     }
 
     /// Sets minimum z
     pub fn set_zmin(&mut self, zmin: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_zlim(zmin={})\n", zmin).unwrap();
+        self.record_limits("set_zmin", &[zmin]);
         self
     }
 
     /// Sets maximum z
     pub fn set_zmax(&mut self, zmax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_zlim(zmax={})\n", zmax).unwrap();
+        self.record_limits("set_zmax", &[zmax]);
         self
     }
 
     /// Sets x-range (i.e. limits)
     pub fn set_xrange(&mut self, xmin: f64, xmax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_xlim([{},{}])\n", xmin, xmax).unwrap();
+        self.record_limits("set_xrange", &[xmin, xmax]);
         self
     }
 
     /// Sets y-range (i.e. limits)
     pub fn set_yrange(&mut self, ymin: f64, ymax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_ylim([{},{}])\n", ymin, ymax).unwrap();
+        self.record_limits("set_yrange", &[ymin, ymax]);
+        self
+    }
+
+    /// Sets x-range (i.e. limits) with dates
+    ///
+    /// See [AsTimestamp] and [Plot::set_time_zone()].
+    pub fn set_xrange_dates<T>(&mut self, tmin: T, tmax: T) -> &mut Self
+    where
+        T: AsTimestamp,
+    {
+        write!(
+            &mut self.buffer,
+            "plt.gca().set_xlim(to_dates([{},{}]))\n",
+            tmin.timestamp_literal(),
+            tmax.timestamp_literal()
+        )
+        .unwrap();
+        self
+    }
+
+    /// Sets y-range (i.e. limits) with dates
+    ///
+    /// See [AsTimestamp] and [Plot::set_time_zone()].
+    pub fn set_yrange_dates<T>(&mut self, tmin: T, tmax: T) -> &mut Self
+    where
+        T: AsTimestamp,
+    {
+        write!(
+            &mut self.buffer,
+            "plt.gca().set_ylim(to_dates([{},{}]))\n",
+            tmin.timestamp_literal(),
+            tmax.timestamp_literal()
+        )
+        .unwrap();
+        self
+    }
+
+    /// Sets the time zone of the dates
+    ///
+    /// The timestamps (see [AsTimestamp]) are converted to dates in this time zone and the date
+    /// ticks are labelled in this time zone. The default is UTC.
+    ///
+    /// * `name` -- an IANA time zone name; e.g., "UTC", "America/New_York", or "Europe/Berlin"
+    ///
+    /// **Note:** This function must be called before adding the graphs with dates.
+    pub fn set_time_zone(&mut self, name: &str) -> &mut Self {
+        write!(&mut self.buffer, "TIME_ZONE=time_zone({})\n", quote(name)).unwrap();
+        self
+    }
+
+    /// Sets the AutoDateLocator and ConciseDateFormatter to the x-axis
+    ///
+    /// **Note:** This is done automatically by the functions drawing dates (e.g., [crate::Curve::draw_dates()]).
+    pub fn set_date_axis_x(&mut self) -> &mut Self {
+        self.buffer.push_str("set_date_axis(plt.gca().xaxis)\n");
+        self
+    }
+
+    /// Sets the AutoDateLocator and ConciseDateFormatter to the y-axis
+    ///
+    /// **Note:** This is done automatically by [crate::Barplot::draw_dates()] with horizontal bars.
+    pub fn set_date_axis_y(&mut self) -> &mut Self {
+        self.buffer.push_str("set_date_axis(plt.gca().yaxis)\n");
         self
     }
 
     /// Sets z-range (i.e. limits)
     pub fn set_zrange(&mut self, zmin: f64, zmax: f64) -> &mut Self {
         write!(&mut self.buffer, "plt.gca().set_zlim([{},{}])\n", zmin, zmax).unwrap();
+        self.record_limits("set_zrange", &[zmin, zmax]);
         self
     }
 
@@ -661,6 +1247,173 @@ impl Plot {
         self
     }
 
+    /// Sets the locator of the major ticks along x
+    ///
+    /// **Note:** Nothing is written to the python script if the locator is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{linspace, Curve, Error, Formatter, Locator, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let x = linspace(0.0, 2e6, 11);
+    ///     let y: Vec<_> = x.iter().map(|v| v / 2e6).collect();
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&x, &y);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve)
+    ///         .set_locator_x(&Locator::MaxN { nbins: 4, integer: false })?
+    ///         .set_minor_locator_x(&Locator::AutoMinor { ndivs: 5 })?
+    ///         .set_formatter_x(&Formatter::Engineering { unit: "Hz".to_string(), places: None })?
+    ///         .set_formatter_y(&Formatter::Percent { xmax: 1.0, decimals: Some(0) })?;
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_locator_formatter.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_locator_x(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("x", "major", locator)
+    }
+
+    /// Sets the locator of the major ticks along y
+    ///
+    /// See [Plot::set_locator_x()].
+    pub fn set_locator_y(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("y", "major", locator)
+    }
+
+    /// Sets the locator of the major ticks along z (3D only)
+    ///
+    /// See [Plot::set_locator_x()].
+    pub fn set_locator_z(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("z", "major", locator)
+    }
+
+    /// Sets the locator of the minor ticks along x
+    ///
+    /// See [Plot::set_locator_x()].
+    pub fn set_minor_locator_x(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("x", "minor", locator)
+    }
+
+    /// Sets the locator of the minor ticks along y
+    ///
+    /// See [Plot::set_locator_x()].
+    pub fn set_minor_locator_y(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("y", "minor", locator)
+    }
+
+    /// Sets the locator of the minor ticks along z (3D only)
+    ///
+    /// See [Plot::set_locator_x()].
+    pub fn set_minor_locator_z(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        self.tick_locator("z", "minor", locator)
+    }
+
+    /// Sets the formatter of the major ticks along x
+    ///
+    /// **Note:** Nothing is written to the python script if the formatter is invalid.
+    /// See [Plot::set_locator_x()].
+    pub fn set_formatter_x(&mut self, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        self.tick_formatter("x", formatter)
+    }
+
+    /// Sets the formatter of the major ticks along y
+    ///
+    /// See [Plot::set_formatter_x()].
+    pub fn set_formatter_y(&mut self, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        self.tick_formatter("y", formatter)
+    }
+
+    /// Sets the formatter of the major ticks along z (3D only)
+    ///
+    /// See [Plot::set_formatter_x()].
+    pub fn set_formatter_z(&mut self, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        self.tick_formatter("z", formatter)
+    }
+
+    /// Sets the major ticks along x at the given positions with labels computed by a function
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&[0.0, 3600.0, 7200.0], &[1.0, 3.0, 2.0]);
+    ///     let mut plot = Plot::new();
+    ///     plot.add(&curve)
+    ///         .set_ticks_x_fn(&[0.0, 3600.0, 7200.0], |t| format!("{}h", t / 3600.0));
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_set_ticks_x_fn.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_ticks_x_fn<F>(&mut self, positions: &[f64], f: F) -> &mut Self
+    where
+        F: Fn(f64) -> String,
+    {
+        self.tick_labels_fn("x", positions, f)
+    }
+
+    /// Sets the major ticks along y at the given positions with labels computed by a function
+    ///
+    /// See [Plot::set_ticks_x_fn()].
+    pub fn set_ticks_y_fn<F>(&mut self, positions: &[f64], f: F) -> &mut Self
+    where
+        F: Fn(f64) -> String,
+    {
+        self.tick_labels_fn("y", positions, f)
+    }
+
+    /// Sets the major ticks along z (3D only) at the given positions with labels computed by a function
+    ///
+    /// See [Plot::set_ticks_x_fn()].
+    pub fn set_ticks_z_fn<F>(&mut self, positions: &[f64], f: F) -> &mut Self
+    where
+        F: Fn(f64) -> String,
+    {
+        self.tick_labels_fn("z", positions, f)
+    }
+
+    /// Writes the commands to set a tick locator (`which` is "major" or "minor")
+    fn tick_locator(&mut self, axis: &str, which: &str, locator: &Locator) -> Result<&mut Self, StrError> {
+        let expression = locator.expression()?;
+        write!(
+            &mut self.buffer,
+            "{}_locator={}\n\
+             plt.gca().{}axis.set_{}_locator({}_locator)\n",
+            which, expression, axis, which, which
+        )
+        .unwrap();
+        Ok(self)
+    }
+
+    /// Writes the commands to set a tick formatter
+    fn tick_formatter(&mut self, axis: &str, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        let commands = formatter.commands("major_formatter")?;
+        write!(
+            &mut self.buffer,
+            "{}plt.gca().{}axis.set_major_formatter(major_formatter)\n",
+            commands, axis
+        )
+        .unwrap();
+        Ok(self)
+    }
+
+    /// Writes the commands to set the ticks at the given positions with labels computed by a function
+    fn tick_labels_fn<F>(&mut self, axis: &str, positions: &[f64], f: F) -> &mut Self
+    where
+        F: Fn(f64) -> String,
+    {
+        let locator = Locator::Fixed(positions.to_vec());
+        let formatter = Formatter::from_fn(positions, f);
+        // the fixed locator and formatter are always valid
+        self.tick_locator(axis, "major", &locator).unwrap();
+        self.tick_formatter(axis, &formatter).unwrap();
+        self
+    }
+
     /// Sets the number and format of x-ticks
     ///
     /// # Input
@@ -684,7 +1437,7 @@ impl Plot {
             write!(&mut self.buffer, "    plt.gca().xaxis.set_minor_locator(minor_locator)\n").unwrap();
         }
         if major_number_format != "" {
-            write!(&mut self.buffer, "major_formatter = tck.FormatStrFormatter(r'{}')\n", major_number_format).unwrap();
+            write!(&mut self.buffer, "major_formatter = tck.FormatStrFormatter({})\n", quote(major_number_format)).unwrap();
             write!(&mut self.buffer, "plt.gca().xaxis.set_major_formatter(major_formatter)\n").unwrap();
         }
         self
@@ -713,7 +1466,7 @@ impl Plot {
             write!(&mut self.buffer, "    plt.gca().yaxis.set_minor_locator(minor_locator)\n").unwrap();
         }
         if major_number_format != "" {
-            write!(&mut self.buffer, "major_formatter = tck.FormatStrFormatter(r'{}')\n", major_number_format).unwrap();
+            write!(&mut self.buffer, "major_formatter = tck.FormatStrFormatter({})\n", quote(major_number_format)).unwrap();
             write!(&mut self.buffer, "plt.gca().yaxis.set_major_formatter(major_formatter)\n").unwrap();
         }
         self
@@ -724,10 +1477,10 @@ impl Plot {
     where
         S: std::fmt::Display,
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
         assert_eq!(ticks.vec_size(), labels.len());
-        vector_to_array(&mut self.buffer, "tx", ticks);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "tx", ticks);
         generate_list_quoted(&mut self.buffer, "lx", labels);
         write!(
             &mut self.buffer,
@@ -742,10 +1495,10 @@ impl Plot {
     where
         S: std::fmt::Display,
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
         assert_eq!(ticks.vec_size(), labels.len());
-        vector_to_array(&mut self.buffer, "ty", ticks);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "ty", ticks);
         generate_list_quoted(&mut self.buffer, "ly", labels);
         write!(
             &mut self.buffer,
@@ -789,12 +1542,14 @@ impl Plot {
     }
 
     /// Writes the function multiple_of_pi_formatter to buffer
+    ///
+    /// The labels are written as fractions of pi with the given denominator (reduced if possible).
     #[inline]
-    fn write_multiple_of_pi_formatter(&mut self) {
+    fn write_multiple_of_pi_formatter(&mut self, den: usize) {
         write!(
             &mut self.buffer,
             "def multiple_of_pi_formatter(x, pos):\n\
-             \x20\x20\x20\x20den = 2\n\
+             \x20\x20\x20\x20den = {}\n\
              \x20\x20\x20\x20num = int(np.rint(den*x/np.pi))\n\
              \x20\x20\x20\x20com = np.gcd(num,den)\n\
              \x20\x20\x20\x20(num,den) = (int(num/com),int(den/com))\n\
@@ -806,7 +1561,8 @@ impl Plot {
              \x20\x20\x20\x20else:\n\
              \x20\x20\x20\x20\x20\x20\x20\x20if num==1: return r'$\\frac{{\\pi}}{{%s}}$'%den\n\
              \x20\x20\x20\x20\x20\x20\x20\x20elif num==-1: return r'$\\frac{{-\\pi}}{{%s}}$'%den\n\
-             \x20\x20\x20\x20\x20\x20\x20\x20else: return r'$\\frac{{%s\\pi}}{{%s}}$'%(num,den)\n"
+             \x20\x20\x20\x20\x20\x20\x20\x20else: return r'$\\frac{{%s\\pi}}{{%s}}$'%(num,den)\n",
+            den
         )
         .unwrap();
     }
@@ -830,7 +1586,7 @@ impl Plot {
             write!(&mut self.buffer, "if n_ticks < minor_locator.MAXTICKS * 0.9:\n").unwrap();
             write!(&mut self.buffer, "    plt.gca().xaxis.set_minor_locator(minor_locator)\n").unwrap();
         }
-        self.write_multiple_of_pi_formatter();
+        self.write_multiple_of_pi_formatter(2);
         write!(&mut self.buffer, "major_formatter = tck.FuncFormatter(multiple_of_pi_formatter)\n").unwrap();
         write!(&mut self.buffer, "plt.gca().xaxis.set_major_formatter(major_formatter)\n").unwrap();
         self
@@ -855,17 +1611,67 @@ impl Plot {
             write!(&mut self.buffer, "if n_ticks < minor_locator.MAXTICKS * 0.9:\n").unwrap();
             write!(&mut self.buffer, "    plt.gca().yaxis.set_minor_locator(minor_locator)\n").unwrap();
         }
-        self.write_multiple_of_pi_formatter();
+        self.write_multiple_of_pi_formatter(2);
         write!(&mut self.buffer, "major_formatter = tck.FuncFormatter(multiple_of_pi_formatter)\n").unwrap();
         write!(&mut self.buffer, "plt.gca().yaxis.set_major_formatter(major_formatter)\n").unwrap();
         self
     }
 
-    /// Sets a log10 x-scale
+    /// Sets the scale of the x-axis
     ///
-    /// # Note
+    /// **Note:** Nothing is written to the python script if the scale parameters are invalid.
     ///
-    /// `set_log_x(true)` must be called before adding curves.
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{linspace, Curve, Error, Plot, Scale};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let x = linspace(-50.0, 50.0, 101);
+    ///     let y: Vec<_> = x.iter().map(|v| v * v * v).collect();
+    ///     let mut curve = Curve::new();
+    ///     curve.draw(&x, &y);
+    ///     let mut plot = Plot::new();
+    ///     plot.set_scale_y(Scale::SymLog {
+    ///         base: 10.0,
+    ///         linthresh: 1.0,
+    ///         linscale: 1.0,
+    ///     })?
+    ///     .add(&curve)
+    ///     .grid_and_labels("x", "y");
+    ///     plot.save("/tmp/plotpy/doc_tests/doc_set_scale.svg")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_scale_x(&mut self, scale: Scale) -> Result<&mut Self, StrError> {
+        let opt = scale.options()?;
+        write!(&mut self.buffer, "plt.gca().set_xscale({})\n", opt).unwrap();
+        Ok(self)
+    }
+
+    /// Sets the scale of the y-axis
+    ///
+    /// See [Plot::set_scale_x()].
+    pub fn set_scale_y(&mut self, scale: Scale) -> Result<&mut Self, StrError> {
+        let opt = scale.options()?;
+        write!(&mut self.buffer, "plt.gca().set_yscale({})\n", opt).unwrap();
+        Ok(self)
+    }
+
+    /// Sets the scale of the z-axis (3D only)
+    ///
+    /// See [Plot::set_scale_x()].
+    pub fn set_scale_z(&mut self, scale: Scale) -> Result<&mut Self, StrError> {
+        let opt = scale.options()?;
+        write!(&mut self.buffer, "plt.gca().set_zscale({})\n", opt).unwrap();
+        Ok(self)
+    }
+
+    /// Sets a log10 x-scale
+    ///
+    /// # Note
+    ///
+    /// `set_log_x(true)` must be called before adding curves. See also [Plot::set_scale_x()].
     pub fn set_log_x(&mut self, log: bool) -> &mut Self {
         if log {
             self.buffer.push_str("plt.gca().set_xscale('log')\n");
@@ -879,7 +1685,7 @@ impl Plot {
     ///
     /// # Note
     ///
-    /// `set_log_y(true)` must be called before adding curves.
+    /// `set_log_y(true)` must be called before adding curves. See also [Plot::set_scale_y()].
     pub fn set_log_y(&mut self, log: bool) -> &mut Self {
         if log {
             self.buffer.push_str("plt.gca().set_yscale('log')\n");
@@ -889,21 +1695,108 @@ impl Plot {
         self
     }
 
+    /// Sets the location of the zero angle on polar axes
+    ///
+    /// # Input
+    ///
+    /// * `location` -- the direction of the zero angle (East by default)
+    /// * `offset` -- additional offset (in degrees) applied in the counterclockwise direction
+    pub fn set_polar_zero_location(&mut self, location: ZeroLocation, offset: f64) -> &mut Self {
+        write!(
+            &mut self.buffer,
+            "plt.gca().set_theta_zero_location('{}',offset={})\n",
+            location.as_str(),
+            offset
+        )
+        .unwrap();
+        self
+    }
+
+    /// Sets the direction of increasing angles on polar axes (counterclockwise by default)
+    pub fn set_polar_clockwise(&mut self, flag: bool) -> &mut Self {
+        let direction = if flag { -1 } else { 1 };
+        write!(&mut self.buffer, "plt.gca().set_theta_direction({})\n", direction).unwrap();
+        self
+    }
+
+    /// Sets the range of angles (in degrees) on polar axes; e.g., to draw a sector
+    pub fn set_polar_theta_range(&mut self, min_deg: f64, max_deg: f64) -> &mut Self {
+        write!(
+            &mut self.buffer,
+            "plt.gca().set_thetamin({})\nplt.gca().set_thetamax({})\n",
+            min_deg, max_deg
+        )
+        .unwrap();
+        self
+    }
+
+    /// Sets the radial limits on polar axes
+    pub fn set_polar_rrange(&mut self, rmin: f64, rmax: f64) -> &mut Self {
+        write!(&mut self.buffer, "plt.gca().set_rlim({},{})\n", rmin, rmax).unwrap();
+        self
+    }
+
+    /// Sets a log10 radial scale on polar axes
+    ///
+    /// **Note:** The radial limits must be positive (see [Plot::set_polar_rrange()]).
+    pub fn set_polar_log_r(&mut self, log: bool) -> &mut Self {
+        if log {
+            self.buffer.push_str("plt.gca().set_rscale('log')\n");
+        } else {
+            self.buffer.push_str("plt.gca().set_rscale('linear')\n");
+        }
+        self
+    }
+
+    /// Sets the angular ticks on polar axes with labels in degrees
+    ///
+    /// * `every` -- step between ticks in degrees (ignored if ≤ 0.0); e.g., 30.0
+    pub fn set_polar_ticks_degrees(&mut self, every: f64) -> &mut Self {
+        if every > 0.0 {
+            write!(
+                &mut self.buffer,
+                "plt.gca().xaxis.set_major_locator(tck.MultipleLocator(np.deg2rad({})))\n\
+                 plt.gca().xaxis.set_major_formatter(tck.FuncFormatter(lambda x,pos:'%g°'%np.round(np.rad2deg(x),6)))\n",
+                every
+            )
+            .unwrap();
+        }
+        self
+    }
+
+    /// Sets the angular ticks on polar axes with labels as multiples of pi
+    ///
+    /// * `denominator` -- the ticks are placed at every `PI / denominator` (ignored if zero); e.g., 4
+    pub fn set_polar_ticks_multiple_of_pi(&mut self, denominator: usize) -> &mut Self {
+        if denominator > 0 {
+            write!(
+                &mut self.buffer,
+                "plt.gca().xaxis.set_major_locator(tck.MultipleLocator(np.pi/{}))\n",
+                denominator
+            )
+            .unwrap();
+            self.write_multiple_of_pi_formatter(denominator);
+            self.buffer
+                .push_str("plt.gca().xaxis.set_major_formatter(tck.FuncFormatter(multiple_of_pi_formatter))\n");
+        }
+        self
+    }
+
     /// Sets the label for the x-axis
     pub fn set_label_x(&mut self, label: &str) -> &mut Self {
-        write!(&mut self.buffer, "plt.gca().set_xlabel(r'{}')\n", label).unwrap();
+        write!(&mut self.buffer, "plt.gca().set_xlabel({})\n", quote(label)).unwrap();
         self
     }
 
     /// Sets the label for the y-axis
     pub fn set_label_y(&mut self, label: &str) -> &mut Self {
-        write!(&mut self.buffer, "plt.gca().set_ylabel(r'{}')\n", label).unwrap();
+        write!(&mut self.buffer, "plt.gca().set_ylabel({})\n", quote(label)).unwrap();
         self
     }
 
     /// Sets the label for the z-axis
     pub fn set_label_z(&mut self, label: &str) -> &mut Self {
-        write!(&mut self.buffer, "plt.gca().set_zlabel(r'{}')\n", label).unwrap();
+        write!(&mut self.buffer, "plt.gca().set_zlabel({})\n", quote(label)).unwrap();
         self
     }
 
@@ -929,9 +1822,9 @@ impl Plot {
     pub fn set_label_x_color(&mut self, color: &str) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().xaxis.label.set_color('{}')\n\
-             plt.gca().tick_params(axis='x',labelcolor='{}')\n",
-            color, color
+            "plt.gca().xaxis.label.set_color({0})\n\
+             plt.gca().tick_params(axis='x',labelcolor={0})\n",
+            quote(color)
         )
         .unwrap();
         self
@@ -941,9 +1834,9 @@ impl Plot {
     pub fn set_label_y_color(&mut self, color: &str) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().yaxis.label.set_color('{}')\n\
-             plt.gca().tick_params(axis='y',labelcolor='{}')\n",
-            color, color
+            "plt.gca().yaxis.label.set_color({0})\n\
+             plt.gca().tick_params(axis='y',labelcolor={0})\n",
+            quote(color)
         )
         .unwrap();
         self
@@ -953,9 +1846,9 @@ impl Plot {
     pub fn set_label_z_color(&mut self, color: &str) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().zaxis.label.set_color('{}')\n\
-             plt.gca().tick_params(axis='z',labelcolor='{}')\n",
-            color, color
+            "plt.gca().zaxis.label.set_color({0})\n\
+             plt.gca().tick_params(axis='z',labelcolor={0})\n",
+            quote(color)
         )
         .unwrap();
         self
@@ -968,8 +1861,8 @@ impl Plot {
         write!(
             &mut self.buffer,
             "if 'ax_twinx' in locals():\n\
-             \x20\x20\x20\x20ax_twinx.set_ylabel(r'{}')\n",
-            label,
+             \x20\x20\x20\x20ax_twinx.set_ylabel({})\n",
+            quote(label),
         )
         .unwrap();
         self
@@ -982,9 +1875,9 @@ impl Plot {
         write!(
             &mut self.buffer,
             "if 'ax_twinx' in locals():\n\
-             \x20\x20\x20\x20ax_twinx.yaxis.label.set_color('{}')\n\
-             \x20\x20\x20\x20ax_twinx.tick_params(axis='y',labelcolor='{}')\n",
-            color, color
+             \x20\x20\x20\x20ax_twinx.yaxis.label.set_color({0})\n\
+             \x20\x20\x20\x20ax_twinx.tick_params(axis='y',labelcolor={0})\n",
+            quote(color)
         )
         .unwrap();
         self
@@ -994,8 +1887,9 @@ impl Plot {
     pub fn set_label_x_and_pad(&mut self, label: &str, pad: f64) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().set_xlabel(r'{}',labelpad={})\n",
-            label, pad
+            "plt.gca().set_xlabel({},labelpad={})\n",
+            quote(label),
+            pad
         )
         .unwrap();
         self
@@ -1005,8 +1899,9 @@ impl Plot {
     pub fn set_label_y_and_pad(&mut self, label: &str, pad: f64) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().set_ylabel(r'{}',labelpad={})\n",
-            label, pad
+            "plt.gca().set_ylabel({},labelpad={})\n",
+            quote(label),
+            pad
         )
         .unwrap();
         self
@@ -1016,8 +1911,9 @@ impl Plot {
     pub fn set_label_z_and_pad(&mut self, label: &str, pad: f64) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().set_zlabel(r'{}',labelpad={})\n",
-            label, pad
+            "plt.gca().set_zlabel({},labelpad={})\n",
+            quote(label),
+            pad
         )
         .unwrap();
         self
@@ -1027,8 +1923,9 @@ impl Plot {
     pub fn set_labels(&mut self, xlabel: &str, ylabel: &str) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().set_xlabel(r'{}')\nplt.gca().set_ylabel(r'{}')\n",
-            xlabel, ylabel
+            "plt.gca().set_xlabel({})\nplt.gca().set_ylabel({})\n",
+            quote(xlabel),
+            quote(ylabel)
         )
         .unwrap();
         self
@@ -1038,8 +1935,10 @@ impl Plot {
     pub fn set_labels_3d(&mut self, xlabel: &str, ylabel: &str, zlabel: &str) -> &mut Self {
         write!(
             &mut self.buffer,
-            "plt.gca().set_xlabel(r'{}')\nplt.gca().set_ylabel(r'{}')\nplt.gca().set_zlabel(r'{}')\n",
-            xlabel, ylabel, zlabel
+            "plt.gca().set_xlabel({})\nplt.gca().set_ylabel({})\nplt.gca().set_zlabel({})\n",
+            quote(xlabel),
+            quote(ylabel),
+            quote(zlabel)
         )
         .unwrap();
         self
@@ -1116,21 +2015,36 @@ impl Plot {
 
     /// Draws an infinite horizontal line at y
     pub fn set_horiz_line(&mut self, y: f64, color: &str, line_style: &str, line_width: f64) -> &mut Self {
-        let opt = format!(",color='{}',linestyle='{}',linewidth={}", color, line_style, line_width);
+        let opt = format!(
+            ",color={},linestyle={},linewidth={}",
+            quote(color),
+            quote(line_style),
+            line_width
+        );
         self.buffer.push_str(&format!("plt.axhline({}{})\n", y, &opt));
         self
     }
 
     /// Draws an infinite vertical line at x
     pub fn set_vert_line(&mut self, x: f64, color: &str, line_style: &str, line_width: f64) -> &mut Self {
-        let opt = format!(",color='{}',linestyle='{}',linewidth={}", color, line_style, line_width);
+        let opt = format!(
+            ",color={},linestyle={},linewidth={}",
+            quote(color),
+            quote(line_style),
+            line_width
+        );
         self.buffer.push_str(&format!("plt.axvline({}{})\n", x, &opt));
         self
     }
 
     /// Draws infinite horizontal and vertical lines at (x, y)
     pub fn set_cross(&mut self, x: f64, y: f64, color: &str, line_style: &str, line_width: f64) -> &mut Self {
-        let opt = format!(",color='{}',linestyle='{}',linewidth={}", color, line_style, line_width);
+        let opt = format!(
+            ",color={},linestyle={},linewidth={}",
+            quote(color),
+            quote(line_style),
+            line_width
+        );
         self.buffer
             .push_str(&format!("plt.axhline({}{})\nplt.axvline({}{})\n", y, &opt, x, &opt));
         self
@@ -1150,20 +2064,381 @@ impl Plot {
         self
     }
 
-    /// Run python
-    fn run<S>(&self, figure_path: &S, show: bool) -> Result<(), StrError>
+    /// Sets a wall-clock time limit for Python
+    ///
+    /// Python is killed and [Error::Timeout] is returned if the limit is reached. This is useful,
+    /// for instance, to prevent [Plot::show()] from blocking a headless machine forever.
+    ///
+    /// **Note:** The time limit is not supported by [Plot::save_with_session()].
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.process.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets an environment variable for Python
+    ///
+    /// The other environment variables are inherited from the current process.
+    ///
+    /// # Input
+    ///
+    /// * `key` -- the name of the variable; e.g., `MPLBACKEND` or `MPLCONFIGDIR`
+    /// * `value` -- the value of the variable
+    ///
+    /// **Note:** The environment variables are not supported by [Plot::save_with_session()].
+    pub fn set_env(&mut self, key: &str, value: &str) -> &mut Self {
+        self.process.env.retain(|(k, _)| k != key);
+        self.process.env.push((key.to_string(), value.to_string()));
+        self.deterministic_env.retain(|k| k != key); // now set by the user
+        self
+    }
+
+    /// Sets the working directory of Python
+    ///
+    /// The paths given to [Plot::save()] and similar functions remain relative to the current
+    /// directory of the Rust program; i.e., they are converted to absolute paths.
+    ///
+    /// **Note:** The working directory is not supported by [Plot::save_with_session()].
+    pub fn set_working_dir<S>(&mut self, dir: &S) -> &mut Self
     where
         S: AsRef<OsStr> + ?Sized,
     {
-        // update commands
-        let fig_path = Path::new(figure_path);
+        self.process.working_dir = Some(Path::new(dir).to_path_buf());
+        self
+    }
+
+    /// Sets the style (default appearance) of the figure
+    ///
+    /// The style is applied at the top of the python script; i.e., before any plotting command.
+    /// Thus, the style may be set at any time. See [Style] for an example.
+    pub fn set_style(&mut self, style: &Style) -> &mut Self {
+        self.style = style.clone();
+        self
+    }
+
+    /// Returns the commands applying the style (see [Plot::set_style()])
+    pub(crate) fn get_style_commands(&self) -> String {
+        self.style.get_commands()
+    }
+
+    /// Returns the plotting commands followed by the commands to be called right before saving the figure
+    ///
+    /// **Note:** The data arrays are masked if the [NonFinite::Mask] policy is set.
+    pub(crate) fn get_commands_before_save(&self) -> String {
+        format!("{}{}", self.get_commands(), self.style.get_presave_commands())
+    }
+
+    /// Makes identical inputs generate byte-identical figures (e.g., to commit figures to git)
+    ///
+    /// When enabled:
+    ///
+    /// 1. The seed of the SVG identifiers (`svg.hashsalt`) is fixed
+    /// 2. The creation date is removed from SVG and PDF files (PNG files do not have dates)
+    /// 3. The environment variables `SOURCE_DATE_EPOCH` and `PYTHONHASHSEED` are set to zero (unless
+    ///    already set by [Plot::set_env()]); thus, the dates of other formats (e.g., EPS) are fixed and
+    ///    the Python hashes do not vary
+    ///
+    /// When disabled, only the environment variables set by this function are removed.
+    ///
+    /// **Note:** The environment variables are not set by [Plot::save_with_session()]; however, the
+    /// other settings still apply.
+    pub fn set_deterministic(&mut self, flag: bool) -> &mut Self {
+        self.deterministic = flag;
+        if flag {
+            for key in ["SOURCE_DATE_EPOCH", "PYTHONHASHSEED"] {
+                if !self.process.env.iter().any(|(k, _)| k == key) {
+                    self.process.env.push((key.to_string(), "0".to_string()));
+                    self.deterministic_env.push(key.to_string());
+                }
+            }
+        } else {
+            let added = std::mem::take(&mut self.deterministic_env);
+            self.process.env.retain(|(k, _)| !added.contains(k));
+        }
+        self
+    }
+
+    /// Returns true if the figures are deterministic (see [Plot::set_deterministic()])
+    pub(crate) fn get_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Sets the Python environment to check the features used by this plot
+    ///
+    /// If the environment is given, [Plot::save()] and similar functions return [Error::Unsupported]
+    /// before running Python if the plot uses a feature that the environment does not support;
+    /// e.g., [Plot::set_equal_axes()] in 3D requires Matplotlib 3.3 or newer.
+    ///
+    /// **Note:** The features are recorded while the commands are generated; e.g., the 3D axes are
+    /// known from [Plot::set_subplot_3d()] and the LaTeX requirement from [Style::set_usetex()].
+    /// The features not recorded by the Plot are still reported by Python when the script runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Error, Plot, PythonEnv};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let env = PythonEnv::probe("python3")?;
+    ///     let mut plot = Plot::new();
+    ///     plot.set_python_env(env);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_python_env(&mut self, env: PythonEnv) -> &mut Self {
+        self.python_env = Some(env);
+        self
+    }
+
+    /// Probes the Python environment and sets it to check the features used by this plot
+    ///
+    /// Python is run with the executable, environment variables, and working directory of this
+    /// plot (see [Plot::set_python_exe()], [Plot::set_env()], and [Plot::set_working_dir()]).
+    /// See [Plot::set_python_env()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Error, Plot};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut plot = Plot::new();
+    ///     plot.set_env("MPLBACKEND", "Agg").probe_python_env()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn probe_python_env(&mut self) -> Result<&mut Self, Error> {
+        let env = PythonEnv::probe_with(&self.python_exe, &self.process)?;
+        self.python_env = Some(env);
+        Ok(self)
+    }
+
+    /// Returns the features used by this plot (including the style)
+    pub(crate) fn get_requirements(&self) -> Requirements {
+        let mut requirements = self.requirements;
+        requirements.usetex |= self.style.get_usetex();
+        requirements
+    }
+
+    /// Checks the data (see [Plot::set_non_finite()]) and the features used by this plot (see [Plot::set_python_env()])
+    pub(crate) fn check_commands(&self) -> Result<(), Error> {
+        self.non_finite.check(&self.arrays)?;
+        if let (NonFinite::Reject, Some(function)) = (self.non_finite, &self.non_finite_limits) {
+            return Err(Error::NonFiniteLimits {
+                function: function.clone(),
+            });
+        }
+        self.check_requirements(&self.get_requirements())
+    }
+
+    /// Records the function given non-finite axes limits, if any (see [Plot::set_non_finite()])
+    fn record_limits(&mut self, function: &str, limits: &[f64]) {
+        if self.non_finite_limits.is_none() && limits.iter().any(|v| !v.is_finite()) {
+            self.non_finite_limits = Some(function.to_string());
+        }
+    }
+
+    /// Checks the given features against the Python environment, if any (see [Plot::set_python_env()])
+    pub(crate) fn check_requirements(&self, requirements: &Requirements) -> Result<(), Error> {
+        match &self.python_env {
+            Some(env) => env.check(requirements),
+            None => Ok(()),
+        }
+    }
+
+    /// Calls Python through a [PythonSession] and saves the python script and figure
+    ///
+    /// The session keeps the Python interpreter alive; thus, this function is much faster
+    /// than [Plot::save()] when many figures are generated.
+    ///
+    /// # Input
+    ///
+    /// * `session` -- the long-lived Python interpreter
+    /// * `figure_path` -- may be a String, &str, or Path
+    ///
+    /// # Notes
+    ///
+    /// 1. The python script is also written to the output directory (for double checking)
+    /// 2. If an error occurs, a log file is written as in [Plot::save()]
+    /// 3. The session is shared by many plots; thus, the options of the python3 process cannot be
+    ///    changed. Hence, [Error::Unsupported] is returned if [Plot::set_timeout()], [Plot::set_env()],
+    ///    or [Plot::set_working_dir()] has been called. The environment variables set by
+    ///    [Plot::set_deterministic()] are ignored, though.
+    pub fn save_with_session<S>(&self, session: &mut PythonSession, figure_path: &S) -> Result<(), Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let user_env = self
+            .process
+            .env
+            .iter()
+            .any(|(k, _)| !self.deterministic_env.contains(k));
+        let options = [
+            ("set_timeout", self.process.timeout.is_some()),
+            ("set_env", user_env),
+            ("set_working_dir", self.process.working_dir.is_some()),
+        ];
+        if let Some((option, _)) = options.iter().find(|(_, set)| *set) {
+            return Err(Error::Unsupported {
+                feature: format!("{} with save_with_session", option),
+                requirement: "a separate python3 process (see Plot::save)".to_string(),
+            });
+        }
+        self.check_commands()?;
+        let commands = self.generate_commands(figure_path, false, Some(Path::new(figure_path)))?;
+        let mut path = Path::new(figure_path).to_path_buf();
+        path.set_extension("py");
+        write_python_file(&commands, &path)?;
+        let result = session.execute(&commands)?;
+        self.handle_output(Some(Path::new(figure_path)), &result)
+    }
+
+    /// Returns the python script that would be executed by [Plot::save()] (without running Python)
+    ///
+    /// # Input
+    ///
+    /// * `figure_path` -- may be a String, &str, or Path
+    ///
+    /// **Note:** The data arrays are always written into the script, even if [Plot::set_binary_data()]
+    /// is enabled, because no binary data file is written by this function. Use [Plot::write_script()]
+    /// to obtain the binary data file as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Plot};
+    ///
+    /// let mut curve = Curve::new();
+    /// curve.draw(&[0.0, 1.0], &[1.0, 0.0]);
+    /// let mut plot = Plot::new();
+    /// plot.add(&curve).set_save_tight(false);
+    /// let script = plot.to_python_string("/tmp/plotpy/doc_tests/doc_to_python_string.svg");
+    /// assert!(script.contains("plt.savefig(fn)"));
+    /// ```
+    pub fn to_python_string<S>(&self, figure_path: &S) -> String
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        self.generate_commands(figure_path, false, None).unwrap() // cannot fail without writing data
+    }
+
+    /// Writes the python script that would be executed by [Plot::save()] (without running Python)
+    ///
+    /// # Input
+    ///
+    /// * `figure_path` -- may be a String, &str, or Path
+    ///
+    /// # Notes
+    ///
+    /// 1. The script is written to the same path as the figure, but with the `.py` extension
+    /// 2. The binary data file (`.npy` extension) is also written if [Plot::set_binary_data()] is enabled
+    /// 3. The figure is not generated; running the script with Python generates it
+    pub fn write_script<S>(&self, figure_path: &S) -> Result<(), Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let commands = self.generate_commands(figure_path, false, Some(Path::new(figure_path)))?;
+        let mut path = Path::new(figure_path).to_path_buf();
+        path.set_extension("py");
+        write_python_file(&commands, &path)
+    }
+
+    /// Generates the complete Python script
+    ///
+    /// **Note:** This function also writes the binary data file if [Plot::set_binary_data()] is enabled
+    /// and `data_path` is not None (the `.npy` extension is used).
+    fn generate_commands<S>(&self, figure_path: &S, show: bool, data_path: Option<&Path>) -> Result<String, Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        self.generate_commands_multiple(&[Path::new(figure_path)], show, data_path)
+    }
+
+    /// Generates the complete Python script calling savefig once for each figure path
+    ///
+    /// **Note:** This function also writes the binary data file if [Plot::set_binary_data()] is enabled
+    /// and `data_path` is not None (the `.npy` extension is used).
+    fn generate_commands_multiple(
+        &self,
+        figure_paths: &[&Path],
+        show: bool,
+        data_path: Option<&Path>,
+    ) -> Result<String, Error> {
         let header = if show {
             "#### >>>> file generated by plotpy <<<< ####\n\n".to_string() + PYTHON_HEADER
         } else {
             "#### >>>> file generated by plotpy <<<< ####\n\nimport matplotlib\nmatplotlib.use('Agg')\n".to_string()
                 + PYTHON_HEADER
         };
-        let mut txt = "plt.savefig(fn".to_string();
+        let mut txt = String::new();
+        for figure_path in figure_paths {
+            let fig_path = self.resolve_path(figure_path);
+            let format = fig_path.extension().unwrap_or_default().to_string_lossy();
+            write!(
+                &mut txt,
+                "\nfn={}\nplt.savefig(fn{})\n",
+                quote(&fig_path.to_string_lossy()),
+                self.savefig_options(&format)
+            )
+            .unwrap();
+        }
+        if show {
+            txt.push_str("\nplt.show()\n");
+        };
+        let buffer = self.data_commands(data_path)?;
+        Ok(format!(
+            "{}{}{}{}{}",
+            header,
+            self.preamble_commands(),
+            buffer,
+            self.style.get_presave_commands(),
+            txt
+        ))
+    }
+
+    /// Returns the buffer with the data arrays written to a binary file if [Plot::set_binary_data()] is enabled
+    ///
+    /// The binary file has the same name as `path`, but with the `.npy` extension.
+    /// If `path` is None, the data arrays are always kept in the buffer.
+    fn data_commands(&self, path: Option<&Path>) -> Result<String, Error> {
+        let masked = self.non_finite.apply(&self.buffer, &self.arrays);
+        let (buffer, arrays) = match &masked {
+            Some((buffer, arrays)) => (buffer.as_str(), arrays),
+            None => (self.buffer.as_str(), &self.arrays),
+        };
+        match path {
+            Some(p) if self.binary_data => {
+                let mut data = Vec::new();
+                let commands = arrays.replace_by_binary(buffer, "BINARY_DATA", &mut data);
+                let data_path = p.with_extension("npy");
+                write_npy_file(&data, &data_path)?;
+                Ok(format!(
+                    "BINARY_DATA=np.load({})\n{}",
+                    quote(&self.resolve_path(&data_path).to_string_lossy()),
+                    commands
+                ))
+            }
+            _ => Ok(buffer.to_string()),
+        }
+    }
+
+    /// Returns the options for savefig such as `,bbox_inches='tight'`
+    ///
+    /// The `format` (e.g., "png") selects the DPI set by [Plot::set_save_dpi()].
+    pub(crate) fn savefig_options(&self, format: &str) -> String {
+        let mut txt = self.savefig_page_options(format);
+        if self.deterministic {
+            match format.to_lowercase().as_str() {
+                "svg" => txt.push_str(",metadata={'Date':None}"),
+                "pdf" => txt.push_str(",metadata={'CreationDate':None}"),
+                _ => (),
+            }
+        }
+        txt
+    }
+
+    /// Returns the options for savefig without the metadata (e.g., for a page of a [crate::PdfDocument])
+    pub(crate) fn savefig_page_options(&self, format: &str) -> String {
+        let mut txt = String::new();
         if self.save_tight {
             txt.push_str(",bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS");
         }
@@ -1175,38 +2450,119 @@ impl Plot {
                 txt.push_str(",transparent=True");
             }
         }
-        txt.push_str(")\n");
-        if show {
-            txt.push_str("\nplt.show()\n");
-        };
-        let commands = format!(
-            "{}{}\nfn=r'{}'\n{}",
-            header,
-            self.buffer,
-            fig_path.to_string_lossy(),
-            txt
-        );
+        if let Some(dpi) = self.save_dpi.get(&format.to_lowercase()) {
+            txt.push_str(format!(",dpi={}", dpi).as_str());
+        }
+        txt
+    }
 
-        // call python
-        let mut path = Path::new(figure_path).to_path_buf();
-        path.set_extension("py");
-        let output = call_python3(&self.python_exe, &commands, &path)?;
+    /// Returns the commands to be written right after the header
+    fn preamble_commands(&self) -> String {
+        let mut txt = String::new();
+        if self.deterministic {
+            txt.push_str("plt.rcParams['svg.hashsalt']='plotpy'\n");
+        }
+        txt.push_str(&self.style.get_commands());
+        txt
+    }
 
-        // handle error => write log file
-        if output != "" {
-            let mut log_path = Path::new(figure_path).to_path_buf();
-            log_path.set_extension("log");
-            let mut log_file = File::create(log_path).map_err(|_| "cannot create log file")?;
-            log_file
-                .write_all(output.as_bytes())
-                .map_err(|_| "cannot write to log file")?;
+    /// Runs the python commands with the executable and process options of this plot, capturing stdout
+    pub(crate) fn call_python_capture(
+        &self,
+        commands: &str,
+        path: Option<&Path>,
+    ) -> Result<(PythonOutput, Vec<u8>), Error> {
+        call_python3_capture(&self.python_exe, commands, path, &self.process)
+    }
+
+    /// Returns the absolute path considering the working directory set by [Plot::set_working_dir()]
+    pub(crate) fn resolve_path(&self, path: &Path) -> PathBuf {
+        absolute_path(path, &self.process)
+    }
+
+    /// Handles the output of Python => writes the log file if the output is not empty
+    ///
+    /// The success is based on the exit status; the output of a successful run
+    /// (e.g., warnings) is recorded as diagnostics, unless the strict mode is enabled.
+    ///
+    /// The log file has the same name as `path`, but with the `.log` extension (None means no log file).
+    fn handle_output(&self, path: Option<&Path>, result: &PythonOutput) -> Result<(), Error> {
+        let mut diagnostics = self.diagnostics.lock().unwrap();
+        diagnostics.clear();
+        if result.output != "" || !result.success {
+            if let Some(p) = path {
+                let log_path = p.with_extension("log");
+                let mut log_file = File::create(&log_path).map_err(Error::io(&log_path))?;
+                log_file
+                    .write_all(result.output.as_bytes())
+                    .map_err(Error::io(&log_path))?;
+            }
             if self.show_errors {
-                println!("{}", output);
+                println!("{}", result.output);
+            }
+            if !result.success {
+                return Err(Error::from_python_output(false, result.code, &result.output));
+            }
+            if self.strict {
+                return Err(Error::Output {
+                    output: result.output.clone(),
+                });
+            }
+            for line in result.output.lines() {
+                match diagnostics.last_mut() {
+                    Some(last) if line.starts_with(char::is_whitespace) => {
+                        last.push('\n');
+                        last.push_str(line);
+                    }
+                    _ => {
+                        if !line.trim().is_empty() {
+                            diagnostics.push(line.to_string())
+                        }
+                    }
+                }
             }
-            return Err("python3 failed; please see the log file");
         }
         Ok(())
     }
+
+    /// Run python
+    fn run<S>(&self, figure_path: &S, show: bool) -> Result<(), Error>
+    where
+        S: AsRef<OsStr> + ?Sized,
+    {
+        let fig_path = Path::new(figure_path);
+        self.run_with_stem(&[fig_path], fig_path, show)
+    }
+
+    /// Run python with the auxiliary files (`.py`, `.log`, and `.npy`) named after `stem` instead of the figure
+    pub(crate) fn run_with_stem(&self, figure_paths: &[&Path], stem: &Path, show: bool) -> Result<(), Error> {
+        // check and update commands
+        self.check_commands()?;
+        let commands = self.generate_commands_multiple(figure_paths, show, Some(stem))?;
+        self.run_commands(&commands, stem)
+    }
+
+    /// Writes the python script named after `stem`, calls Python, and handles its output
+    ///
+    /// This function is also used by other drivers (e.g., [crate::Animation]) to share the logging and error handling.
+    pub(crate) fn run_commands(&self, commands: &String, stem: &Path) -> Result<(), Error> {
+        // call python
+        let path = stem.with_extension("py");
+        let result = call_python3(&self.python_exe, commands, &path, &self.process)?;
+
+        // handle error => write log file
+        self.handle_output(Some(stem), &result)
+    }
+
+    /// Returns the plotting commands (without header and savefig)
+    ///
+    /// **Note:** The data arrays are masked if the [NonFinite::Mask] policy is set.
+    pub(crate) fn get_commands(&self) -> String {
+        match self.non_finite.apply(&self.buffer, &self.arrays) {
+            Some((buffer, _)) => buffer,
+            None => self.buffer.clone(),
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -1216,9 +2572,14 @@ mod tests {
     use crate::SuperTitleParams;
 
     use super::Plot;
-    use std::fs::File;
+    use crate::{
+        call_python3_capture, AxisTransform, Barplot, Curve, Error, Formatter, GridLayout, Locator, NonFinite,
+        ProcessOptions, PythonEnv, PythonOutput, PythonSession, Scale, Style, Text, ZeroLocation, PYTHON_HEADER,
+    };
+    use std::fs::{self, File};
     use std::io::{BufRead, BufReader};
     use std::path::Path;
+    use std::time::Duration;
 
     const OUT_DIR: &str = "/tmp/plotpy/unit_tests";
 
@@ -1249,6 +2610,287 @@ mod tests {
         assert_eq!(result, ());
     }
 
+    #[test]
+    fn save_with_session_works() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let plot = Plot::new();
+        for i in 0..2 {
+            let path = Path::new(OUT_DIR).join(format!("save_with_session_works_{}.svg", i));
+            plot.save_with_session(&mut session, &path).unwrap();
+            let file = File::open(&path).map_err(|_| "cannot open file").unwrap();
+            let buffered = BufReader::new(file);
+            let lines_iter = buffered.lines();
+            assert!(lines_iter.count() > 20);
+        }
+    }
+
+    #[test]
+    fn save_with_session_captures_errors() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let path = Path::new(OUT_DIR).join("save_with_session_captures_errors.svg");
+        let mut plot = Plot::new();
+        plot.set_timeout(Duration::from_secs(1));
+        assert_eq!(
+            plot.save_with_session(&mut session, &path).err().unwrap().to_string(),
+            "set_timeout with save_with_session is not supported: it requires a separate python3 process (see Plot::save)"
+        );
+        let mut plot = Plot::new();
+        plot.set_deterministic(true).set_env("MPLBACKEND", "svg");
+        assert_eq!(
+            plot.save_with_session(&mut session, &path).err().unwrap().to_string(),
+            "set_env with save_with_session is not supported: it requires a separate python3 process (see Plot::save)"
+        );
+        let mut plot = Plot::new();
+        plot.set_working_dir("/tmp");
+        assert_eq!(
+            plot.save_with_session(&mut session, &path).err().unwrap().to_string(),
+            "set_working_dir with save_with_session is not supported: it requires a separate python3 process (see Plot::save)"
+        );
+        let mut plot = Plot::new();
+        plot.set_deterministic(true)
+            .set_non_finite(NonFinite::Reject)
+            .set_xmin(f64::NAN);
+        assert_eq!(
+            plot.save_with_session(&mut session, &path).err().unwrap().to_string(),
+            "the axes limits given to set_xmin are not finite (NaN or ±inf)"
+        );
+    }
+
+    #[test]
+    fn to_python_string_works() {
+        let mut plot = Plot::new();
+        plot.set_save_pad_inches(0.5).set_save_transparent(true);
+        let script = plot.to_python_string("/tmp/plotpy/unit_tests/to_python_string_works.svg");
+        assert!(script
+            .starts_with("#### >>>> file generated by plotpy <<<< ####\n\nimport matplotlib\nmatplotlib.use('Agg')\n"));
+        assert!(script.contains(PYTHON_HEADER));
+        assert!(script.ends_with(
+            "\nfn=r'/tmp/plotpy/unit_tests/to_python_string_works.svg'\n\
+             plt.savefig(fn,bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS,pad_inches=0.5,transparent=True)\n"
+        ));
+    }
+
+    #[test]
+    fn write_script_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[1.0, 2.5, 3.0], &[1.0, 2.0, 3.0]);
+        let mut plot = Plot::new();
+        plot.set_binary_data(true).add(&curve);
+        let path = Path::new(OUT_DIR).join("write_script_works.svg");
+        plot.write_script(&path).unwrap();
+        let script = fs::read_to_string(path.with_extension("py")).unwrap();
+        assert!(script.contains("x=BINARY_DATA[0:3]"));
+        assert!(path.with_extension("npy").exists());
+        assert!(!path.exists());
+        let script = plot.to_python_string(&path);
+        assert!(script.contains("x=np.array([1,2.5,3,])"));
+    }
+
+    #[test]
+    fn process_options_work() {
+        let mut plot = Plot::new();
+        plot.set_timeout(Duration::from_millis(500))
+            .set_env("MPLBACKEND", "Agg")
+            .set_env("MPLBACKEND", "svg")
+            .set_working_dir("/tmp");
+        assert_eq!(plot.process.timeout, Some(Duration::from_millis(500)));
+        assert_eq!(plot.process.env, &[("MPLBACKEND".to_string(), "svg".to_string())]);
+        let script = plot.to_python_string("process_options_work.svg");
+        let fig_path = std::env::current_dir().unwrap().join("process_options_work.svg");
+        assert!(script.contains(&format!("fn=r'{}'", fig_path.display())));
+        plot.extra("import time\ntime.sleep(10)\n");
+        let err = plot
+            .save(&Path::new(OUT_DIR).join("process_options_work.svg"))
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "python3 timed out after 0.5 s");
+    }
+
+    #[test]
+    fn set_python_env_works() {
+        let mut env = PythonEnv::probe("python3").unwrap();
+        env.numpy_version = Some("1.26.0".to_string());
+        env.matplotlib_version = Some("3.2.0".to_string());
+        let mut plot = Plot::new();
+        plot.set_python_env(env).set_subplot_3d(1, 1, 1).set_equal_axes(true);
+        let path = Path::new(OUT_DIR).join("set_python_env_works.svg");
+        let err = plot.save(&path).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "set_equal_axes in 3D is not supported: it requires matplotlib >= 3.3"
+        );
+        let err = plot.render("svg").err().unwrap();
+        assert!(matches!(err, Error::Unsupported { .. }));
+        let mut style = Style::new();
+        style.set_usetex(true);
+        let mut plot = Plot::new();
+        let mut env = PythonEnv::probe("python3").unwrap();
+        env.numpy_version = Some("1.26.0".to_string());
+        env.matplotlib_version = Some("3.8.0".to_string());
+        env.has_latex = false;
+        plot.set_python_env(env).set_style(&style);
+        let err = plot.save(&path).err().unwrap();
+        assert_eq!(err.to_string(), "text.usetex is not supported: it requires LaTeX");
+    }
+
+    #[test]
+    fn probe_python_env_works() {
+        let mut plot = Plot::new();
+        plot.set_env("PYTHONPATH", "/tmp/plotpy/unit_tests/__missing__");
+        plot.probe_python_env().unwrap();
+        assert_eq!(plot.python_env.as_ref().unwrap().python_exe, "python3");
+        plot.set_python_exe("__wrong_python_exe__");
+        let err = plot.probe_python_env().err().unwrap();
+        assert!(err.to_string().starts_with("cannot run __wrong_python_exe__"));
+    }
+
+    #[test]
+    fn save_multiple_works() {
+        let mut plot = Plot::new();
+        plot.set_save_tight(false).set_save_dpi("PNG", 300);
+        let paths = [
+            Path::new(OUT_DIR).join("save_multiple_works.pdf"),
+            Path::new(OUT_DIR).join("save_multiple_works.png"),
+        ];
+        let refs: Vec<&Path> = paths.iter().map(|p| p.as_path()).collect();
+        let commands = plot.generate_commands_multiple(&refs, false, None).unwrap();
+        assert!(commands.ends_with(
+            "\nfn=r'/tmp/plotpy/unit_tests/save_multiple_works.pdf'\n\
+             plt.savefig(fn)\n\
+             \nfn=r'/tmp/plotpy/unit_tests/save_multiple_works.png'\n\
+             plt.savefig(fn,dpi=300)\n"
+        ));
+        plot.save_multiple(&paths).unwrap();
+        assert!(paths[0].exists());
+        assert!(paths[1].exists());
+        let empty: [&str; 0] = [];
+        assert_eq!(
+            plot.save_multiple(&empty).err().unwrap().to_string(),
+            "figure_paths must not be empty"
+        );
+    }
+
+    #[test]
+    fn set_style_works() {
+        let mut style = Style::new();
+        style.set_font_size(14.0);
+        let mut plot = Plot::new();
+        plot.set_title("styled").set_style(&style);
+        let script = plot.to_python_string("/tmp/plotpy/unit_tests/set_style_works.svg");
+        assert!(script.contains(&format!(
+            "{}plt.rcParams.update({{\n    r'font.size':14,\n}})\nplt.title(r'styled')\n",
+            PYTHON_HEADER
+        )));
+    }
+
+    #[test]
+    fn set_style_with_usetex_works() {
+        let mut style = Style::new();
+        style.set_usetex(true);
+        let mut plot = Plot::new();
+        plot.set_title("Developer's 100%").set_style(&style);
+        let script = plot.to_python_string("/tmp/plotpy/unit_tests/set_style_with_usetex_works.pdf");
+        assert!(script.contains("def sanitize_usetex():"));
+        assert!(script.contains(
+            "plt.title(r'Developer' \"'\" r's 100%')\nsanitize_usetex()\n\n\
+             fn=r'/tmp/plotpy/unit_tests/set_style_with_usetex_works.pdf'\n"
+        ));
+        assert!(plot.get_commands_before_save().ends_with("sanitize_usetex()\n"));
+    }
+
+    #[test]
+    fn set_deterministic_works() {
+        let mut plot = Plot::new();
+        plot.set_deterministic(true).set_save_tight(false);
+        assert_eq!(plot.savefig_options("svg"), ",metadata={'Date':None}");
+        assert_eq!(plot.savefig_options("PDF"), ",metadata={'CreationDate':None}");
+        assert_eq!(plot.savefig_options("png"), "");
+        assert_eq!(plot.process.env.len(), 2);
+        let script = plot.to_python_string("/tmp/plotpy/unit_tests/set_deterministic_works.svg");
+        assert!(script.contains(&format!("{}plt.rcParams['svg.hashsalt']='plotpy'\n", PYTHON_HEADER)));
+        plot.set_deterministic(false);
+        assert_eq!(plot.savefig_options("svg"), "");
+        assert_eq!(plot.process.env.len(), 0);
+
+        // the variables set by the user are kept
+        plot.set_env("PYTHONHASHSEED", "123").set_deterministic(true);
+        assert_eq!(plot.process.env.len(), 2);
+        plot.set_deterministic(false);
+        assert_eq!(plot.process.env, &[("PYTHONHASHSEED".to_string(), "123".to_string())]);
+        plot.set_deterministic(true).set_env("SOURCE_DATE_EPOCH", "1700000000");
+        plot.set_deterministic(false);
+        assert_eq!(plot.process.env.len(), 2);
+        assert_eq!(plot.savefig_page_options("pdf"), "");
+
+        // identical inputs => identical bytes
+        let mut curve = Curve::new();
+        curve.set_label("curve").draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+        let mut plot = Plot::new();
+        plot.set_deterministic(true).add(&curve).legend();
+        for ext in ["svg", "pdf", "png"] {
+            let path_a = Path::new(OUT_DIR).join(format!("set_deterministic_works_a.{}", ext));
+            let path_b = Path::new(OUT_DIR).join(format!("set_deterministic_works_b.{}", ext));
+            plot.save(&path_a).unwrap();
+            plot.save(&path_b).unwrap();
+            assert_eq!(fs::read(&path_a).unwrap(), fs::read(&path_b).unwrap());
+        }
+    }
+
+    #[test]
+    fn set_non_finite_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[1.0, 2.0, 3.0], &[1.0, f64::INFINITY, f64::NAN]);
+        let mut plot = Plot::new();
+        plot.add(&curve).set_range(0.0, f64::INFINITY, f64::NEG_INFINITY, 1.0);
+        assert_eq!(plot.non_finite, NonFinite::Keep);
+        assert!(plot.buffer.contains("y=np.array([1,inf,NaN,])\n"));
+        assert!(plot.buffer.contains("plt.axis([0,inf,-inf,1])\n"));
+        assert!(PYTHON_HEADER.contains("\ninf = np.inf\n"));
+        let path = Path::new(OUT_DIR).join("set_non_finite_works.svg");
+        plot.set_non_finite(NonFinite::Mask);
+        let script = plot.to_python_string(&path);
+        assert!(script.contains("y=np.array([1,inf,NaN,])\ny=np.ma.masked_invalid(y)\n"));
+        assert!(plot.get_commands_before_save().contains("y=np.ma.masked_invalid(y)\n"));
+        plot.set_non_finite(NonFinite::Reject);
+        assert!(!plot.get_commands().contains("masked_invalid"));
+        assert_eq!(
+            plot.save(&path).err().unwrap().to_string(),
+            "the data array y has non-finite values (NaN or ±inf)"
+        );
+        let mut plot = Plot::new();
+        plot.set_xrange(0.0, 1.0)
+            .set_ymax(f64::NAN)
+            .set_range(0.0, f64::INFINITY, 0.0, 1.0);
+        assert_eq!(plot.non_finite_limits.as_deref(), Some("set_ymax"));
+        plot.set_non_finite(NonFinite::Keep);
+        assert!(plot.check_commands().is_ok());
+        plot.set_non_finite(NonFinite::Reject);
+        assert_eq!(
+            plot.check_commands().err().unwrap().to_string(),
+            "the axes limits given to set_ymax are not finite (NaN or ±inf)"
+        );
+    }
+
+    #[test]
+    fn render_works() {
+        let plot = Plot::new();
+        let svg = plot.render("svg").unwrap();
+        assert!(String::from_utf8(svg).unwrap().contains("<svg"));
+        let png = plot.render("png").unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn render_keep_files_works() {
+        let mut plot = Plot::new();
+        let path = Path::new(OUT_DIR).join("render_keep_files_works.py");
+        plot.set_render_keep_files(&path).extra("print('hello')\n");
+        let pdf = plot.render("pdf").unwrap();
+        assert_eq!(&pdf[0..4], b"%PDF");
+        assert!(fs::read_to_string(&path).unwrap().contains("format='pdf'"));
+        assert_eq!(plot.get_diagnostics(), &["hello"]);
+    }
+
     #[test]
     fn save_str_works() {
         let plot = Plot::new();
@@ -1261,6 +2903,30 @@ mod tests {
         assert!(lines_iter.count() > 20);
     }
 
+    #[test]
+    fn binary_data_works() {
+        let x = vec![1.0, 2.5, 3.0];
+        let mut plot = Plot::new();
+        plot.set_ticks_x_labels(&x, &["a", "b", "c"]);
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 1.0], &[0.5, -0.5]);
+        plot.add(&curve).extra("z=np.array([1,2,])\n");
+        let path = Path::new(OUT_DIR).join("binary_data_works.svg");
+        let commands = plot.generate_commands(&path, false, Some(&path)).unwrap();
+        assert!(commands.contains("tx=np.array([1,2.5,3,])\n"));
+        plot.set_binary_data(true);
+        let commands = plot.generate_commands(&path, false, Some(&path)).unwrap();
+        assert!(commands.contains(
+            "BINARY_DATA=np.load(r'/tmp/plotpy/unit_tests/binary_data_works.npy')\n\
+             tx=BINARY_DATA[0:3]\n\
+             lx=[r'a',r'b',r'c',]\n"
+        ));
+        assert!(commands.contains("x=BINARY_DATA[3:5].astype(int)\ny=BINARY_DATA[5:7]\n"));
+        assert!(commands.contains("z=np.array([1,2,])\n")); // only the arrays of the graphs are binary
+        let data = fs::read(Path::new(OUT_DIR).join("binary_data_works.npy")).unwrap();
+        assert_eq!(data.len(), 128 + 7 * 8);
+    }
+
     #[test]
     fn show_errors_works() {
         const WRONG: usize = 0;
@@ -1268,7 +2934,51 @@ mod tests {
         plot.set_show_errors(true);
         plot.set_subplot(1, 1, WRONG);
         let path = Path::new(OUT_DIR).join("show_errors_works.svg");
-        assert_eq!(plot.save(&path).err(), Some("python3 failed; please see the log file"));
+        match plot.save(&path).err() {
+            Some(Error::Python { kind, .. }) => assert_eq!(kind, "ValueError"),
+            _ => panic!("Python error expected"),
+        }
+    }
+
+    #[test]
+    fn handle_output_works() {
+        let path = Path::new(OUT_DIR).join("handle_output_works.svg");
+        let log_path = Path::new(OUT_DIR).join("handle_output_works.log");
+        let output = "/tmp/a.py:3: UserWarning: careful\n\
+                      \x20\x20warnings.warn('careful')\n\
+                      hello\n";
+        let mut result = PythonOutput {
+            success: true,
+            code: Some(0),
+            output: output.to_string(),
+        };
+        let mut plot = Plot::new();
+        plot.handle_output(Some(&path), &result).unwrap();
+        assert_eq!(
+            plot.get_diagnostics(),
+            &[
+                "/tmp/a.py:3: UserWarning: careful\n\x20\x20warnings.warn('careful')",
+                "hello"
+            ]
+        );
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), output);
+
+        plot.set_strict(true);
+        let err = plot.handle_output(Some(&path), &result).err().unwrap();
+        assert_eq!(err.to_string(), "python3 issued warnings; please see the log file");
+        assert_eq!(plot.get_diagnostics().len(), 0);
+
+        result.success = false;
+        result.code = Some(1);
+        plot.set_strict(false);
+        let err = plot.handle_output(Some(&path), &result).err().unwrap();
+        assert_eq!(err.to_string(), "python3 failed with exit code 1");
+
+        result.success = true;
+        result.code = Some(0);
+        result.output = String::new();
+        plot.handle_output(Some(&path), &result).unwrap();
+        assert_eq!(plot.get_diagnostics().len(), 0);
     }
 
     #[test]
@@ -1348,24 +3058,157 @@ mod tests {
 
         let mut p2 = Plot::new();
         p2.set_title("Developer's Plot").set_super_title("Dev's", None);
-        let b: &str = "plt.title(r'Developer’s Plot')\n\
-                       st=plt.suptitle(r'Dev’s')\n\
+        let b: &str = "plt.title(r'Developer' \"'\" r's Plot')\n\
+                       st=plt.suptitle(r'Dev' \"'\" r's')\n\
                        add_to_ea(st)\n";
         assert_eq!(p2.buffer, b);
 
         let mut p3 = Plot::new();
         p3.set_title("\"Look at This\"").set_super_title("\"Dev's\"", None);
         let b: &str = "plt.title(r'\"Look at This\"')\n\
-                       st=plt.suptitle(r'\"Dev’s\"')\n\
+                       st=plt.suptitle(r'\"Dev' \"'\" r's\"')\n\
                        add_to_ea(st)\n";
         assert_eq!(p3.buffer, b);
     }
 
+    #[test]
+    fn subplots_works() {
+        let mut curve = Curve::new();
+        curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+        let mut plot = Plot::new();
+        let axes = plot.subplots(1, 2);
+        assert_eq!(axes.iter().map(|a| a.index()).collect::<Vec<_>>(), &[0, 1]);
+        plot.add_to(axes[1], &curve).set_axes(axes[0]).set_title("first");
+        let more = plot.subplots(2, 1);
+        assert_eq!(more.iter().map(|a| a.index()).collect::<Vec<_>>(), &[2, 3]);
+        let b: &str = "\nAXES+=list(plt.gcf().subplots(1,2,squeeze=False).flat)\nplt.sca(AXES[0])\n\
+                       \nplt.sca(AXES[1])\n\
+                       x=np.array([0,1,])\ny=np.array([0,1,])\nplt.plot(x,y)\n\
+                       \nplt.sca(AXES[0])\n\
+                       plt.title(r'first')\n\
+                       \nAXES+=list(plt.gcf().subplots(2,1,squeeze=False).flat)\nplt.sca(AXES[2])\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn grid_axes_works() {
+        let mut layout = GridLayout::new(2, 2);
+        layout.set_width_ratios(&[2.0, 1.0]);
+        let mut plot = Plot::new();
+        let top = plot.grid_axes(&layout, 0..1, ..).unwrap();
+        let bottom = plot.grid_axes(&layout, 1..=1, 1..2).unwrap();
+        assert_eq!((top.index(), bottom.index()), (0, 1));
+        assert_eq!(
+            plot.grid_axes(&layout, 0..1, 1..3).err(),
+            Some("the ranges of rows and columns must be within the grid layout")
+        );
+        layout.set_wspace(-1.0);
+        assert_eq!(
+            plot.grid_axes(&layout, 0..1, 0..1).err(),
+            Some("the spaces between rows and columns must not be negative")
+        );
+        let b: &str = "GRID_0=plt.gcf().add_gridspec(2,2,width_ratios=[2,1])\n\
+                       \nAXES.append(plt.gcf().add_subplot(GRID_0[0:1,0:2]))\nplt.sca(AXES[0])\n\
+                       \nAXES.append(plt.gcf().add_subplot(GRID_0[1:2,1:2]))\nplt.sca(AXES[1])\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn subplot_mosaic_works() {
+        let mut plot = Plot::new();
+        let axes = plot.subplots(1, 1);
+        assert_eq!(
+            plot.subplot_mosaic(&GridLayout::new(1, 2)).err(),
+            Some("the grid layout must be created from a mosaic")
+        );
+        let layout = GridLayout::from_mosaic("AAB;C.B").unwrap();
+        let panels = plot.subplot_mosaic(&layout).unwrap();
+        assert_eq!(axes[0].index(), 0);
+        assert_eq!(panels.keys().collect::<String>(), "ABC");
+        assert_eq!(panels[&'A'].index(), 1);
+        assert_eq!(panels[&'B'].index(), 2);
+        assert_eq!(panels[&'C'].index(), 3);
+        assert!(plot.buffer.contains("GRID_0=plt.gcf().add_gridspec(2,3)\n"));
+        assert!(plot
+            .buffer
+            .contains("add_subplot(GRID_0[0:2,2:3]))\nplt.sca(AXES[2])\n"));
+        assert_eq!(plot.buffer.matches("add_gridspec").count(), 1);
+    }
+
+    #[test]
+    fn twin_axes_work() {
+        let mut plot = Plot::new();
+        let main = plot.current_axes();
+        let twin_x = plot.twin_x();
+        plot.set_log_y(true).set_label_y("pressure");
+        plot.set_axes(main);
+        let twin_y = plot.twin_y();
+        assert_eq!(main.index(), 0);
+        assert_eq!(twin_x.index(), 1);
+        assert_eq!(twin_y.index(), 2);
+        let b: &str = "\nAXES.append(plt.gca())\n\
+                       \nAXES.append(plt.gca().twinx())\nplt.sca(AXES[1])\n\
+                       plt.gca().set_yscale('log')\n\
+                       plt.gca().set_ylabel(r'pressure')\n\
+                       \nplt.sca(AXES[0])\n\
+                       \nAXES.append(plt.gca().twiny())\nplt.sca(AXES[2])\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn secondary_axes_work() {
+        let mut plot = Plot::new();
+        assert_eq!(
+            plot.set_secondary_x_axis(AxisTransform::Reciprocal(0.0), "").err(),
+            Some("the constant of the reciprocal transform must be finite and not zero")
+        );
+        assert_eq!(plot.buffer, "");
+        plot.set_secondary_x_axis(AxisTransform::DegreesToRadians, "angle [rad]")
+            .unwrap()
+            .set_secondary_y_axis(AxisTransform::CelsiusToKelvin, "")
+            .unwrap();
+        let b: &str = "sec=plt.gca().secondary_xaxis('top',functions=(np.deg2rad,np.rad2deg))\n\
+                       sec.set_xlabel(r'angle [rad]')\n\
+                       sec=plt.gca().secondary_yaxis('right',functions=(lambda v:v+273.15,lambda v:v-273.15))\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn strings_generate_valid_python() {
+        let mut curve = Curve::new();
+        curve.set_label("it's \\").draw(&[0.0, 1.0], &[0.0, 1.0]);
+        let mut text = Text::new();
+        text.draw(0.5, 0.5, "C:\\temp\\");
+        let mut barplot = Barplot::new();
+        barplot.draw_with_str(&["Ann's", "température"], &[1.0, 2.0]);
+        let mut plot = Plot::new();
+        plot.add(&curve)
+            .add(&text)
+            .add(&barplot)
+            .set_title("first line\nDeveloper's $\\varepsilon$")
+            .set_labels("\"x\"", "ünïcödé ✓");
+        assert!(plot.buffer.contains("label=r'it' \"'\" r's ' '\\\\'"));
+        assert!(plot.buffer.contains("plt.text(0.5,0.5,r'C:\\temp' '\\\\')"));
+        assert!(plot.buffer.contains("x=[r'Ann' \"'\" r's',r'température',]"));
+        assert!(plot
+            .buffer
+            .contains("plt.title(r'first line' '\\n' r'Developer' \"'\" r's $\\varepsilon$')"));
+        let path = Path::new(OUT_DIR).join("strings_generate_valid_python.svg");
+        plot.write_script(&path).unwrap();
+        let check = format!(
+            "compile(open(r'{}',encoding='utf-8').read(),'script','exec')\n",
+            path.with_extension("py").display()
+        );
+        let (output, _) = call_python3_capture("python3", &check, None, &ProcessOptions::default()).unwrap();
+        assert_eq!(output.output, "");
+        assert!(output.success);
+    }
+
     #[test]
     fn set_super_title_handles_math_and_newline() {
         let mut plot = Plot::new();
         plot.set_super_title("$\\alpha$\\n$\\beta$", None);
-        let b: &str = "st=plt.suptitle(r'$\\alpha$'+'\\n'+r'$\\beta$')\n\
+        let b: &str = "st=plt.suptitle(r'$\\alpha$' '\\n' r'$\\beta$')\n\
                        add_to_ea(st)\n";
         assert_eq!(plot.buffer, b);
     }
@@ -1465,6 +3308,144 @@ mod tests {
         assert_eq!(plot.show_errors, true);
     }
 
+    #[test]
+    fn locators_and_formatters_work() {
+        let mut plot = Plot::new();
+        assert_eq!(
+            plot.set_locator_x(&Locator::Multiple {
+                base: -1.0,
+                offset: 0.0
+            })
+            .err(),
+            Some("the base of the Multiple locator must be positive")
+        );
+        assert_eq!(
+            plot.set_formatter_x(&Formatter::Percent {
+                xmax: -1.0,
+                decimals: None
+            })
+            .err(),
+            Some("the xmax of the Percent formatter must be positive")
+        );
+        assert_eq!(plot.buffer, "");
+        plot.set_locator_x(&Locator::MaxN {
+            nbins: 3,
+            integer: true,
+        })
+        .unwrap()
+        .set_minor_locator_y(&Locator::AutoMinor { ndivs: 2 })
+        .unwrap()
+        .set_formatter_z(&Formatter::Scientific {
+            use_offset: true,
+            power_limits: (-1, 1),
+            math_text: false,
+        })
+        .unwrap();
+        let b: &str = "major_locator=tck.MaxNLocator(nbins=3,integer=True)\n\
+                       plt.gca().xaxis.set_major_locator(major_locator)\n\
+                       minor_locator=tck.AutoMinorLocator(2)\n\
+                       plt.gca().yaxis.set_minor_locator(minor_locator)\n\
+                       major_formatter=tck.ScalarFormatter(useOffset=True,useMathText=False)\n\
+                       major_formatter.set_powerlimits((-1,1))\n\
+                       plt.gca().zaxis.set_major_formatter(major_formatter)\n";
+        assert_eq!(plot.buffer, b);
+        plot.buffer.clear();
+        plot.set_ticks_y_fn(&[1.0, 2.0], |v| format!("{}x", v));
+        let b: &str = "major_locator=tck.FixedLocator([1,2,])\n\
+                       plt.gca().yaxis.set_major_locator(major_locator)\n\
+                       labels=[r'1x',r'2x',]\n\
+                       major_formatter=tck.FixedFormatter(labels)\n\
+                       plt.gca().yaxis.set_major_formatter(major_formatter)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn set_scale_works() {
+        let mut plot = Plot::new();
+        assert_eq!(
+            plot.set_scale_x(Scale::Log { base: -1.0 }).err(),
+            Some("the base of the log scale must be positive and not equal to one")
+        );
+        assert_eq!(plot.buffer, "");
+        plot.set_scale_x(Scale::Log { base: 2.0 })
+            .unwrap()
+            .set_scale_y(Scale::SymLog {
+                base: 10.0,
+                linthresh: 0.1,
+                linscale: 0.5,
+            })
+            .unwrap()
+            .set_scale_z(Scale::Logit)
+            .unwrap()
+            .set_scale_y(Scale::Asinh { linear_width: 1.0 })
+            .unwrap()
+            .set_scale_x(Scale::Linear)
+            .unwrap();
+        let b: &str = "plt.gca().set_xscale('log',base=2)\n\
+                       plt.gca().set_yscale('symlog',base=10,linthresh=0.1,linscale=0.5)\n\
+                       plt.gca().set_zscale('logit')\n\
+                       plt.gca().set_yscale('asinh',linear_width=1)\n\
+                       plt.gca().set_xscale('linear')\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn date_functions_work() {
+        let mut plot = Plot::new();
+        plot.set_time_zone("America/New_York")
+            .set_xrange_dates("2024-03-15", "2024-03-16")
+            .set_yrange_dates(0, 86400)
+            .set_date_axis_x()
+            .set_date_axis_y();
+        let b: &str = "TIME_ZONE=time_zone(r'America/New_York')\n\
+                       plt.gca().set_xlim(to_dates([r'2024-03-15',r'2024-03-16']))\n\
+                       plt.gca().set_ylim(to_dates([0,86400]))\n\
+                       set_date_axis(plt.gca().xaxis)\n\
+                       set_date_axis(plt.gca().yaxis)\n";
+        assert_eq!(plot.buffer, b);
+    }
+
+    #[test]
+    fn polar_functions_work() {
+        let mut plot = Plot::new();
+        let polar = plot.set_subplot_polar(1, 2, 1);
+        assert_eq!(polar.index(), 0);
+        plot.set_polar_zero_location(ZeroLocation::N, 10.0)
+            .set_polar_zero_location(ZeroLocation::SW, 0.0)
+            .set_polar_clockwise(true)
+            .set_polar_clockwise(false)
+            .set_polar_theta_range(0.0, 180.0)
+            .set_polar_rrange(0.1, 10.0)
+            .set_polar_log_r(true)
+            .set_polar_log_r(false)
+            .set_polar_ticks_degrees(0.0)
+            .set_polar_ticks_degrees(45.0)
+            .set_polar_ticks_multiple_of_pi(0);
+        let b: &str = "\nAXES.append(plt.gcf().add_subplot(1,2,1,projection='polar'))\n\
+                       plt.sca(AXES[0])\n\
+                       plt.gca().set_theta_zero_location('N',offset=10)\n\
+                       plt.gca().set_theta_zero_location('SW',offset=0)\n\
+                       plt.gca().set_theta_direction(-1)\n\
+                       plt.gca().set_theta_direction(1)\n\
+                       plt.gca().set_thetamin(0)\n\
+                       plt.gca().set_thetamax(180)\n\
+                       plt.gca().set_rlim(0.1,10)\n\
+                       plt.gca().set_rscale('log')\n\
+                       plt.gca().set_rscale('linear')\n\
+                       plt.gca().xaxis.set_major_locator(tck.MultipleLocator(np.deg2rad(45)))\n\
+                       plt.gca().xaxis.set_major_formatter(tck.FuncFormatter(lambda x,pos:'%g°'%np.round(np.rad2deg(x),6)))\n";
+        assert_eq!(plot.buffer, b);
+        plot.buffer.clear();
+        plot.set_polar_ticks_multiple_of_pi(6);
+        assert!(plot
+            .buffer
+            .starts_with("plt.gca().xaxis.set_major_locator(tck.MultipleLocator(np.pi/6))\n"));
+        assert!(plot.buffer.contains("\x20\x20\x20\x20den = 6\n"));
+        assert!(plot
+            .buffer
+            .ends_with("plt.gca().xaxis.set_major_formatter(tck.FuncFormatter(multiple_of_pi_formatter))\n"));
+    }
+
     #[test]
     fn set_functions_work_2() {
         let mut plot = Plot::new();
@@ -1644,16 +3625,35 @@ mod tests {
         assert_eq!(plot.buffer, b);
     }
 
+    #[test]
+    fn set_label_colors_work() {
+        let mut plot = Plot::new();
+        plot.set_label_x_color("red")
+            .set_label_y_color("#00ff00")
+            .set_label_z_color("blue")
+            .set_label_y_twinx_color("it's");
+        let b: &str = "plt.gca().xaxis.label.set_color(r'red')\n\
+                       plt.gca().tick_params(axis='x',labelcolor=r'red')\n\
+                       plt.gca().yaxis.label.set_color(r'#00ff00')\n\
+                       plt.gca().tick_params(axis='y',labelcolor=r'#00ff00')\n\
+                       plt.gca().zaxis.label.set_color(r'blue')\n\
+                       plt.gca().tick_params(axis='z',labelcolor=r'blue')\n\
+                       if 'ax_twinx' in locals():\n\
+                       \x20\x20\x20\x20ax_twinx.yaxis.label.set_color(r'it' \"'\" r's')\n\
+                       \x20\x20\x20\x20ax_twinx.tick_params(axis='y',labelcolor=r'it' \"'\" r's')\n";
+        assert_eq!(plot.buffer, b);
+    }
+
     #[test]
     fn extra_functionality_works() {
         let mut plot = Plot::new();
         plot.set_horiz_line(-1.0, "blue", "-", 1.1)
             .set_vert_line(-2.0, "green", ":", 1.2)
             .set_cross(0.25, 0.75, "red", "--", 3.0);
-        let b: &str = "plt.axhline(-1,color='blue',linestyle='-',linewidth=1.1)\n\
-                       plt.axvline(-2,color='green',linestyle=':',linewidth=1.2)\n\
-                       plt.axhline(0.75,color='red',linestyle='--',linewidth=3)\n\
-                       plt.axvline(0.25,color='red',linestyle='--',linewidth=3)\n";
+        let b: &str = "plt.axhline(-1,color=r'blue',linestyle=r'-',linewidth=1.1)\n\
+                       plt.axvline(-2,color=r'green',linestyle=r':',linewidth=1.2)\n\
+                       plt.axhline(0.75,color=r'red',linestyle=r'--',linewidth=3)\n\
+                       plt.axvline(0.25,color=r'red',linestyle=r'--',linewidth=3)\n";
         assert_eq!(plot.buffer, b);
     }
 
@@ -0,0 +1,297 @@
+use super::{call_python3_capture, Error, ProcessOptions};
+
+/// Script that reports the Python environment (one `key=value` per line)
+const PYTHON_PROBE: &str = r#"
+import sys, shutil
+print('python=' + '.'.join(map(str, sys.version_info[:3])))
+try:
+    import numpy
+    print('numpy=' + numpy.__version__)
+except ImportError:
+    pass
+try:
+    import matplotlib
+    print('matplotlib=' + matplotlib.__version__)
+    try:
+        from matplotlib.backends import backend_registry
+        backends = backend_registry.list_builtin()
+    except ImportError:
+        from matplotlib import rcsetup
+        backends = rcsetup.all_backends
+    print('backends=' + ','.join(backends))
+except ImportError:
+    pass
+try:
+    import PIL
+    print('pillow=' + PIL.__version__)
+except ImportError:
+    pass
+print('latex=' + str(shutil.which('latex') is not None))
+"#;
+
+/// Holds information about the Python environment used to generate the figures
+///
+/// The environment is obtained by [PythonEnv::probe()] and may be given to
+/// [crate::Plot::set_python_env()]. Then, the Plot refuses to run scripts requiring
+/// features that the environment does not support.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{Error, PythonEnv};
+///
+/// fn main() -> Result<(), Error> {
+///     let env = PythonEnv::probe("python3")?;
+///     println!("Python {}", env.python_version);
+///     if let Some(version) = &env.matplotlib_version {
+///         println!("Matplotlib {} (backends: {})", version, env.backends.join(", "));
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PythonEnv {
+    /// The Python executable
+    pub python_exe: String,
+
+    /// The version of Python; e.g., `3.11.4`
+    pub python_version: String,
+
+    /// The version of NumPy (None if not installed)
+    pub numpy_version: Option<String>,
+
+    /// The version of Matplotlib (None if not installed)
+    pub matplotlib_version: Option<String>,
+
+    /// The names of the builtin Matplotlib backends; e.g., `agg`, `svg`, `tkagg`
+    pub backends: Vec<String>,
+
+    /// The `latex` command is available (required by `text.usetex`)
+    pub has_latex: bool,
+
+    /// The Pillow package is installed
+    pub has_pillow: bool,
+}
+
+/// Holds the features used by a script that the Python environment may not support
+///
+/// The flags are recorded while the python commands are generated (e.g., by [crate::Plot::set_subplot_3d()]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Requirements {
+    pub three_d: bool,    // the axes are 3D
+    pub equal_axes: bool, // set_equal_axes is called
+    pub usetex: bool,     // the texts are typeset by LaTeX
+    pub pillow: bool,     // the animation is written by Pillow (GIF)
+}
+
+impl Requirements {
+    /// Adds the requirements of another script
+    pub(crate) fn merge(&mut self, other: &Requirements) {
+        self.three_d |= other.three_d;
+        self.equal_axes |= other.equal_axes;
+        self.usetex |= other.usetex;
+        self.pillow |= other.pillow;
+    }
+}
+
+impl PythonEnv {
+    /// Runs Python once and returns information about its environment
+    ///
+    /// # Input
+    ///
+    /// * `python_exe` -- the Python executable; e.g., `python3` or simply `python` (e.g., on Windows)
+    ///
+    /// **Note:** See also [crate::Plot::probe_python_env()], which runs Python with the environment
+    /// variables and working directory of the Plot.
+    pub fn probe(python_exe: &str) -> Result<Self, Error> {
+        PythonEnv::probe_with(python_exe, &ProcessOptions::default())
+    }
+
+    /// Runs Python once with the given process options and returns information about its environment
+    pub(crate) fn probe_with(python_exe: &str, options: &ProcessOptions) -> Result<Self, Error> {
+        let (result, stdout) = call_python3_capture(python_exe, PYTHON_PROBE, None, options)?;
+        let stdout = String::from_utf8_lossy(&stdout);
+        if !result.success {
+            return Err(Error::from_python_output(
+                false,
+                result.code,
+                &(stdout.to_string() + &result.output),
+            ));
+        }
+        Ok(PythonEnv::parse(python_exe, &stdout))
+    }
+
+    /// Parses the output of the probe script
+    fn parse(python_exe: &str, output: &str) -> Self {
+        let mut env = PythonEnv {
+            python_exe: python_exe.to_string(),
+            python_version: String::new(),
+            numpy_version: None,
+            matplotlib_version: None,
+            backends: Vec::new(),
+            has_latex: false,
+            has_pillow: false,
+        };
+        for line in output.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                match key {
+                    "python" => env.python_version = value.to_string(),
+                    "numpy" => env.numpy_version = Some(value.to_string()),
+                    "matplotlib" => env.matplotlib_version = Some(value.to_string()),
+                    "backends" => env.backends = value.split(',').map(|b| b.to_string()).collect(),
+                    "pillow" => env.has_pillow = true,
+                    "latex" => env.has_latex = value == "True",
+                    _ => (),
+                }
+            }
+        }
+        env
+    }
+
+    /// Returns true if Matplotlib is installed with at least the given version
+    pub fn matplotlib_at_least(&self, major: u32, minor: u32) -> bool {
+        match &self.matplotlib_version {
+            Some(version) => parse_version(version) >= (major, minor),
+            None => false,
+        }
+    }
+
+    /// Checks whether the environment supports the features required by a script
+    ///
+    /// Returns [Error::Unsupported] for the first feature that is not supported.
+    pub(crate) fn check(&self, requirements: &Requirements) -> Result<(), Error> {
+        let unsupported = |feature: &str, requirement: &str| {
+            Err(Error::Unsupported {
+                feature: feature.to_string(),
+                requirement: requirement.to_string(),
+            })
+        };
+        if self.numpy_version.is_none() {
+            return unsupported("plotting", "numpy");
+        }
+        if self.matplotlib_version.is_none() {
+            return unsupported("plotting", "matplotlib");
+        }
+        if requirements.three_d && requirements.equal_axes && !self.matplotlib_at_least(3, 3) {
+            return unsupported("set_equal_axes in 3D", "matplotlib >= 3.3");
+        }
+        if requirements.usetex && !self.has_latex {
+            return unsupported("text.usetex", "LaTeX");
+        }
+        if requirements.pillow && !self.has_pillow {
+            return unsupported("GIF animation", "pillow");
+        }
+        Ok(())
+    }
+}
+
+/// Parses the major and minor numbers of a version string such as `3.10.0rc1`
+fn parse_version(version: &str) -> (u32, u32) {
+    let mut numbers = version.split('.').map(|part| {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().unwrap_or(0)
+    });
+    let major = numbers.next().unwrap_or(0);
+    let minor = numbers.next().unwrap_or(0);
+    (major, minor)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_version, PythonEnv, Requirements};
+    use crate::ProcessOptions;
+
+    #[test]
+    fn parse_version_works() {
+        assert_eq!(parse_version("3.10.0rc1"), (3, 10));
+        assert_eq!(parse_version("3.3"), (3, 3));
+        assert_eq!(parse_version("4"), (4, 0));
+        assert_eq!(parse_version(""), (0, 0));
+    }
+
+    #[test]
+    fn parse_works() {
+        let env = PythonEnv::parse(
+            "python3",
+            "python=3.11.4\nnumpy=1.26.0\nmatplotlib=3.2.2\nbackends=agg,svg,pdf\nlatex=False\npillow=10.0.0\n",
+        );
+        assert_eq!(env.python_version, "3.11.4");
+        assert_eq!(env.numpy_version, Some("1.26.0".to_string()));
+        assert_eq!(env.matplotlib_version, Some("3.2.2".to_string()));
+        assert_eq!(env.backends, &["agg", "svg", "pdf"]);
+        assert_eq!(env.has_latex, false);
+        assert_eq!(env.has_pillow, true);
+        assert_eq!(env.matplotlib_at_least(3, 2), true);
+        assert_eq!(env.matplotlib_at_least(3, 3), false);
+    }
+
+    #[test]
+    fn check_works() {
+        let mut env = PythonEnv::parse("python3", "python=3.11.4\nnumpy=1.26.0\nmatplotlib=3.2.2\n");
+        let mut requirements = Requirements {
+            equal_axes: true,
+            ..Default::default()
+        };
+        assert!(env.check(&requirements).is_ok());
+        requirements.three_d = true;
+        let err = env.check(&requirements).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "set_equal_axes in 3D is not supported: it requires matplotlib >= 3.3"
+        );
+        let requirements = Requirements {
+            usetex: true,
+            ..Default::default()
+        };
+        let err = env.check(&requirements).err().unwrap();
+        assert_eq!(err.to_string(), "text.usetex is not supported: it requires LaTeX");
+        env.has_latex = true;
+        assert!(env.check(&requirements).is_ok());
+        let mut requirements = Requirements {
+            pillow: true,
+            ..Default::default()
+        };
+        let err = env.check(&requirements).err().unwrap();
+        assert_eq!(err.to_string(), "GIF animation is not supported: it requires pillow");
+        requirements.merge(&Requirements {
+            usetex: true,
+            ..Default::default()
+        });
+        assert_eq!(requirements.pillow && requirements.usetex, true);
+        env.matplotlib_version = None;
+        let err = env.check(&Requirements::default()).err().unwrap();
+        assert_eq!(err.to_string(), "plotting is not supported: it requires matplotlib");
+    }
+
+    #[test]
+    fn probe_works() {
+        let env = PythonEnv::probe("python3").unwrap();
+        assert_eq!(env.python_exe, "python3");
+        assert!(env.python_version.starts_with('3'));
+        let err = PythonEnv::probe("__wrong_python_exe__").err().unwrap();
+        assert!(err.to_string().starts_with("cannot run __wrong_python_exe__"));
+    }
+
+    #[test]
+    fn probe_with_uses_process_options() {
+        let options = ProcessOptions {
+            env: vec![(
+                "PYTHONPATH".to_string(),
+                "/tmp/plotpy/unit_tests/probe_with".to_string(),
+            )],
+            working_dir: Some("/tmp".into()),
+            ..Default::default()
+        };
+        std::fs::create_dir_all("/tmp/plotpy/unit_tests/probe_with").unwrap();
+        std::fs::write(
+            "/tmp/plotpy/unit_tests/probe_with/shutil.py",
+            "raise ImportError('probe_with')\n",
+        )
+        .unwrap();
+        let err = PythonEnv::probe_with("python3", &options).err().unwrap();
+        assert!(err.to_string().contains("probe_with"));
+        assert!(PythonEnv::probe_with("python3", &ProcessOptions::default()).is_ok());
+    }
+}
@@ -0,0 +1,237 @@
+use super::{Error, PythonOutput};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// Driver executed by the long-lived Python interpreter
+///
+/// The protocol is very simple: each request consists of a line with the number of bytes
+/// of the script followed by the script itself. Each response consists of a line with the
+/// number of bytes of the output and the status (0 = success, 1 = failure) followed by
+/// the output (stdout and stderr combined).
+///
+/// The protocol runs on private duplicates of the original stdin and stdout. Afterwards, the
+/// file descriptor 0 is pointed to `os.devnull` and the file descriptor 1 to stderr; thus, the
+/// scripts cannot corrupt the protocol by reading from stdin or writing to `sys.__stdout__`.
+///
+/// Matplotlib is imported (if available) once; afterwards, each script runs in a fresh
+/// namespace within `rc_context()` and all figures are closed at the end. Thus, the state
+/// of Matplotlib is reset between figures.
+const PYTHON_DRIVER: &str = r#"
+import sys, os, io, traceback, contextlib
+_inp = os.fdopen(os.dup(0), 'rb')
+_out = os.fdopen(os.dup(1), 'wb')
+_null = os.open(os.devnull, os.O_RDONLY)
+os.dup2(_null, 0)
+os.close(_null)
+os.dup2(2, 1)
+try:
+    import matplotlib
+    matplotlib.use('Agg')
+    import matplotlib.pyplot
+    import numpy
+except ImportError:
+    matplotlib = None
+while True:
+    line = _inp.readline()
+    if not line:
+        break
+    size = int(line.decode('ascii'))
+    source = _inp.read(size).decode('utf-8')
+    capture = io.StringIO()
+    status = 0
+    with contextlib.redirect_stdout(capture), contextlib.redirect_stderr(capture):
+        try:
+            namespace = {'__name__': '__main__'}
+            if matplotlib is None:
+                exec(compile(source, '<plotpy>', 'exec'), namespace)
+            else:
+                with matplotlib.rc_context():
+                    exec(compile(source, '<plotpy>', 'exec'), namespace)
+        except SystemExit as e:
+            if e.code not in (None, 0):
+                status = 1
+        except BaseException:
+            status = 1
+            traceback.print_exc()
+        finally:
+            if matplotlib is not None:
+                matplotlib.pyplot.close('all')
+    result = capture.getvalue().encode('utf-8')
+    _out.write(b'%d %d\n' % (len(result), status))
+    _out.write(result)
+    _out.flush()
+"#;
+
+/// Holds a long-lived Python interpreter to render many figures
+///
+/// Each call to [crate::Plot::save_with_session()] sends the generated script to the same
+/// Python process. Hence, the costs of starting the interpreter and importing NumPy and
+/// Matplotlib are paid only once. The Matplotlib state (rcParams and figures) is reset
+/// between figures.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{linspace, Curve, Error, Plot, PythonSession};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut session = PythonSession::new("python3")?;
+///     let x = linspace(0.0, 1.0, 11);
+///     for i in 0..3 {
+///         let y: Vec<_> = x.iter().map(|v| f64::powi(*v, i + 1)).collect();
+///         let mut curve = Curve::new();
+///         curve.draw(&x, &y);
+///         let mut plot = Plot::new();
+///         plot.add(&curve);
+///         let path = format!("/tmp/plotpy/doc_tests/doc_python_session_{}.svg", i);
+///         plot.save_with_session(&mut session, &path)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct PythonSession {
+    child: Child,                   // the Python process
+    stdin: Option<ChildStdin>,      // requests
+    stdout: BufReader<ChildStdout>, // responses
+}
+
+impl PythonSession {
+    /// Starts a new Python interpreter
+    ///
+    /// # Input
+    ///
+    /// * `python_exe` -- the Python executable; e.g., `python3` or simply `python` (e.g., on Windows)
+    pub fn new(python_exe: &str) -> Result<Self, Error> {
+        let mut child = Command::new(python_exe)
+            .arg("-u")
+            .arg("-c")
+            .arg(PYTHON_DRIVER)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|source| Error::Spawn {
+                python_exe: python_exe.to_string(),
+                source,
+            })?;
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap()); // cannot fail because of Stdio::piped
+        Ok(PythonSession { child, stdin, stdout })
+    }
+
+    /// Executes a script and returns the status and the combined stdout and stderr
+    pub(crate) fn execute(&mut self, python_commands: &str) -> Result<PythonOutput, Error> {
+        match self.request(python_commands) {
+            Some(output) => Ok(output),
+            None => {
+                // the process has died; thus, we collect its exit status
+                drop(self.stdin.take());
+                let code = self.child.wait().ok().and_then(|status| status.code());
+                Err(Error::Exit {
+                    code,
+                    output: "python3 session terminated".to_string(),
+                })
+            }
+        }
+    }
+
+    /// Sends the script and reads the response (returns None if the communication fails)
+    fn request(&mut self, python_commands: &str) -> Option<PythonOutput> {
+        // send request
+        let stdin = self.stdin.as_mut()?;
+        writeln!(stdin, "{}", python_commands.len()).ok()?;
+        stdin.write_all(python_commands.as_bytes()).ok()?;
+        stdin.flush().ok()?;
+
+        // read response
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let (size, status) = line.trim().split_once(' ')?;
+        let size: usize = size.parse().ok()?;
+        let mut output = vec![0; size];
+        self.stdout.read_exact(&mut output).ok()?;
+        Some(PythonOutput {
+            success: status == "0",
+            code: Some(if status == "0" { 0 } else { 1 }),
+            output: String::from_utf8_lossy(&output).to_string(),
+        })
+    }
+}
+
+impl Drop for PythonSession {
+    /// Closes the stdin of the Python process (which makes it exit) and waits for it
+    fn drop(&mut self) {
+        drop(self.stdin.take());
+        let _ = self.child.wait();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PythonSession;
+
+    #[test]
+    fn execute_works() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let result = session.execute("print(\"Python says: Hello World!\")").unwrap();
+        assert_eq!(result.success, true);
+        assert_eq!(result.output, "Python says: Hello World!\n");
+        let result = session.execute("x = 1").unwrap();
+        assert_eq!(result.success, true);
+        assert_eq!(result.output, "");
+    }
+
+    #[test]
+    fn execute_uses_fresh_namespace() {
+        let mut session = PythonSession::new("python3").unwrap();
+        session.execute("x = 123").unwrap();
+        let result = session.execute("print('x' in globals())").unwrap();
+        assert_eq!(result.output, "False\n");
+    }
+
+    #[test]
+    fn execute_captures_errors() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let result = session.execute("raise ValueError('wrong')").unwrap();
+        assert_eq!(result.success, false);
+        assert!(result.output.contains("ValueError: wrong"));
+        let result = session.execute("import sys\nsys.exit(2)").unwrap();
+        assert_eq!(result.success, false);
+        let result = session.execute("import warnings\nwarnings.warn('careful')").unwrap();
+        assert_eq!(result.success, true);
+        assert!(result.output.contains("UserWarning: careful"));
+        let result = session.execute("print('still alive')").unwrap();
+        assert_eq!(result.output, "still alive\n");
+    }
+
+    #[test]
+    fn execute_protects_protocol() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let result = session
+            .execute("import os, sys\nos.write(1, b'123 0\\n')\nsys.__stdout__.write('4 0\\n')\nsys.__stdout__.flush()")
+            .unwrap();
+        assert_eq!(result.success, true);
+        assert_eq!(result.output, "");
+        let result = session.execute("import sys\nprint(repr(sys.stdin.read()))").unwrap();
+        assert_eq!(result.output, "''\n");
+        let result = session.execute("print('still alive')").unwrap();
+        assert_eq!(result.output, "still alive\n");
+    }
+
+    #[test]
+    fn new_captures_errors() {
+        let err = PythonSession::new("__wrong_python_exe__").err().unwrap();
+        assert!(err.to_string().starts_with("cannot run __wrong_python_exe__"));
+    }
+
+    #[test]
+    fn execute_captures_termination() {
+        let mut session = PythonSession::new("python3").unwrap();
+        let err = session.execute("import os\nos._exit(7)").err().unwrap();
+        assert_eq!(err.to_string(), "python3 failed with exit code 7");
+    }
+}
@@ -0,0 +1,181 @@
+use super::{AxisTransform, StrError};
+
+/// Defines the scale of an axis
+///
+/// See [crate::Plot::set_scale_x()], [crate::Plot::set_scale_y()], and [crate::Plot::set_scale_z()].
+///
+/// [See Matplotlib's documentation](https://matplotlib.org/stable/gallery/scales/scales.html)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    /// Linear scale (default)
+    Linear,
+
+    /// Logarithmic scale with the given base (e.g., 10.0); only positive values are shown
+    Log {
+        /// The base of the logarithm (positive and not equal to one)
+        base: f64,
+    },
+
+    /// Symmetrical logarithmic scale (logarithmic in both directions from the origin)
+    ///
+    /// The scale is linear around zero; thus, the data may cross zero.
+    SymLog {
+        /// The base of the logarithm (greater than one); e.g., 10.0
+        base: f64,
+        /// The range `(-linthresh, linthresh)` around zero is linear (positive); e.g., 2.0
+        linthresh: f64,
+        /// The number of decades to use for the linear range (positive); e.g., 1.0
+        linscale: f64,
+    },
+
+    /// Logit scale for probabilities in the open interval (0, 1)
+    Logit,
+
+    /// Inverse hyperbolic sine scale (similar to SymLog, but smooth)
+    Asinh {
+        /// The range of values around zero where the scale is nearly linear (positive); e.g., 1.0
+        linear_width: f64,
+    },
+
+    /// Scale given by the forward and inverse functions of a transform
+    Function(AxisTransform),
+}
+
+impl Scale {
+    /// Returns the arguments of `set_xscale`, `set_yscale`, or `set_zscale`
+    pub(crate) fn options(&self) -> Result<String, StrError> {
+        let res = match self {
+            Scale::Linear => "'linear'".to_string(),
+            Scale::Log { base } => {
+                if !(base.is_finite() && *base > 0.0) || *base == 1.0 {
+                    return Err("the base of the log scale must be positive and not equal to one");
+                }
+                format!("'log',base={}", base)
+            }
+            Scale::SymLog {
+                base,
+                linthresh,
+                linscale,
+            } => {
+                if !(base.is_finite() && *base > 1.0) {
+                    return Err("the base of the symlog scale must be greater than one");
+                }
+                if !(linthresh.is_finite() && *linthresh > 0.0) {
+                    return Err("the linthresh of the symlog scale must be positive");
+                }
+                if !(linscale.is_finite() && *linscale > 0.0) {
+                    return Err("the linscale of the symlog scale must be positive");
+                }
+                format!("'symlog',base={},linthresh={},linscale={}", base, linthresh, linscale)
+            }
+            Scale::Logit => "'logit'".to_string(),
+            Scale::Asinh { linear_width } => {
+                if !(linear_width.is_finite() && *linear_width > 0.0) {
+                    return Err("the linear_width of the asinh scale must be positive");
+                }
+                format!("'asinh',linear_width={}", linear_width)
+            }
+            Scale::Function(transform) => {
+                let (forward, inverse) = transform.functions()?;
+                format!("'function',functions=({},{})", forward, inverse)
+            }
+        };
+        Ok(res)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Scale;
+    use crate::AxisTransform;
+
+    #[test]
+    fn derive_works() {
+        let scale = Scale::Log { base: 2.0 };
+        let cloned = scale.clone();
+        assert_eq!(scale, cloned);
+        assert_eq!(format!("{:?}", scale), "Log { base: 2.0 }");
+    }
+
+    #[test]
+    fn options_work() {
+        assert_eq!(Scale::Linear.options().unwrap(), "'linear'");
+        assert_eq!(Scale::Log { base: 10.0 }.options().unwrap(), "'log',base=10");
+        assert_eq!(
+            Scale::SymLog {
+                base: 10.0,
+                linthresh: 0.5,
+                linscale: 1.0
+            }
+            .options()
+            .unwrap(),
+            "'symlog',base=10,linthresh=0.5,linscale=1"
+        );
+        assert_eq!(Scale::Logit.options().unwrap(), "'logit'");
+        assert_eq!(
+            Scale::Asinh { linear_width: 2.0 }.options().unwrap(),
+            "'asinh',linear_width=2"
+        );
+        assert_eq!(
+            Scale::Function(AxisTransform::Linear {
+                scale: 2.0,
+                offset: 0.0
+            })
+            .options()
+            .unwrap(),
+            "'function',functions=(lambda v:2*v+(0),lambda v:(v-(0))/2)"
+        );
+    }
+
+    #[test]
+    fn options_capture_errors() {
+        assert_eq!(
+            Scale::Log { base: 1.0 }.options().err(),
+            Some("the base of the log scale must be positive and not equal to one")
+        );
+        assert_eq!(
+            Scale::Log { base: f64::NAN }.options().err(),
+            Some("the base of the log scale must be positive and not equal to one")
+        );
+        assert_eq!(
+            Scale::SymLog {
+                base: 1.0,
+                linthresh: 1.0,
+                linscale: 1.0
+            }
+            .options()
+            .err(),
+            Some("the base of the symlog scale must be greater than one")
+        );
+        assert_eq!(
+            Scale::SymLog {
+                base: 10.0,
+                linthresh: 0.0,
+                linscale: 1.0
+            }
+            .options()
+            .err(),
+            Some("the linthresh of the symlog scale must be positive")
+        );
+        assert_eq!(
+            Scale::SymLog {
+                base: 10.0,
+                linthresh: 1.0,
+                linscale: -1.0
+            }
+            .options()
+            .err(),
+            Some("the linscale of the symlog scale must be positive")
+        );
+        assert_eq!(
+            Scale::Asinh { linear_width: 0.0 }.options().err(),
+            Some("the linear_width of the asinh scale must be positive")
+        );
+        assert_eq!(
+            Scale::Function(AxisTransform::Reciprocal(0.0)).options().err(),
+            Some("the constant of the reciprocal transform must be finite and not zero")
+        );
+    }
+}
@@ -1,19 +1,20 @@
-use super::GraphMaker;
+use super::{quote, GraphMaker};
 use std::fmt::Write;
 
 /// Creates an icon to indicate the slope of lines
 ///
 /// # Notes
 ///
-/// When using log scales, `plot.set_log_x(true)` or `plot.set_log_y(true)`
-/// must be called before adding the icon.
+/// When using non-linear scales, `plot.set_log_x(true)`, `plot.set_log_y(true)`, or
+/// [crate::Plot::set_scale_x()] and [crate::Plot::set_scale_y()] must be called before adding the icon.
+/// The icon is then drawn in the transformed coordinates of the scales (e.g., decades for log scales).
 ///
 /// # Example
 ///
 /// ```
-/// use plotpy::{linspace, Curve, Plot, SlopeIcon, StrError};
+/// use plotpy::{linspace, Curve, Error, Plot, SlopeIcon};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // models
 ///     let slope = 2.0;
 ///     let (xi, xf, yi) = (0.0, 10.0, 0.0);
@@ -125,8 +126,8 @@ impl SlopeIcon {
         write!(
             &mut self.buffer,
             "slope,cx,cy=float({}),float({}),float({})\n\
-             if plt.gca().get_xscale() == 'log': cx=np.log10(cx)\n\
-             if plt.gca().get_yscale() == 'log': cy=np.log10(cy)\n\
+             if plt.gca().get_xscale() != 'linear': cx=plt.gca().xaxis.get_transform().transform(cx)\n\
+             if plt.gca().get_yscale() != 'linear': cy=plt.gca().yaxis.get_transform().transform(cy)\n\
              xc,yc=data_to_axis((cx,cy))\n\
              xa,ya=data_to_axis((cx+1.0,cy+slope))\n\
              m,l=(ya-yc)/(xa-xc),{}\n",
@@ -198,44 +199,50 @@ impl SlopeIcon {
             if slope < 0.0 {
                 write!(
                     &mut self.buffer,
-                    "plt.text(xc,yp,r'{}',ha='center',va='top'{})\n",
-                    self.text_h, opt_x
+                    "plt.text(xc,yp,{},ha='center',va='top'{})\n",
+                    quote(&self.text_h),
+                    opt_x
                 )
                 .unwrap();
             } else {
                 write!(
                     &mut self.buffer,
-                    "plt.text(xc,yp,r'{}',ha='center',va='bottom'{})\n",
-                    self.text_h, opt_x
+                    "plt.text(xc,yp,{},ha='center',va='bottom'{})\n",
+                    quote(&self.text_h),
+                    opt_x
                 )
                 .unwrap();
             }
             write!(
                 &mut self.buffer,
-                "plt.text(xm,yc,r'{}',ha='right',va='center'{})\n",
-                text, opt_y
+                "plt.text(xm,yc,{},ha='right',va='center'{})\n",
+                quote(&text),
+                opt_y
             )
             .unwrap();
         } else {
             if slope < 0.0 {
                 write!(
                     &mut self.buffer,
-                    "plt.text(xc,ym,r'{}',ha='center',va='bottom'{})\n",
-                    self.text_h, opt_x
+                    "plt.text(xc,ym,{},ha='center',va='bottom'{})\n",
+                    quote(&self.text_h),
+                    opt_x
                 )
                 .unwrap();
             } else {
                 write!(
                     &mut self.buffer,
-                    "plt.text(xc,ym,r'{}',ha='center',va='top'{})\n",
-                    self.text_h, opt_x
+                    "plt.text(xc,ym,{},ha='center',va='top'{})\n",
+                    quote(&self.text_h),
+                    opt_x
                 )
                 .unwrap();
             }
             write!(
                 &mut self.buffer,
-                "plt.text(xp,yc,r'{}',ha='left',va='center'{})\n",
-                text, opt_y
+                "plt.text(xp,yc,{},ha='left',va='center'{})\n",
+                quote(&text),
+                opt_y
             )
             .unwrap();
         }
@@ -637,8 +644,8 @@ mod tests {
             .set_text_offset_v(7.0)
             .draw(10.0, 0.5, 0.1);
         let b: &str = "slope,cx,cy=float(10),float(0.5),float(0.1)\n\
-                       if plt.gca().get_xscale() == 'log': cx=np.log10(cx)\n\
-                       if plt.gca().get_yscale() == 'log': cy=np.log10(cy)\n\
+                       if plt.gca().get_xscale() != 'linear': cx=plt.gca().xaxis.get_transform().transform(cx)\n\
+                       if plt.gca().get_yscale() != 'linear': cy=plt.gca().yaxis.get_transform().transform(cy)\n\
                        xc,yc=data_to_axis((cx,cy))\n\
                        xa,ya=data_to_axis((cx+1.0,cy+slope))\n\
                        m,l=(ya-yc)/(xa-xc),0.1\n\
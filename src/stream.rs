@@ -1,7 +1,7 @@
 use super::GraphMaker;
-use crate::conversions::{matrix_to_array, vector_to_array};
+use crate::conversions::{matrix_to_array, vector_to_array, DataArrays};
 use crate::{AsMatrix, AsVector};
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Implements functions to illustrate vector fields using streamlines and quiver plots
@@ -24,6 +24,7 @@ pub struct Stream {
 
     // buffer
     buffer: String,
+    arrays: DataArrays,
 }
 
 impl Stream {
@@ -46,6 +47,7 @@ impl Stream {
             // extra options
             // buffer
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -61,12 +63,12 @@ impl Stream {
     pub fn draw<'a, T, U>(&mut self, xx: &'a T, yy: &'a T, uu: &'a T, vv: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "xx", xx);
-        matrix_to_array(&mut self.buffer, "yy", yy);
-        matrix_to_array(&mut self.buffer, "uu", uu);
-        matrix_to_array(&mut self.buffer, "vv", vv);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "xx", xx);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "yy", yy);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "uu", uu);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "vv", vv);
         let opt = self.options_streamplot();
         write!(&mut self.buffer, "plt.streamplot(xx,yy,uu,vv{})\n", &opt).unwrap();
     }
@@ -82,12 +84,12 @@ impl Stream {
     where
         V: AsVector<'a, U>,
         M: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        matrix_to_array(&mut self.buffer, "uu", uu);
-        matrix_to_array(&mut self.buffer, "vv", vv);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "uu", uu);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "vv", vv);
         let opt = self.options_streamplot();
         write!(&mut self.buffer, "plt.streamplot(x,y,uu,vv{})\n", &opt).unwrap();
     }
@@ -101,12 +103,12 @@ impl Stream {
     pub fn draw_arrows<'a, T, U>(&mut self, xx: &'a T, yy: &'a T, uu: &'a T, vv: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "xx", xx);
-        matrix_to_array(&mut self.buffer, "yy", yy);
-        matrix_to_array(&mut self.buffer, "uu", uu);
-        matrix_to_array(&mut self.buffer, "vv", vv);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "xx", xx);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "yy", yy);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "uu", uu);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "vv", vv);
         let opt = self.options_quiver();
         write!(&mut self.buffer, "plt.quiver(xx,yy,uu,vv{})\n", &opt).unwrap();
     }
@@ -120,12 +122,12 @@ impl Stream {
     pub fn draw_arrows_alt<'a, T, U>(&mut self, x: &'a T, y: &'a T, u: &'a T, v: &'a T)
     where
         T: AsVector<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        vector_to_array(&mut self.buffer, "x", x);
-        vector_to_array(&mut self.buffer, "y", y);
-        vector_to_array(&mut self.buffer, "u", u);
-        vector_to_array(&mut self.buffer, "v", v);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "u", u);
+        vector_to_array(&mut self.buffer, &mut self.arrays, "v", v);
         let opt = self.options_quiver();
         write!(&mut self.buffer, "plt.quiver(x,y,u,v{})\n", &opt).unwrap();
     }
@@ -266,8 +268,12 @@ impl GraphMaker for Stream {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
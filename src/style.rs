@@ -0,0 +1,492 @@
+use super::quote;
+use std::fmt::Write;
+
+/// Python function that makes the texts of all figures safe for LaTeX (called before savefig)
+///
+/// The characters `%`, `&`, `#`, and `_` are escaped outside math mode (`$...$`), unless already
+/// escaped.
+const SANITIZE_USETEX: &str = r#"
+# Makes the texts of all figures safe for LaTeX (text.usetex)
+def sanitize_usetex():
+    import re
+    import matplotlib.text as mtext
+    def fix(txt):
+        parts = re.split(r'((?<!\\)\$.*?(?<!\\)\$)', txt)
+        for i in range(0, len(parts), 2):
+            parts[i] = re.sub(r'(?<!\\)([%&#_])', r'\\\1', parts[i])
+        return ''.join(parts)
+    for num in plt.get_fignums():
+        for t in plt.figure(num).findobj(mtext.Text):
+            if t.get_text(): t.set_text(fix(t.get_text()))
+"#;
+
+/// Holds the default appearance of figures (Matplotlib's rcParams)
+///
+/// A Style is given to [crate::Plot::set_style()] and is applied at the top of the python script;
+/// i.e., before any figure is created. The presets (e.g., [Style::mocha()]) may be modified by
+/// the setters; e.g., to change the font size of a dark style.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::{Curve, Error, Plot, Style};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut style = Style::nordic();
+///     style
+///         .set_font_size(12.0)
+///         .set_line_width(2.0)
+///         .set_tick_direction("in")
+///         .set_color_cycle(&["#88C0D0", "#BF616A", "#A3BE8C"]);
+///     let mut curve = Curve::new();
+///     curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+///     let mut plot = Plot::new();
+///     plot.set_style(&style).add(&curve).grid_and_labels("x", "y");
+///     plot.save("/tmp/plotpy/doc_tests/doc_style.svg")
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    base: Option<String>,          // name of a Matplotlib style applied first; e.g., dark_background
+    params: Vec<(String, String)>, // rcParams keys and python values (in insertion order)
+    usetex: bool,                  // texts are typeset by LaTeX
+}
+
+impl Style {
+    /// Allocates a new instance (without any modification to the defaults)
+    pub fn new() -> Self {
+        Style {
+            base: None,
+            params: Vec::new(),
+            usetex: false,
+        }
+    }
+
+    /// Returns the Matplotlib native dark style (dark_background)
+    pub fn dark_background() -> Self {
+        let mut style = Style::new();
+        style.set_base("dark_background");
+        style
+    }
+
+    /// Returns the Mathematica-like dark style
+    pub fn mathematica() -> Self {
+        let mut style = Style::new();
+        style
+            .set_figure_face_color("#000000")
+            .set_axes_face_color("#000000")
+            .set_text_color("#FFFFFF")
+            .set_label_color("#FFFFFF")
+            .set_tick_color("#FFFFFF")
+            .set_edge_color("#555555")
+            .set_color_cycle(&[
+                "#5E81B5", "#E19C24", "#8FB032", "#EB6238", "#9467BD", "#8C564B", "#E377C2",
+            ])
+            .set_grid_color("#313244")
+            .set_legend_face_color("#181825")
+            .set_legend_edge_color("#313244")
+            .set_legend_label_color("#cdd6f4");
+        style
+    }
+
+    /// Returns the Catppuccin Mocha dark style
+    pub fn mocha() -> Self {
+        let mut style = Style::new();
+        style
+            .set_figure_face_color("#11111b")
+            .set_axes_face_color("#1e1e2e")
+            .set_savefig_face_color("#11111b")
+            .set_text_color("#cdd6f4")
+            .set_label_color("#cdd6f4")
+            .set_tick_color("#7f849c")
+            .set_edge_color("#45475a")
+            .set_color_cycle(&[
+                "#89b4fa", "#fab387", "#a6e3a1", "#f38ba8", "#cba6f7", "#94e2d5", "#f9e2af",
+            ])
+            .set_grid_color("#313244")
+            .set_legend_face_color("#181825")
+            .set_legend_edge_color("#313244")
+            .set_legend_label_color("#cdd6f4");
+        style
+    }
+
+    /// Returns the Nordic Night dark style
+    pub fn nordic() -> Self {
+        let mut style = Style::new();
+        style
+            .set_figure_face_color("#2E3440")
+            .set_axes_face_color("#2E3440")
+            .set_savefig_face_color("#2E3440")
+            .set_text_color("#D8DEE9")
+            .set_label_color("#D8DEE9")
+            .set_tick_color("#4C566A")
+            .set_edge_color("#4C566A")
+            .set_color_cycle(&[
+                "#88C0D0", "#81A1C1", "#BF616A", "#D08770", "#EBCB8B", "#A3BE8C", "#B48EAD",
+            ])
+            .set_grid_color("#3B4252")
+            .set_legend_face_color("#181825")
+            .set_legend_edge_color("#313244")
+            .set_legend_label_color("#D8DEE9");
+        style
+    }
+
+    /// Sets a Matplotlib style applied before the other parameters; e.g., `ggplot` or `dark_background`
+    pub fn set_base(&mut self, name: &str) -> &mut Self {
+        self.base = Some(name.to_string());
+        self
+    }
+
+    /// Sets the background color of figures
+    pub fn set_figure_face_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("figure.facecolor", color)
+    }
+
+    /// Sets the background color of axes (the plotting area)
+    pub fn set_axes_face_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("axes.facecolor", color)
+    }
+
+    /// Sets the background color of saved figures
+    pub fn set_savefig_face_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("savefig.facecolor", color)
+    }
+
+    /// Sets the color of the axes frame (spines)
+    pub fn set_edge_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("axes.edgecolor", color)
+    }
+
+    /// Sets the color of texts (e.g., titles)
+    pub fn set_text_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("text.color", color)
+    }
+
+    /// Sets the color of the axes labels
+    pub fn set_label_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("axes.labelcolor", color)
+    }
+
+    /// Sets the font family; e.g., `serif`, `sans-serif`, `monospace`, or the name of a font
+    pub fn set_font_family(&mut self, family: &str) -> &mut Self {
+        self.set_str("font.family", family)
+    }
+
+    /// Sets the default font size
+    pub fn set_font_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("font.size", size)
+    }
+
+    /// Sets the font size of the axes titles
+    pub fn set_title_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("axes.titlesize", size)
+    }
+
+    /// Sets the font size of the axes labels
+    pub fn set_label_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("axes.labelsize", size)
+    }
+
+    /// Sets the font size of the tick labels (x and y)
+    pub fn set_tick_label_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("xtick.labelsize", size);
+        self.set_number("ytick.labelsize", size)
+    }
+
+    /// Sets the font size of legends
+    pub fn set_legend_font_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("legend.fontsize", size)
+    }
+
+    /// Sets the default line width
+    pub fn set_line_width(&mut self, width: f64) -> &mut Self {
+        self.set_number("lines.linewidth", width)
+    }
+
+    /// Sets the default line style; e.g., `-`, `--`, `-.`, `:`
+    pub fn set_line_style(&mut self, style: &str) -> &mut Self {
+        self.set_str("lines.linestyle", style)
+    }
+
+    /// Sets the default marker style; e.g., `o`, `+`, `None`
+    pub fn set_marker_style(&mut self, style: &str) -> &mut Self {
+        self.set_str("lines.marker", style)
+    }
+
+    /// Sets the default marker size
+    pub fn set_marker_size(&mut self, size: f64) -> &mut Self {
+        self.set_number("lines.markersize", size)
+    }
+
+    /// Sets the color of the ticks and tick labels (x and y)
+    pub fn set_tick_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("xtick.color", color);
+        self.set_str("ytick.color", color)
+    }
+
+    /// Sets the direction of the ticks (x and y); `in`, `out`, or `inout`
+    pub fn set_tick_direction(&mut self, direction: &str) -> &mut Self {
+        self.set_str("xtick.direction", direction);
+        self.set_str("ytick.direction", direction)
+    }
+
+    /// Sets the length of the major ticks (x and y)
+    pub fn set_tick_length(&mut self, length: f64) -> &mut Self {
+        self.set_number("xtick.major.size", length);
+        self.set_number("ytick.major.size", length)
+    }
+
+    /// Sets the width of the major ticks (x and y)
+    pub fn set_tick_width(&mut self, width: f64) -> &mut Self {
+        self.set_number("xtick.major.width", width);
+        self.set_number("ytick.major.width", width)
+    }
+
+    /// Shows or hides the grid by default
+    pub fn set_grid(&mut self, flag: bool) -> &mut Self {
+        self.set_param("axes.grid", if flag { "True" } else { "False" })
+    }
+
+    /// Sets the color of the grid lines
+    pub fn set_grid_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("grid.color", color)
+    }
+
+    /// Sets the style of the grid lines; e.g., `-`, `--`, `-.`, `:`
+    pub fn set_grid_line_style(&mut self, style: &str) -> &mut Self {
+        self.set_str("grid.linestyle", style)
+    }
+
+    /// Sets the width of the grid lines
+    pub fn set_grid_line_width(&mut self, width: f64) -> &mut Self {
+        self.set_number("grid.linewidth", width)
+    }
+
+    /// Sets the opacity of the grid lines
+    pub fn set_grid_alpha(&mut self, alpha: f64) -> &mut Self {
+        self.set_number("grid.alpha", alpha)
+    }
+
+    /// Sets the background color of legends
+    pub fn set_legend_face_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("legend.facecolor", color)
+    }
+
+    /// Sets the frame color of legends
+    pub fn set_legend_edge_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("legend.edgecolor", color)
+    }
+
+    /// Sets the color of the text in legends
+    pub fn set_legend_label_color(&mut self, color: &str) -> &mut Self {
+        self.set_str("legend.labelcolor", color)
+    }
+
+    /// Sets the colors used, in turn, by the curves (the color cycle)
+    pub fn set_color_cycle(&mut self, colors: &[&str]) -> &mut Self {
+        let list: Vec<_> = colors.iter().map(|c| quote(c)).collect();
+        let value = format!("plt.cycler('color',[{}])", list.join(","));
+        self.set_param("axes.prop_cycle", &value)
+    }
+
+    /// Enables or disables the typesetting of all texts by LaTeX (text.usetex)
+    ///
+    /// When enabled, the fonts are embedded as TrueType (Type 42) into PDF and PS files; thus, the texts
+    /// remain editable. When disabled, the font types are reset to the Matplotlib defaults. Also, the texts are made safe for LaTeX right before saving the figure:
+    /// the characters `%`, `&`, `#`, and `_` are escaped outside math mode (`$...$`).
+    ///
+    /// **Important:** This option requires LaTeX (see [crate::PythonEnv]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plotpy::{Curve, Plot, Style};
+    ///
+    /// let mut style = Style::new();
+    /// style
+    ///     .set_usetex(true)
+    ///     .set_latex_preamble(r"\usepackage{amsmath}")
+    ///     .set_font_family("serif");
+    /// let mut curve = Curve::new();
+    /// curve.draw(&[0.0, 1.0, 2.0], &[0.0, 1.0, 4.0]);
+    /// let mut plot = Plot::new();
+    /// plot.set_style(&style)
+    ///     .add(&curve)
+    ///     .set_title("Growth rate 100% for $\\alpha_1$")
+    ///     .set_labels("$x$", "$\\text{d}y/\\text{d}x$");
+    /// // plot.save("/tmp/plotpy/doc_tests/doc_style_usetex.pdf")?; // requires LaTeX
+    /// ```
+    pub fn set_usetex(&mut self, flag: bool) -> &mut Self {
+        self.usetex = flag;
+        self.set_param("text.usetex", if flag { "True" } else { "False" });
+        if flag {
+            self.set_param("pdf.fonttype", "42");
+            self.set_param("ps.fonttype", "42");
+        } else {
+            self.params.retain(|(k, _)| k != "pdf.fonttype" && k != "ps.fonttype");
+        }
+        self
+    }
+
+    /// Sets the LaTeX preamble used when texts are typeset by LaTeX (see [Style::set_usetex()])
+    ///
+    /// For example: `\usepackage{amsmath}\usepackage{siunitx}`
+    pub fn set_latex_preamble(&mut self, preamble: &str) -> &mut Self {
+        self.set_param("text.latex.preamble", &quote(preamble))
+    }
+
+    /// Sets any rcParams entry
+    ///
+    /// # Input
+    ///
+    /// * `key` -- the rcParams key; e.g., `axes.spines.top`
+    /// * `value` -- the value written as a Python expression; e.g., `False`, `1.5`, or `'red'` (with quotes)
+    pub fn set_param(&mut self, key: &str, value: &str) -> &mut Self {
+        match self.params.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.params.push((key.to_string(), value.to_string())),
+        }
+        self
+    }
+
+    /// Returns the python commands that apply this style
+    pub(crate) fn get_commands(&self) -> String {
+        let mut buffer = String::new();
+        if let Some(base) = &self.base {
+            write!(&mut buffer, "plt.style.use({})\n", quote(base)).unwrap();
+        }
+        if !self.params.is_empty() {
+            buffer.push_str("plt.rcParams.update({\n");
+            for (key, value) in &self.params {
+                write!(&mut buffer, "    {}:{},\n", quote(key), value).unwrap();
+            }
+            buffer.push_str("})\n");
+        }
+        if self.usetex {
+            buffer.push_str(SANITIZE_USETEX);
+        }
+        buffer
+    }
+
+    /// Returns true if the texts are typeset by LaTeX (see [Style::set_usetex()])
+    pub(crate) fn get_usetex(&self) -> bool {
+        self.usetex
+    }
+
+    /// Returns the python commands to be called right before saving the figure
+    pub(crate) fn get_presave_commands(&self) -> &'static str {
+        if self.usetex {
+            "sanitize_usetex()\n"
+        } else {
+            ""
+        }
+    }
+
+    /// Sets an rcParams entry with a string value
+    fn set_str(&mut self, key: &str, value: &str) -> &mut Self {
+        self.set_param(key, &quote(value))
+    }
+
+    /// Sets an rcParams entry with a numeric value
+    fn set_number(&mut self, key: &str, value: f64) -> &mut Self {
+        self.set_param(key, &format!("{}", value))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Style;
+
+    #[test]
+    fn new_works() {
+        let style = Style::new();
+        assert_eq!(style.base, None);
+        assert_eq!(style.params.len(), 0);
+        assert_eq!(style.get_commands(), "");
+        assert_eq!(Style::default().get_commands(), "");
+    }
+
+    #[test]
+    fn setters_work() {
+        let mut style = Style::new();
+        style
+            .set_font_family("serif")
+            .set_font_size(11.0)
+            .set_tick_direction("in")
+            .set_grid(true)
+            .set_color_cycle(&["red", "#00ff00"])
+            .set_param("axes.spines.top", "False")
+            .set_font_size(12.5);
+        assert_eq!(
+            style.get_commands(),
+            "plt.rcParams.update({\n\
+             \x20\x20\x20\x20r'font.family':r'serif',\n\
+             \x20\x20\x20\x20r'font.size':12.5,\n\
+             \x20\x20\x20\x20r'xtick.direction':r'in',\n\
+             \x20\x20\x20\x20r'ytick.direction':r'in',\n\
+             \x20\x20\x20\x20r'axes.grid':True,\n\
+             \x20\x20\x20\x20r'axes.prop_cycle':plt.cycler('color',[r'red',r'#00ff00']),\n\
+             \x20\x20\x20\x20r'axes.spines.top':False,\n\
+             })\n"
+        );
+        let mut style = Style::new();
+        style.set_font_family("Helvetica'").set_param("it's", "1");
+        assert_eq!(
+            style.get_commands(),
+            "plt.rcParams.update({\n\
+             \x20\x20\x20\x20r'font.family':r'Helvetica' \"'\",\n\
+             \x20\x20\x20\x20r'it' \"'\" r's':1,\n\
+             })\n"
+        );
+    }
+
+    #[test]
+    fn set_usetex_works() {
+        let mut style = Style::new();
+        assert_eq!(style.get_presave_commands(), "");
+        style
+            .set_usetex(true)
+            .set_latex_preamble("\\usepackage{amsmath}\n\\newcommand{\\q}{'}");
+        let commands = style.get_commands();
+        assert!(commands.starts_with(
+            "plt.rcParams.update({\n\
+             \x20\x20\x20\x20r'text.usetex':True,\n\
+             \x20\x20\x20\x20r'pdf.fonttype':42,\n\
+             \x20\x20\x20\x20r'ps.fonttype':42,\n\
+             \x20\x20\x20\x20r'text.latex.preamble':r'\\usepackage{amsmath}' '\\n' r'\\newcommand{\\q}{' \"'\" r'}',\n\
+             })\n"
+        ));
+        assert!(commands.contains("def sanitize_usetex():"));
+        assert_eq!(style.get_presave_commands(), "sanitize_usetex()\n");
+        style.set_usetex(false);
+        let commands = style.get_commands();
+        assert!(commands.starts_with(
+            "plt.rcParams.update({\n\
+             \x20\x20\x20\x20r'text.usetex':False,\n\
+             \x20\x20\x20\x20r'text.latex.preamble':"
+        ));
+        assert!(!commands.contains("fonttype"));
+        assert!(!commands.contains("sanitize_usetex"));
+        assert_eq!(style.get_presave_commands(), "");
+    }
+
+    #[test]
+    fn presets_work() {
+        assert_eq!(
+            Style::dark_background().get_commands(),
+            "plt.style.use(r'dark_background')\n"
+        );
+        let mut style = Style::mocha();
+        style.set_base("dark_background").set_axes_face_color("#000000");
+        let commands = style.get_commands();
+        assert!(commands.starts_with("plt.style.use(r'dark_background')\nplt.rcParams.update({\n"));
+        assert!(commands.contains("r'axes.facecolor':r'#000000',\n"));
+        assert!(!commands.contains("#1e1e2e"));
+        assert!(Style::mathematica().get_commands().contains("'#5E81B5'"));
+        assert!(Style::nordic()
+            .get_commands()
+            .contains("r'figure.facecolor':r'#2E3440'"));
+    }
+}
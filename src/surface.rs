@@ -1,6 +1,6 @@
-use super::{matrix_to_array, AsMatrix, GraphMaker, StrError};
+use super::{matrix_to_array, quote, AsMatrix, DataArrays, Formatter, GraphMaker, Locator, StrError};
 use crate::quote_marker;
-use num_traits::Num;
+use num_traits::{Num, ToPrimitive};
 use std::fmt::Write;
 
 /// Generates a 3D a surface (or wireframe, or both)
@@ -10,9 +10,9 @@ use std::fmt::Write;
 /// # Example
 ///
 /// ```
-/// use plotpy::{generate3d, Plot, StrError, Surface};
+/// use plotpy::{generate3d, Error, Plot, Surface};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // generate (x,y,z) matrices
 ///     let n = 21;
 ///     let (x, y, z) = generate3d(-2.0, 2.0, -2.0, 2.0, n, n, |x, y| x * x - y * y);
@@ -56,6 +56,7 @@ pub struct Surface {
     with_colorbar: bool,      // Draw a colorbar
     colorbar_label: String,   // Colorbar label
     number_format_cb: String, // Number format for labels in colorbar
+    colorbar_ticks: String,   // Commands to set the locator and formatter of the colorbar ticks
     surf_color: String,       // Const color of surface (when not using colormap)
     surf_line_color: String,  // Color of surface lines
     surf_line_style: String,  // Style of surface lines
@@ -70,6 +71,7 @@ pub struct Surface {
     point_size: f64,          // Size of markers
     point_style: String,      // Style of markers, e.g., "`o`", "`+`"
     buffer: String,           // buffer
+    arrays: DataArrays,       // data arrays written to the buffer
 }
 
 impl Surface {
@@ -85,6 +87,7 @@ impl Surface {
             with_colorbar: false,
             colorbar_label: String::new(),
             number_format_cb: String::new(),
+            colorbar_ticks: String::new(),
             surf_color: String::new(),
             surf_line_color: String::new(),
             surf_line_style: String::new(),
@@ -99,6 +102,7 @@ impl Surface {
             point_size: 0.0,
             point_style: String::new(),
             buffer: String::new(),
+            arrays: DataArrays::new(),
         }
     }
 
@@ -119,11 +123,11 @@ impl Surface {
     pub fn draw<'a, T, U>(&mut self, x: &'a T, y: &'a T, z: &'a T)
     where
         T: AsMatrix<'a, U>,
-        U: 'a + std::fmt::Display + Num,
+        U: 'a + std::fmt::Display + Num + ToPrimitive,
     {
-        matrix_to_array(&mut self.buffer, "x", x);
-        matrix_to_array(&mut self.buffer, "y", y);
-        matrix_to_array(&mut self.buffer, "z", z);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "x", x);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "y", y);
+        matrix_to_array(&mut self.buffer, &mut self.arrays, "z", z);
         if self.with_surface {
             let opt_surface = self.options_surface();
             write!(&mut self.buffer, "sf=ax3d().plot_surface(x,y,z{})\n", &opt_surface).unwrap();
@@ -140,7 +144,10 @@ impl Surface {
             let opt_colorbar = self.options_colorbar();
             write!(&mut self.buffer, "cb=plt.colorbar(sf{})\n", &opt_colorbar).unwrap();
             if self.colorbar_label != "" {
-                write!(&mut self.buffer, "cb.ax.set_ylabel(r'{}')\n", self.colorbar_label).unwrap();
+                write!(&mut self.buffer, "cb.ax.set_ylabel({})\n", quote(&self.colorbar_label)).unwrap();
+            }
+            if self.colorbar_ticks != "" {
+                write!(&mut self.buffer, "{}cb.update_ticks()\n", self.colorbar_ticks).unwrap();
             }
         }
     }
@@ -224,6 +231,24 @@ impl Surface {
         self
     }
 
+    /// Sets the locator of the colorbar ticks
+    ///
+    /// **Note:** The locator is ignored (and an error is returned) if invalid.
+    pub fn set_colorbar_locator(&mut self, locator: &Locator) -> Result<&mut Self, StrError> {
+        let expression = locator.expression()?;
+        write!(&mut self.colorbar_ticks, "cb.locator={}\n", expression).unwrap();
+        Ok(self)
+    }
+
+    /// Sets the formatter of the colorbar ticks
+    ///
+    /// **Note:** The formatter is ignored (and an error is returned) if invalid.
+    pub fn set_colorbar_formatter(&mut self, formatter: &Formatter) -> Result<&mut Self, StrError> {
+        let commands = formatter.commands("cb_formatter")?;
+        write!(&mut self.colorbar_ticks, "{}cb.formatter=cb_formatter\n", commands).unwrap();
+        Ok(self)
+    }
+
     /// Sets the number format for the labels in the colorbar (cb)
     pub fn set_number_format_cb(&mut self, format: &str) -> &mut Self {
         self.number_format_cb = String::from(format);
@@ -466,8 +491,12 @@ impl GraphMaker for Surface {
     fn get_buffer<'a>(&'a self) -> &'a String {
         &self.buffer
     }
+    fn get_arrays<'a>(&'a self) -> Option<&'a DataArrays> {
+        Some(&self.arrays)
+    }
     fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.arrays.clear();
     }
 }
 
@@ -476,7 +505,7 @@ impl GraphMaker for Surface {
 #[cfg(test)]
 mod tests {
     use super::Surface;
-    use crate::GraphMaker;
+    use crate::{Formatter, GraphMaker, Locator};
 
     #[test]
     fn new_works() {
@@ -612,6 +641,34 @@ mod tests {
         assert_eq!(surface.buffer, "");
     }
 
+    #[test]
+    fn colorbar_ticks_work() {
+        let mut surface = Surface::new();
+        surface
+            .set_with_colorbar(true)
+            .set_colorbar_locator(&Locator::Multiple {
+                base: 0.25,
+                offset: 0.0,
+            })
+            .unwrap()
+            .set_colorbar_formatter(&Formatter::Percent {
+                xmax: 1.0,
+                decimals: None,
+            })
+            .unwrap();
+        let x = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let y = vec![vec![0.0, 0.0], vec![1.0, 1.0]];
+        let z = vec![vec![0.0, 0.5], vec![0.5, 1.0]];
+        surface.draw(&x, &y, &z);
+        assert!(surface.buffer.ends_with(
+            "cb=plt.colorbar(sf)\n\
+             cb.locator=tck.MultipleLocator(0.25)\n\
+             cb_formatter=tck.PercentFormatter(xmax=1)\n\
+             cb.formatter=cb_formatter\n\
+             cb.update_ticks()\n"
+        ));
+    }
+
     #[test]
     fn aligned_system_fails_on_wrong_input() {
         let res = Surface::aligned_system(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0]);
@@ -1,4 +1,4 @@
-use crate::{generate3d, suq_cos, suq_sin, StrError, Surface};
+use crate::{generate3d, suq_cos, suq_sin, Error, Surface};
 use std::f64::consts::PI;
 
 impl Surface {
@@ -15,10 +15,10 @@ impl Surface {
     /// # Example
     ///
     /// ```
-    /// use plotpy::{Plot, StrError, Surface};
+    /// use plotpy::{Error, Plot, Surface};
     /// use std::path::Path;
     ///
-    /// fn main() -> Result<(), StrError> {
+    /// fn main() -> Result<(), Error> {
     ///     // configure and draw surface
     ///     let mut surface = Surface::new();
     ///     let a = &[0.0, 0.0, 0.0];
@@ -48,18 +48,18 @@ impl Surface {
         radius: f64,
         ndiv_axis: usize,
         ndiv_perimeter: usize,
-    ) -> Result<(), StrError> {
+    ) -> Result<(), Error> {
         if a.len() != 3 {
-            return Err("a.len() must equal to 3");
+            return Err(Error::InvalidInput("a.len() must equal to 3"));
         }
         if b.len() != 3 {
-            return Err("b.len() must equal to 3");
+            return Err(Error::InvalidInput("b.len() must equal to 3"));
         }
         if ndiv_axis < 1 {
-            return Err("ndiv_axis must be ≥ 1");
+            return Err(Error::InvalidInput("ndiv_axis must be ≥ 1"));
         }
         if ndiv_perimeter < 3 {
-            return Err("ndiv_perimeter must be ≥ 3");
+            return Err(Error::InvalidInput("ndiv_perimeter must be ≥ 3"));
         }
         let (e0, e1, e2) = Surface::aligned_system(a, b)?;
         let cylinder_height =
@@ -107,10 +107,10 @@ impl Surface {
     /// # Example
     ///
     /// ```
-    /// use plotpy::{Plot, StrError, Surface};
+    /// use plotpy::{Error, Plot, Surface};
     /// use std::path::Path;
     ///
-    /// fn main() -> Result<(), StrError> {
+    /// fn main() -> Result<(), Error> {
     ///     // configure and draw surface
     ///     let mut surface = Surface::new();
     ///     let p = &[0.0, 0.0, 0.0];
@@ -144,15 +144,17 @@ impl Surface {
         ymax: f64,
         nx: usize,
         ny: usize,
-    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
         if p.len() != 3 || n.len() != 3 {
-            return Err("p.len() and n.len() must be equal to 3");
+            return Err(Error::InvalidInput("p.len() and n.len() must be equal to 3"));
         }
         if f64::abs(n[2]) < 1e-10 {
-            return Err("the z-component of the normal vector cannot be zero");
+            return Err(Error::InvalidInput(
+                "the z-component of the normal vector cannot be zero",
+            ));
         }
         if nx < 2 || ny < 2 {
-            return Err("nx and ny must be ≥ 2");
+            return Err(Error::InvalidInput("nx and ny must be ≥ 2"));
         }
         let d = -n[0] * p[0] - n[1] * p[1] - n[2] * p[2];
         let (x, y, z) = generate3d(xmin, xmax, ymin, ymax, nx + 1, ny + 1, |x, y| {
@@ -181,10 +183,10 @@ impl Surface {
     /// # Example
     ///
     /// ```
-    /// use plotpy::{Plot, StrError, Surface};
+    /// use plotpy::{Error, Plot, Surface};
     /// use std::path::Path;
     ///
-    /// fn main() -> Result<(), StrError> {
+    /// fn main() -> Result<(), Error> {
     ///     // draw hat
     ///     let mut hat = Surface::new();
     ///     let c = &[-0.5, 0.0, 0.0];
@@ -222,12 +224,12 @@ impl Surface {
         n_alpha: usize,
         n_theta: usize,
         cup: bool,
-    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
         if c.len() != 3 {
-            return Err("c.len() must be equal to 3");
+            return Err(Error::InvalidInput("c.len() must be equal to 3"));
         }
         if n_alpha < 2 || n_theta < 2 {
-            return Err("n_alpha and n_theta must be ≥ 2");
+            return Err(Error::InvalidInput("n_alpha and n_theta must be ≥ 2"));
         }
         let a_min = alpha_min * PI / 180.0;
         let a_max = alpha_max * PI / 180.0;
@@ -278,10 +280,10 @@ impl Surface {
     /// # Example
     ///
     /// ```
-    /// use plotpy::{Plot, StrError, Surface};
+    /// use plotpy::{Error, Plot, Surface};
     /// use std::path::Path;
     ///
-    /// fn main() -> Result<(), StrError> {
+    /// fn main() -> Result<(), Error> {
     ///     // configure and draw surface
     ///     let c = &[0.0, 0.0, 0.0];
     ///     let r = &[1.0, 1.0, 1.0];
@@ -316,15 +318,15 @@ impl Surface {
         theta_max: f64,
         n_alpha: usize,
         n_theta: usize,
-    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
         if c.len() != 3 || r.len() != 3 || k.len() != 3 {
-            return Err("c.len(), r.len(), and k.len() must be equal to 3");
+            return Err(Error::InvalidInput("c.len(), r.len(), and k.len() must be equal to 3"));
         }
         if n_alpha < 2 || n_theta < 2 {
-            return Err("n_alpha and n_theta must be ≥ 2");
+            return Err(Error::InvalidInput("n_alpha and n_theta must be ≥ 2"));
         }
         if k[0] < 0.0 || k[1] < 0.0 || k[2] < 0.0 {
-            return Err("exponents k must be greater than zero");
+            return Err(Error::InvalidInput("exponents k must be greater than zero"));
         }
         let (aa, bb, cc) = (2.0 / k[0], 2.0 / k[1], 2.0 / k[2]);
         let a_min = alpha_min * PI / 180.0;
@@ -365,10 +367,10 @@ impl Surface {
     /// # Example
     ///
     /// ```
-    /// use plotpy::{Plot, StrError, Surface};
+    /// use plotpy::{Error, Plot, Surface};
     /// use std::path::Path;
     ///
-    /// fn main() -> Result<(), StrError> {
+    /// fn main() -> Result<(), Error> {
     ///     // configure and draw surface
     ///     let mut surface = Surface::new();
     ///     let c = &[0.0, 0.0, 0.0];
@@ -396,12 +398,12 @@ impl Surface {
         r: f64,
         n_alpha: usize,
         n_theta: usize,
-    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), StrError> {
+    ) -> Result<(Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<Vec<f64>>), Error> {
         if c.len() != 3 {
-            return Err("c.len() must be equal to 3");
+            return Err(Error::InvalidInput("c.len() must be equal to 3"));
         }
         if n_alpha < 2 || n_theta < 2 {
-            return Err("n_alpha and n_theta must be ≥ 2");
+            return Err(Error::InvalidInput("n_alpha and n_theta must be ≥ 2"));
         }
         let (alpha_min, alpha_max) = (-180.0, 180.0);
         let (theta_min, theta_max) = (-90.0, 90.0);
@@ -430,19 +432,19 @@ mod tests {
     fn draw_cylinder_fails_on_wrong_input() {
         let mut surf = Surface::new();
         let res = surf.draw_cylinder(&[0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 1, 3);
-        assert_eq!(res.err(), Some("a.len() must equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "a.len() must equal to 3");
 
         let res = surf.draw_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0], 1.0, 1, 3);
-        assert_eq!(res.err(), Some("b.len() must equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "b.len() must equal to 3");
 
         let res = surf.draw_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 0, 3);
-        assert_eq!(res.err(), Some("ndiv_axis must be ≥ 1"));
+        assert_eq!(res.err().unwrap().to_string(), "ndiv_axis must be ≥ 1");
 
         let res = surf.draw_cylinder(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 1.0, 1, 2);
-        assert_eq!(res.err(), Some("ndiv_perimeter must be ≥ 3"));
+        assert_eq!(res.err().unwrap().to_string(), "ndiv_perimeter must be ≥ 3");
 
         let res = surf.draw_cylinder(&[0.0, 0.0, 0.0], &[0.0, 0.0, 0.0], 1.0, 1, 3);
-        assert_eq!(res.err(), Some("a-to-b segment is too short"));
+        assert_eq!(res.err().unwrap().to_string(), "a-to-b segment is too short");
     }
 
     #[test]
@@ -457,17 +459,20 @@ mod tests {
     fn draw_plane_nzz_fails_on_wrong_input() {
         let mut surf = Surface::new();
         let res = surf.draw_plane_nzz(&[0.0, 0.0], &[1.0, 1.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
-        assert_eq!(res.err(), Some("p.len() and n.len() must be equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "p.len() and n.len() must be equal to 3");
         let res = surf.draw_plane_nzz(&[0.0, 0.0, 0.0], &[1.0, 1.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
-        assert_eq!(res.err(), Some("p.len() and n.len() must be equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "p.len() and n.len() must be equal to 3");
 
         let res = surf.draw_plane_nzz(&[0.0, 0.0, 0.0], &[1.0, 1.0, 0.0], 0.0, 1.0, 0.0, 1.0, 2, 2);
-        assert_eq!(res.err(), Some("the z-component of the normal vector cannot be zero"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "the z-component of the normal vector cannot be zero"
+        );
 
         let res = surf.draw_plane_nzz(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 0.0, 1.0, 0.0, 1.0, 1, 2);
-        assert_eq!(res.err(), Some("nx and ny must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "nx and ny must be ≥ 2");
         let res = surf.draw_plane_nzz(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], 0.0, 1.0, 0.0, 1.0, 2, 1);
-        assert_eq!(res.err(), Some("nx and ny must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "nx and ny must be ≥ 2");
     }
 
     #[test]
@@ -482,12 +487,12 @@ mod tests {
     fn draw_hemisphere_fails_on_wrong_input() {
         let mut surf = Surface::new();
         let res = surf.draw_hemisphere(&[0.0, 0.0], 1.0, 0.0, 180.0, 2, 2, false);
-        assert_eq!(res.err(), Some("c.len() must be equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "c.len() must be equal to 3");
 
         let res = surf.draw_hemisphere(&[0.0, 0.0, 0.0], 1.0, 0.0, 180.0, 1, 2, false);
-        assert_eq!(res.err(), Some("n_alpha and n_theta must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "n_alpha and n_theta must be ≥ 2");
         let res = surf.draw_hemisphere(&[0.0, 0.0, 0.0], 1.0, 0.0, 180.0, 2, 1, false);
-        assert_eq!(res.err(), Some("n_alpha and n_theta must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "n_alpha and n_theta must be ≥ 2");
     }
 
     #[test]
@@ -508,24 +513,33 @@ mod tests {
 
         let mut surf = Surface::new();
         let res = surf.draw_superquadric(d2, d3, d3, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("c.len(), r.len(), and k.len() must be equal to 3"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "c.len(), r.len(), and k.len() must be equal to 3"
+        );
         let res = surf.draw_superquadric(d3, d2, d3, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("c.len(), r.len(), and k.len() must be equal to 3"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "c.len(), r.len(), and k.len() must be equal to 3"
+        );
         let res = surf.draw_superquadric(d3, d3, d2, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("c.len(), r.len(), and k.len() must be equal to 3"));
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "c.len(), r.len(), and k.len() must be equal to 3"
+        );
 
         let res = surf.draw_superquadric(d3, d3, d3, 0.0, 180.0, 0.0, 180.0, 1, 2);
-        assert_eq!(res.err(), Some("n_alpha and n_theta must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "n_alpha and n_theta must be ≥ 2");
 
         let ka = &[-1.0, 0.0, 0.0];
         let kb = &[0.0, -1.0, 0.0];
         let kc = &[0.0, 0.0, -1.0];
         let res = surf.draw_superquadric(d3, d3, ka, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("exponents k must be greater than zero"));
+        assert_eq!(res.err().unwrap().to_string(), "exponents k must be greater than zero");
         let res = surf.draw_superquadric(d3, d3, kb, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("exponents k must be greater than zero"));
+        assert_eq!(res.err().unwrap().to_string(), "exponents k must be greater than zero");
         let res = surf.draw_superquadric(d3, d3, kc, 0.0, 180.0, 0.0, 180.0, 2, 2);
-        assert_eq!(res.err(), Some("exponents k must be greater than zero"));
+        assert_eq!(res.err().unwrap().to_string(), "exponents k must be greater than zero");
     }
 
     #[test]
@@ -550,12 +564,12 @@ mod tests {
     fn draw_sphere_fails_on_wrong_input() {
         let mut surf = Surface::new();
         let res = surf.draw_sphere(&[0.0, 0.0], 1.0, 2, 2);
-        assert_eq!(res.err(), Some("c.len() must be equal to 3"));
+        assert_eq!(res.err().unwrap().to_string(), "c.len() must be equal to 3");
 
         let res = surf.draw_sphere(&[0.0, 0.0, 0.0], 1.0, 1, 2);
-        assert_eq!(res.err(), Some("n_alpha and n_theta must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "n_alpha and n_theta must be ≥ 2");
         let res = surf.draw_sphere(&[0.0, 0.0, 0.0], 1.0, 2, 1);
-        assert_eq!(res.err(), Some("n_alpha and n_theta must be ≥ 2"));
+        assert_eq!(res.err().unwrap().to_string(), "n_alpha and n_theta must be ≥ 2");
     }
 
     #[test]
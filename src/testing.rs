@@ -0,0 +1,258 @@
+//! Implements helpers for snapshot testing (requires the `testing` feature)
+//!
+//! The first helper, [assert_golden()], compares the python commands generated by [crate::Plot]
+//! (e.g., [crate::Plot::to_python_string()]) or by any [crate::GraphMaker] (`get_buffer()`) with
+//! a stored golden file. The golden files are (re)written when the environment variable
+//! `PLOTPY_UPDATE_GOLDEN` is set to `1`. The common [crate::PYTHON_HEADER] is replaced by the
+//! [HEADER_PLACEHOLDER] line, thus the golden files do not change when the header changes.
+//!
+//! The second helper, [compare_png()], compares a rendered PNG figure with a baseline image
+//! using `matplotlib.testing.compare`. The variant [compare_png_with()] runs the Python executable
+//! (and process options) set in a [crate::Plot].
+
+use super::{quote, Error, Plot};
+use crate::PYTHON_HEADER;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Environment variable that makes [assert_golden()] write the golden files
+pub const UPDATE_GOLDEN_VAR: &str = "PLOTPY_UPDATE_GOLDEN";
+
+/// Line replacing the [crate::PYTHON_HEADER] in the commands compared by [assert_golden()]
+pub const HEADER_PLACEHOLDER: &str = "#### PYTHON_HEADER ####\n";
+
+/// Asserts that the python commands match the contents of a golden file
+///
+/// If the environment variable `PLOTPY_UPDATE_GOLDEN` is set to `1`, the golden file is written
+/// (or overwritten) with the given commands instead (the directory is created if needed).
+///
+/// The first occurrence of [crate::PYTHON_HEADER] in `actual` is replaced by [HEADER_PLACEHOLDER]
+/// before comparing or writing the commands.
+///
+/// # Input
+///
+/// * `actual` -- the python commands; e.g., `curve.get_buffer()` or `plot.to_python_string(...)`
+/// * `golden_path` -- the path of the golden file
+///
+/// # Panics
+///
+/// Panics if the golden file cannot be read or written or if the contents differ; the message
+/// shows the first line that differs.
+///
+/// # Example
+///
+/// ```
+/// use plotpy::testing::assert_golden;
+/// use plotpy::{Curve, GraphMaker};
+///
+/// let mut curve = Curve::new();
+/// curve.draw(&[0.0, 1.0], &[0.0, 1.0]);
+/// std::fs::create_dir_all("/tmp/plotpy/doc_tests").unwrap();
+/// std::fs::write("/tmp/plotpy/doc_tests/doc_assert_golden.py", curve.get_buffer()).unwrap();
+/// assert_golden(curve.get_buffer(), "/tmp/plotpy/doc_tests/doc_assert_golden.py");
+/// ```
+pub fn assert_golden<P>(actual: &str, golden_path: P)
+where
+    P: AsRef<Path>,
+{
+    let path = golden_path.as_ref();
+    let actual = &actual.replacen(PYTHON_HEADER, HEADER_PLACEHOLDER, 1);
+    if env::var(UPDATE_GOLDEN_VAR).is_ok_and(|v| v == "1") {
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).unwrap_or_else(|e| panic!("cannot create {}: {}", p.display(), e));
+        }
+        fs::write(path, actual).unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+        return;
+    }
+    let expected = fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read golden file {}: {} (set {}=1 to create it)",
+            path.display(),
+            e,
+            UPDATE_GOLDEN_VAR
+        )
+    });
+    if let Some(message) = first_difference(&expected, actual) {
+        panic!(
+            "python commands differ from golden file {}\n{}\n(set {}=1 to update it)",
+            path.display(),
+            message,
+            UPDATE_GOLDEN_VAR
+        );
+    }
+}
+
+/// Compares a PNG figure with a baseline image using `matplotlib.testing.compare`
+///
+/// This function runs `python3`; use [compare_png_with()] to run the Python executable set by
+/// [Plot::set_python_exe()].
+///
+/// # Input
+///
+/// * `baseline_path` -- the path of the expected image
+/// * `actual_path` -- the path of the generated image
+/// * `tolerance` -- the maximum root-mean-square (RMS) difference of the pixel values (0 to 255)
+///
+/// # Output
+///
+/// Returns [Error::ImageMismatch] if the RMS difference exceeds the tolerance or if the images
+/// have different sizes (the RMS value is NaN in this case). Matplotlib also writes an image with
+/// the differences next to the actual image (with the `-failed-diff.png` suffix).
+pub fn compare_png<P, Q>(baseline_path: P, actual_path: Q, tolerance: f64) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    compare_png_with(&Plot::new(), baseline_path, actual_path, tolerance)
+}
+
+/// Compares a PNG figure with a baseline image using the Python executable of a plot
+///
+/// Same as [compare_png()], but runs the Python executable set by [Plot::set_python_exe()] with
+/// the process options of `plot` (e.g., [Plot::set_working_dir()] and [Plot::set_env()]).
+/// Relative paths are resolved with respect to the working directory of `plot`.
+pub fn compare_png_with<P, Q>(plot: &Plot, baseline_path: P, actual_path: Q, tolerance: f64) -> Result<(), Error>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let baseline_path = plot.resolve_path(baseline_path.as_ref());
+    let actual_path = plot.resolve_path(actual_path.as_ref());
+    for path in [&baseline_path, &actual_path] {
+        fs::metadata(path).map_err(Error::io(path))?;
+    }
+    let commands = format!(
+        "from matplotlib.testing.compare import compare_images\n\
+         from matplotlib.testing.exceptions import ImageComparisonFailure\n\
+         try:\n\
+         \x20\x20\x20\x20res=compare_images({},{},{},in_decorator=True)\n\
+         \x20\x20\x20\x20print('ok' if res is None else repr(float(res['rms'])))\n\
+         except ImageComparisonFailure as e:\n\
+         \x20\x20\x20\x20print('nan', str(e).replace('\\n',' '))\n",
+        quote(&baseline_path.to_string_lossy()),
+        quote(&actual_path.to_string_lossy()),
+        tolerance
+    );
+    let (result, stdout) = plot.call_python_capture(&commands, None)?;
+    if !result.success {
+        return Err(Error::from_python_output(false, result.code, &result.output));
+    }
+    let stdout = String::from_utf8_lossy(&stdout);
+    let line = stdout.trim();
+    if line == "ok" {
+        return Ok(());
+    }
+    let (rms, message) = line.split_once(' ').unwrap_or((line, ""));
+    Err(Error::ImageMismatch {
+        rms: rms.parse().unwrap_or(f64::NAN),
+        tolerance,
+        message: message.to_string(),
+    })
+}
+
+/// Returns a message describing the first line that differs (None if the strings are equal)
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut number = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => number += 1,
+            (e, a) => {
+                return Some(format!(
+                    "line {}:\n  expected: {}\n    actual: {}",
+                    number,
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>")
+                ))
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_golden, compare_png, compare_png_with, first_difference, HEADER_PLACEHOLDER};
+    use crate::{Plot, PYTHON_HEADER};
+    use std::fs;
+    use std::path::Path;
+
+    const OUT_DIR: &str = "/tmp/plotpy/unit_tests";
+
+    #[test]
+    fn first_difference_works() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            first_difference("a\nb\n", "a\nc\n").unwrap(),
+            "line 2:\n  expected: b\n    actual: c"
+        );
+        assert_eq!(
+            first_difference("a\n", "a\nb\n").unwrap(),
+            "line 2:\n  expected: <end of file>\n    actual: b"
+        );
+        assert_eq!(
+            first_difference("a\n", "a").unwrap(),
+            "line 2:\n  expected: <end of file>\n    actual: <end of file>"
+        );
+    }
+
+    #[test]
+    fn assert_golden_works() {
+        let path = Path::new(OUT_DIR).join("assert_golden_works.py");
+        fs::create_dir_all(OUT_DIR).unwrap();
+        fs::write(&path, "plt.plot(x,y)\n").unwrap();
+        assert_golden("plt.plot(x,y)\n", &path);
+    }
+
+    #[test]
+    fn assert_golden_replaces_header() {
+        let path = Path::new(OUT_DIR).join("assert_golden_replaces_header.py");
+        fs::create_dir_all(OUT_DIR).unwrap();
+        fs::write(
+            &path,
+            format!("import matplotlib\n{}plt.plot(x,y)\n", HEADER_PLACEHOLDER),
+        )
+        .unwrap();
+        assert_golden(&format!("import matplotlib\n{}plt.plot(x,y)\n", PYTHON_HEADER), &path);
+    }
+
+    #[test]
+    #[should_panic(expected = "python commands differ from golden file")]
+    fn assert_golden_captures_differences() {
+        let path = Path::new(OUT_DIR).join("assert_golden_captures_differences.py");
+        fs::create_dir_all(OUT_DIR).unwrap();
+        fs::write(&path, "plt.plot(x,y)\n").unwrap();
+        assert_golden("plt.plot(y,x)\n", &path);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read golden file")]
+    fn assert_golden_captures_missing_file() {
+        assert_golden("", "/tmp/plotpy/unit_tests/__missing_golden_file__.py");
+    }
+
+    #[test]
+    fn compare_png_captures_errors() {
+        let err = compare_png("/tmp/plotpy/__missing__.png", "/tmp/plotpy/__missing__.png", 1.0)
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("cannot access /tmp/plotpy/__missing__.png"));
+    }
+
+    #[test]
+    fn compare_png_with_uses_python_exe() {
+        let path = Path::new(OUT_DIR).join("compare_png_with_uses_python_exe.png");
+        fs::create_dir_all(OUT_DIR).unwrap();
+        fs::write(&path, b"").unwrap();
+        let mut plot = Plot::new();
+        plot.set_python_exe("__wrong_python_exe__");
+        let err = compare_png_with(&plot, &path, &path, 1.0).err().unwrap();
+        assert!(err.to_string().starts_with("cannot run __wrong_python_exe__"));
+    }
+}
@@ -1,4 +1,4 @@
-use super::GraphMaker;
+use super::{quote, GraphMaker};
 use num_traits::Num;
 use std::fmt::Write;
 
@@ -9,10 +9,10 @@ use std::fmt::Write;
 /// # Example
 ///
 /// ```
-/// use plotpy::{Plot, Text, StrError};
+/// use plotpy::{Error, Plot, Text};
 /// use std::path::Path;
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // configure text
 ///     let mut text = Text::new();
 ///     text.set_color("purple")
@@ -85,7 +85,7 @@ impl Text {
         T: std::fmt::Display + Num,
     {
         let opt = self.options();
-        write!(&mut self.buffer, "t=plt.text({},{},r'{}'{})\n", x, y, message, &opt).unwrap();
+        write!(&mut self.buffer, "t=plt.text({},{},{}{})\n", x, y, quote(message), &opt).unwrap();
         if self.bbox {
             let opt_bbox = self.options_bbox();
             write!(&mut self.buffer, "t.set_bbox(dict({}))\n", opt_bbox).unwrap();
@@ -100,8 +100,12 @@ impl Text {
         let opt = self.options();
         write!(
             &mut self.buffer,
-            "t=ax3d().text({},{},{},r'{}'{})\n",
-            x, y, z, message, &opt
+            "t=ax3d().text({},{},{},{}{})\n",
+            x,
+            y,
+            z,
+            quote(message),
+            &opt
         )
         .unwrap();
         if self.bbox {
@@ -0,0 +1,76 @@
+/// Defines the location of the zero angle on polar axes
+///
+/// See [crate::Plot::set_polar_zero_location()].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZeroLocation {
+    /// North (top)
+    N,
+
+    /// Northwest
+    NW,
+
+    /// West (left)
+    W,
+
+    /// Southwest
+    SW,
+
+    /// South (bottom)
+    S,
+
+    /// Southeast
+    SE,
+
+    /// East (right; default)
+    #[default]
+    E,
+
+    /// Northeast
+    NE,
+}
+
+impl ZeroLocation {
+    /// Returns the argument of `set_theta_zero_location`
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ZeroLocation::N => "N",
+            ZeroLocation::NW => "NW",
+            ZeroLocation::W => "W",
+            ZeroLocation::SW => "SW",
+            ZeroLocation::S => "S",
+            ZeroLocation::SE => "SE",
+            ZeroLocation::E => "E",
+            ZeroLocation::NE => "NE",
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ZeroLocation;
+
+    #[test]
+    fn derive_and_default_work() {
+        let location = ZeroLocation::default();
+        assert_eq!(location, ZeroLocation::E);
+        assert_eq!(format!("{:?}", location.clone()), "E");
+    }
+
+    #[test]
+    fn as_str_works() {
+        let all = [
+            ZeroLocation::N,
+            ZeroLocation::NW,
+            ZeroLocation::W,
+            ZeroLocation::SW,
+            ZeroLocation::S,
+            ZeroLocation::SE,
+            ZeroLocation::E,
+            ZeroLocation::NE,
+        ];
+        let names: Vec<_> = all.iter().map(|l| l.as_str()).collect();
+        assert_eq!(names, &["N", "NW", "W", "SW", "S", "SE", "E", "NE"]);
+    }
+}
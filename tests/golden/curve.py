@@ -0,0 +1,3 @@
+x=np.array([1,2,3,])
+y=np.array([1,4,9,])
+plt.plot(x,y,color='#cd0000',marker='o')
@@ -0,0 +1,16 @@
+#### >>>> file generated by plotpy <<<< ####
+
+import matplotlib
+matplotlib.use('Agg')
+#### PYTHON_HEADER ####
+x=np.array([1,2,3,])
+y=np.array([1,4,9,])
+plt.plot(x,y)
+plt.title(r'golden')
+plt.gca().set_axisbelow(True)
+plt.grid(linestyle='--',color='grey',zorder=-1000)
+plt.gca().set_xlabel(r'x')
+plt.gca().set_ylabel(r'y')
+
+fn=r'/tmp/plotpy/integ_tests/golden_plot.svg'
+plt.savefig(fn,bbox_inches='tight',bbox_extra_artists=EXTRA_ARTISTS)
@@ -0,0 +1,49 @@
+use plotpy::{linspace, Animation, Curve, Error, Plot};
+use std::fs;
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_animation() -> Result<(), Error> {
+    // frames
+    let x = linspace(0.0, 6.3, 31);
+    let mut animation = Animation::new();
+    animation.set_fps(5.0).set_dpi(50.0).set_loop(false);
+    for i in 0..5 {
+        let y: Vec<_> = x.iter().map(|v| f64::sin(v - 0.5 * (i as f64))).collect();
+        let mut curve = Curve::new();
+        curve.draw(&x, &y);
+        animation.add_frame(&[&curve]);
+    }
+    animation.add_frames(5, |i| {
+        let y: Vec<_> = x.iter().map(|v| f64::cos(v - 0.5 * (i as f64))).collect();
+        let mut curve = Curve::new();
+        curve.draw(&x, &y);
+        let mut plot = Plot::new();
+        plot.add(&curve).set_range(0.0, 6.3, -1.1, 1.1);
+        plot
+    })?;
+
+    // save animation
+    let path = Path::new(OUT_DIR).join("integ_animation.gif");
+    animation.save(&path)?;
+
+    // check file (plays once: no loop extension)
+    let data = fs::read(&path).map_err(|_| "cannot read file")?;
+    assert_eq!(&data[0..3], b"GIF");
+    assert!(!has_loop_extension(&data));
+
+    // save animation that loops forever
+    animation.set_loop(true);
+    let path = Path::new(OUT_DIR).join("integ_animation_loop.gif");
+    animation.save(&path)?;
+    let data = fs::read(&path).map_err(|_| "cannot read file")?;
+    assert!(has_loop_extension(&data));
+    Ok(())
+}
+
+/// Returns true if the GIF data has the NETSCAPE2.0 application extension (loop count)
+fn has_loop_extension(data: &[u8]) -> bool {
+    data.windows(11).any(|w| w == b"NETSCAPE2.0")
+}
@@ -1,4 +1,4 @@
-use plotpy::{Barplot, Plot, StrError};
+use plotpy::{Barplot, Error, Plot};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -7,7 +7,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_barplot_1() -> Result<(), StrError> {
+fn test_barplot_1() -> Result<(), Error> {
     // data
     let xx = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
     let yy = [5, 4, 3, 2, 1, 0, 1, 2, 3, 4];
@@ -33,7 +33,7 @@ fn test_barplot_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_barplot_2() -> Result<(), StrError> {
+fn test_barplot_2() -> Result<(), Error> {
     // data
     let species = ["Adelie", "Chinstrap", "Gentoo"];
     let sex_counts = HashMap::from([
@@ -77,7 +77,7 @@ fn test_barplot_2() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_barplot_3() -> Result<(), StrError> {
+fn test_barplot_3() -> Result<(), Error> {
     // data
     let fruits = [1.0, 2.0, 3.0];
     let prices = [10.0, 20.0, 30.0];
@@ -108,7 +108,7 @@ fn test_barplot_3() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_barplot_4() -> Result<(), StrError> {
+fn test_barplot_4() -> Result<(), Error> {
     // data
     let fruits = ["Apple", "Banana", "Orange"];
     let prices = [10.0, 20.0, 30.0];
@@ -139,7 +139,7 @@ fn test_barplot_4() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_barplot_5() -> Result<(), StrError> {
+fn test_barplot_5() -> Result<(), Error> {
     // data
     let y = vec![-2.5, -15.0, -5.0, -7.5, -2.5, -5.0, -17.5];
     let e = vec![0.5, 0.4, 0.1, 0.7, 0.2, 0.0, 1.7];
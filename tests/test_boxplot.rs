@@ -1,4 +1,4 @@
-use plotpy::{Boxplot, Plot, StrError};
+use plotpy::{Boxplot, Error, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_boxplot_1() -> Result<(), StrError> {
+fn test_boxplot_1() -> Result<(), Error> {
     let data = vec![
         //   A  B  C  D  E
         vec![1, 2, 3, 4, 5],
@@ -53,7 +53,7 @@ fn test_boxplot_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_boxplot_2() -> Result<(), StrError> {
+fn test_boxplot_2() -> Result<(), Error> {
     let data = vec![
         vec![1, 2, 3, 4, 5],              // A
         vec![2, 3, 4, 5, 6, 7, 8, 9, 10], // B
@@ -94,7 +94,7 @@ fn test_boxplot_2() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_boxplot_3() -> Result<(), StrError> {
+fn test_boxplot_3() -> Result<(), Error> {
     let data = vec![
         //    A    B    C    D    E ← matrix: columns are series
         //                            nested: rows are series
@@ -139,7 +139,7 @@ fn test_boxplot_3() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_boxplot_4() -> Result<(), StrError> {
+fn test_boxplot_4() -> Result<(), Error> {
     let data = vec![
         vec![1, 2, 3, 4, 5, 10],          // A
         vec![2, 3, 4, 5, 6, 7, 8, 9, 10], // B
@@ -183,7 +183,7 @@ fn test_boxplot_4() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_boxplot_5() -> Result<(), StrError> {
+fn test_boxplot_5() -> Result<(), Error> {
     let x = vec![
         vec![1, 2, 3],       // A
         vec![2, 3, 4, 5, 6], // B
@@ -1,4 +1,4 @@
-use plotpy::{Canvas, Curve, Plot, PolyCode, StrError};
+use plotpy::{Canvas, Curve, Error, Plot, PolyCode};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_canvas() -> Result<(), StrError> {
+fn test_canvas() -> Result<(), Error> {
     // canvas object and common options
     let mut canvas = Canvas::new();
     canvas.set_edge_color("#cd0000").set_face_color("#1862ab");
@@ -51,7 +51,7 @@ fn test_canvas() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_grid_2d() -> Result<(), StrError> {
+fn test_canvas_grid_2d() -> Result<(), Error> {
     // canvas object and common options
     let mut s2d = Canvas::new();
     s2d.draw_grid(&[-0.2, -0.2], &[0.8, 1.8], &[5, 5], true, true)?;
@@ -73,7 +73,7 @@ fn test_canvas_grid_2d() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_grid_3d() -> Result<(), StrError> {
+fn test_canvas_grid_3d() -> Result<(), Error> {
     // canvas object and common options
     let mut s3d = Canvas::new();
     s3d.draw_grid(&[-1.0, -1.0, -1.0], &[1.0, 1.0, 1.0], &[2, 2, 2], true, true)?;
@@ -98,7 +98,7 @@ fn test_canvas_grid_3d() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_polyline_3d_methods() -> Result<(), StrError> {
+fn test_canvas_polyline_3d_methods() -> Result<(), Error> {
     //           .   .  .   . ,.2|
     //         ' .           ,,'||
     //       '   .         ,,'  ||
@@ -154,7 +154,7 @@ fn test_canvas_polyline_3d_methods() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_polyline_3d() -> Result<(), StrError> {
+fn test_canvas_polyline_3d() -> Result<(), Error> {
     //           .   .  .   . ,.2|
     //         ' .           ,,'||
     //       '   .         ,,'  ||
@@ -209,7 +209,7 @@ fn test_canvas_polyline_3d() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_polycurve_quadratic() -> Result<(), StrError> {
+fn test_canvas_polycurve_quadratic() -> Result<(), Error> {
     // coordinates of control points
     let points = &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]];
 
@@ -247,7 +247,7 @@ fn test_canvas_polycurve_quadratic() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_polycurve_cubic() -> Result<(), StrError> {
+fn test_canvas_polycurve_cubic() -> Result<(), Error> {
     // coordinates of control points
     let x = &[1.58, 0.35, -1.75, 0.375, 0.85, 2.2, 3.0, 2.0];
     let y = &[-2.57, -1.1, 2.0, 2.0, 1.15, 3.2, 0.05, -0.5];
@@ -296,7 +296,7 @@ fn test_canvas_polycurve_cubic() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_polycurve_methods() -> Result<(), StrError> {
+fn test_canvas_polycurve_methods() -> Result<(), Error> {
     // coordinates of control points
     let x = &[1.58, 0.35, -1.75, 0.375, 0.85, 2.2, 3.0, 2.0];
     let y = &[-2.57, -1.1, 2.0, 2.0, 1.15, 3.2, 0.05, -0.5];
@@ -349,7 +349,7 @@ fn test_canvas_polycurve_methods() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_rectangle() -> Result<(), StrError> {
+fn test_canvas_rectangle() -> Result<(), Error> {
     // canvas
     let mut canvas = Canvas::new();
 
@@ -383,7 +383,7 @@ fn test_canvas_rectangle() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_rectangle_and_text() -> Result<(), StrError> {
+fn test_canvas_rectangle_and_text() -> Result<(), Error> {
     // canvas
     let mut canvas = Canvas::new();
 
@@ -429,7 +429,7 @@ fn test_canvas_rectangle_and_text() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_draw_triangles() -> Result<(), StrError> {
+fn test_canvas_draw_triangles() -> Result<(), Error> {
     // point coordinates (two triangles in a square)
     let xx = &[0.0, 1.0, 1.0, 0.0];
     let yy = &[0.0, 0.0, 1.0, 1.0];
@@ -465,7 +465,7 @@ fn test_canvas_draw_triangles() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_draw_triangles_3d() -> Result<(), StrError> {
+fn test_canvas_draw_triangles_3d() -> Result<(), Error> {
     // point coordinates (one tetrahedron)
     let xx = &[0.0, 1.0, 0.0, 0.0];
     let yy = &[0.0, 0.0, 1.0, 0.0];
@@ -512,7 +512,7 @@ fn test_canvas_draw_triangles_3d() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_canvas_glyph_3d_and_hide_3d_grid() -> Result<(), StrError> {
+fn test_canvas_glyph_3d_and_hide_3d_grid() -> Result<(), Error> {
     let y = 0.5;
     const W: f64 = 2.0;
     const H: f64 = 1.0;
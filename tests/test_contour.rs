@@ -1,4 +1,4 @@
-use plotpy::{generate3d, Contour, Plot, StrError};
+use plotpy::{generate3d, Contour, Error, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_contour() -> Result<(), StrError> {
+fn test_contour() -> Result<(), Error> {
     // contour object and options
     let mut contour = Contour::new();
     contour
@@ -45,7 +45,7 @@ fn test_contour() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_contour_colors() -> Result<(), StrError> {
+fn test_contour_colors() -> Result<(), Error> {
     // contour object and options
     let mut contour = Contour::new();
     contour
@@ -78,7 +78,7 @@ fn test_contour_colors() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_contour_colormap_index() -> Result<(), StrError> {
+fn test_contour_colormap_index() -> Result<(), Error> {
     for index in 0..10 {
         // contour object and options
         let mut contour = Contour::new();
@@ -113,7 +113,7 @@ fn test_contour_colormap_index() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_contour_colormap_name() -> Result<(), StrError> {
+fn test_contour_colormap_name() -> Result<(), Error> {
     for name in ["Pastel1", "tab20c", "gnuplot2"] {
         // contour object and options
         let mut contour = Contour::new();
@@ -148,7 +148,7 @@ fn test_contour_colormap_name() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_contour_draw_tri() -> Result<(), StrError> {
+fn test_contour_draw_tri() -> Result<(), Error> {
     // point coordinates (two triangles in a square)
     let x = vec![0.0, 1.0, 2.0, 3.0, 0.5, 1.5, 2.5, 1.0, 2.0, 1.5];
     let y = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 3.0];
@@ -206,7 +206,7 @@ fn test_contour_draw_tri() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_contour_colorbar_options() -> Result<(), StrError> {
+fn test_contour_colorbar_options() -> Result<(), Error> {
     // data
     let n = 9;
     let (x, y, z) = generate3d(-2.0, 2.0, 0.0, 2.0, n, n, |x, y| x * x + y * y);
@@ -1,4 +1,4 @@
-use plotpy::{Curve, Plot, RayEndpoint, StrError};
+use plotpy::{Curve, Error, Plot, RayEndpoint};
 use std::f64::consts::PI;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -7,7 +7,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_curve() -> Result<(), StrError> {
+fn test_curve() -> Result<(), Error> {
     // curve object and options
     let mut curve1 = Curve::new();
     curve1
@@ -82,7 +82,7 @@ fn test_curve() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_curve_twinx() -> Result<(), StrError> {
+fn test_curve_twinx() -> Result<(), Error> {
     // data
     let np = 201;
     let mut x = vec![0.0; np];
@@ -123,7 +123,7 @@ fn test_curve_twinx() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_curve_points_methods_work() -> Result<(), StrError> {
+fn test_curve_points_methods_work() -> Result<(), Error> {
     // add points
     let mut curve = Curve::new();
     curve
@@ -150,7 +150,7 @@ fn test_curve_points_methods_work() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_curve_points_3d_methods_work() -> Result<(), StrError> {
+fn test_curve_points_3d_methods_work() -> Result<(), Error> {
     // add points
     let mut curve = Curve::new();
     curve
@@ -178,7 +178,7 @@ fn test_curve_points_3d_methods_work() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_curve_3d() -> Result<(), StrError> {
+fn test_curve_3d() -> Result<(), Error> {
     // curve object and options
     let mut curve = Curve::new();
     curve
@@ -1,4 +1,4 @@
-use plotpy::{Curve, DarkMode, Plot, StrError};
+use plotpy::{Curve, DarkMode, Error, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_dark_mode_default() -> Result<(), StrError> {
+fn test_dark_mode_default() -> Result<(), Error> {
     // curve
     let x = [1.0, 2.0, 3.0, 4.0];
     let y = [1.0, 4.0, 9.0, 16.0];
@@ -37,7 +37,7 @@ fn test_dark_mode_default() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_dark_mode_mathematica() -> Result<(), StrError> {
+fn test_dark_mode_mathematica() -> Result<(), Error> {
     // curve
     let x = [1.0, 2.0, 3.0, 4.0];
     let y = [1.0, 4.0, 9.0, 16.0];
@@ -69,7 +69,7 @@ fn test_dark_mode_mathematica() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_dark_mode_mocha() -> Result<(), StrError> {
+fn test_dark_mode_mocha() -> Result<(), Error> {
     // curve
     let x = [1.0, 2.0, 3.0, 4.0];
     let y = [1.0, 4.0, 9.0, 16.0];
@@ -101,7 +101,7 @@ fn test_dark_mode_mocha() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_dark_mode_nordic() -> Result<(), StrError> {
+fn test_dark_mode_nordic() -> Result<(), Error> {
     // curve
     let x = [1.0, 2.0, 3.0, 4.0];
     let y = [1.0, 4.0, 9.0, 16.0];
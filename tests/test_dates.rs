@@ -0,0 +1,47 @@
+use plotpy::{Barplot, Curve, Error, FillBetween, Plot};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_dates() -> Result<(), Error> {
+    // telemetry given by ISO-8601 strings and epoch seconds
+    let iso = [
+        "2024-03-15T08:00:00",
+        "2024-03-15T10:00:00",
+        "2024-03-15T12:00:00+01:00",
+        "2024-03-15T14:00:00Z",
+    ];
+    let epoch: Vec<i64> = (0..4).map(|i| 1710489600 + i * 7200).collect();
+    let mut curve = Curve::new();
+    curve.set_marker_style("o").draw_dates(&iso, &[12.5, 16.0, 18.0, 15.5]);
+    let mut fill = FillBetween::new();
+    fill.set_facecolor("#ccccff")
+        .draw_dates(&epoch, &[10.0, 13.0, 15.0, 12.0], None);
+    let mut bars = Barplot::new();
+    bars.set_width(0.5)
+        .draw_dates(&["2024-03-15", "2024-03-16", "2024-03-17"], &[3, 5, 2]);
+
+    // plot
+    let mut plot = Plot::new();
+    plot.set_time_zone("Europe/Berlin")
+        .set_subplot(2, 1, 1)
+        .add(&fill)
+        .add(&curve)
+        .set_xrange_dates("2024-03-15T06:00:00", "2024-03-15T18:00:00")
+        .set_subplot(2, 1, 2)
+        .add(&bars);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_dates.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 800);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use plotpy::{linspace, Curve, FillBetween, Plot, StrError};
+use plotpy::{linspace, Curve, Error, FillBetween, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_fill_between_1() -> Result<(), StrError> {
+fn test_fill_between_1() -> Result<(), Error> {
     // data
     let x = linspace(-1.0, 2.0, 21);
     let y1: Vec<_> = x.iter().map(|&x| x * x).collect();
@@ -34,7 +34,7 @@ fn test_fill_between_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_fill_between_2() -> Result<(), StrError> {
+fn test_fill_between_2() -> Result<(), Error> {
     // data
     let x = linspace(-1.0, 2.0, 21);
     let y1: Vec<_> = x.iter().map(|&x| x * x).collect();
@@ -64,7 +64,7 @@ fn test_fill_between_2() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_fill_between_3() -> Result<(), StrError> {
+fn test_fill_between_3() -> Result<(), Error> {
     // data and curve
     let x = linspace(-1.0, 2.0, 21);
     let y: Vec<_> = x.iter().map(|&x| x * x).collect();
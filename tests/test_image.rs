@@ -1,4 +1,4 @@
-use plotpy::{Image, Plot, StrError};
+use plotpy::{Error, Image, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_image_1() -> Result<(), StrError> {
+fn test_image_1() -> Result<(), Error> {
     // data
     let data = [
         [0.8, 2.4, 2.5, 3.9, 0.0, 4.0, 0.0],
@@ -39,7 +39,7 @@ fn test_image_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_image_with_rgb() -> Result<(), StrError> {
+fn test_image_with_rgb() -> Result<(), Error> {
     let data = vec![
         // --- Row 0 ---
         vec![
@@ -84,7 +84,7 @@ fn test_image_with_rgb() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_image_with_rgba() -> Result<(), StrError> {
+fn test_image_with_rgba() -> Result<(), Error> {
     let data = vec![
         // --- Row 0 ---
         vec![
@@ -1,4 +1,4 @@
-use plotpy::{generate3d, Barplot, Canvas, Contour, Curve, Histogram, Image, InsetAxes, Plot, StrError, Text};
+use plotpy::{generate3d, Barplot, Canvas, Contour, Curve, Error, Histogram, Image, InsetAxes, Plot, Text};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_inset_axes_1() -> Result<(), StrError> {
+fn test_inset_axes_1() -> Result<(), Error> {
     // draw image
     let data = [
         [0.8, 2.4, 2.5, 3.9, 0.0, 4.0, 0.0],
@@ -54,7 +54,7 @@ fn test_inset_axes_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_2() -> Result<(), StrError> {
+fn test_inset_axes_2() -> Result<(), Error> {
     // data
     let x = [0, 1, 2, 3, 4];
     let y = [5, 4, 3, 2, 1];
@@ -105,7 +105,7 @@ fn test_inset_axes_2() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_3() -> Result<(), StrError> {
+fn test_inset_axes_3() -> Result<(), Error> {
     // canvas
     let mut canvas = Canvas::new();
     canvas
@@ -144,7 +144,7 @@ fn test_inset_axes_3() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_4() -> Result<(), StrError> {
+fn test_inset_axes_4() -> Result<(), Error> {
     // curve
     let mut curve = Curve::new();
     let x = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
@@ -184,7 +184,7 @@ fn test_inset_axes_4() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_5() -> Result<(), StrError> {
+fn test_inset_axes_5() -> Result<(), Error> {
     // contour
     let mut contour = Contour::new();
     contour.set_colorbar_label("TEMPERATURE").set_number_format_cb("%.1f");
@@ -218,7 +218,7 @@ fn test_inset_axes_5() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_6() -> Result<(), StrError> {
+fn test_inset_axes_6() -> Result<(), Error> {
     // histogram
     let mut histogram = Histogram::new();
     let values = vec![
@@ -254,7 +254,7 @@ fn test_inset_axes_6() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_inset_axes_7() -> Result<(), StrError> {
+fn test_inset_axes_7() -> Result<(), Error> {
     // curve
     let mut curve = Curve::new();
     let x = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
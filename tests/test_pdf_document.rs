@@ -0,0 +1,39 @@
+use plotpy::{linspace, Curve, Error, PdfDocument, Plot};
+use std::fs;
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_pdf_document() -> Result<(), Error> {
+    // pages
+    let x = linspace(0.0, 1.0, 11);
+    let mut document = PdfDocument::new();
+    document
+        .set_title("Integration test")
+        .set_author("Plotpy")
+        .set_subject("Multi-page PDF")
+        .set_keywords("plotpy, pdf");
+    for i in 0..3 {
+        let y: Vec<_> = x.iter().map(|v| f64::powi(*v, i + 1)).collect();
+        let mut curve = Curve::new();
+        curve.draw(&x, &y);
+        let mut plot = Plot::new();
+        plot.add(&curve)
+            .set_title(&format!("page {}", i + 1))
+            .set_figure_size_inches(3.0 + i as f64, 3.0)
+            .set_save_transparent(i == 1);
+        document.add_page(&plot)?;
+    }
+
+    // save document
+    let path = Path::new(OUT_DIR).join("integ_pdf_document.pdf");
+    document.save(&path)?;
+
+    // check file
+    let data = fs::read(&path).map_err(|_| "cannot read file")?;
+    assert_eq!(&data[0..4], b"%PDF");
+    let text = String::from_utf8_lossy(&data);
+    assert_eq!(text.matches("/Type /Page ").count(), 3);
+    Ok(())
+}
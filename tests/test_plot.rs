@@ -1,4 +1,4 @@
-use plotpy::{linspace, Curve, Image, Plot, StrError, SuperTitleParams, Text};
+use plotpy::{linspace, Curve, Error, Image, Plot, SuperTitleParams, Text};
 use std::f64::consts::PI;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -7,7 +7,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_plot() -> Result<(), StrError> {
+fn test_plot() -> Result<(), Error> {
     // curve object and options
     let mut curve = Curve::new();
 
@@ -98,7 +98,7 @@ fn gen_curve_3d() -> Curve {
 }
 
 #[test]
-fn test_plot_3d() -> Result<(), StrError> {
+fn test_plot_3d() -> Result<(), Error> {
     // curve
     let curve = gen_curve_3d();
 
@@ -163,11 +163,17 @@ fn test_plot_3d() -> Result<(), StrError> {
 fn test_plot_error() {
     let plot = Plot::new();
     let path = Path::new(OUT_DIR).join("integ_plot_error.xyz");
-    assert_eq!(plot.save(&path).err(), Some("python3 failed; please see the log file"));
+    match plot.save(&path).err() {
+        Some(Error::Python { kind, message, .. }) => {
+            assert_eq!(kind, "ValueError");
+            assert!(message.contains("xyz"));
+        }
+        _ => panic!("Python error expected"),
+    }
 }
 
 #[test]
-fn test_plot_handles_quotes() -> Result<(), StrError> {
+fn test_plot_handles_quotes() -> Result<(), Error> {
     let mut plot = Plot::new();
     plot.set_title("\"$\\int$ \"The Plot of the Developer\" $versus$ \"Developer's Plot\" $\\mathrm{d}\\sigma$\"");
 
@@ -186,7 +192,7 @@ fn test_plot_handles_quotes() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_title_handles_tex() -> Result<(), StrError> {
+fn test_plot_title_handles_tex() -> Result<(), Error> {
     let mut plot = Plot::new();
     plot.set_title("Van der Pol ($\\varepsilon = 10^{-6}$) - Radau5 - Tol = 1e-4");
 
@@ -205,7 +211,7 @@ fn test_plot_title_handles_tex() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_subplots() -> Result<(), StrError> {
+fn test_plot_subplots() -> Result<(), Error> {
     // curve object and options
     let mut curve = Curve::new();
 
@@ -244,7 +250,7 @@ fn test_plot_subplots() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_log() -> Result<(), StrError> {
+fn test_plot_log() -> Result<(), Error> {
     // curves
     let mut curve1 = Curve::new();
     let mut curve2 = Curve::new();
@@ -296,7 +302,7 @@ fn test_plot_log() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_multiple_of_pi() -> Result<(), StrError> {
+fn test_plot_multiple_of_pi() -> Result<(), Error> {
     // configure curve
     let mut cos_curve = Curve::new();
     let mut sin_curve = Curve::new();
@@ -342,7 +348,7 @@ fn test_plot_multiple_of_pi() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_extra_functionality() -> Result<(), StrError> {
+fn test_plot_extra_functionality() -> Result<(), Error> {
     // plot
     let mut plot = Plot::new();
     plot.set_horiz_line(-0.5, "green", "-", 1.0)
@@ -364,7 +370,7 @@ fn test_plot_extra_functionality() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_tick_labels() -> Result<(), StrError> {
+fn test_plot_tick_labels() -> Result<(), Error> {
     // data
     let vegetables = [
         "cucumber",
@@ -430,7 +436,7 @@ fn test_plot_tick_labels() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_fontsize_and_color_2d() -> Result<(), StrError> {
+fn test_plot_fontsize_and_color_2d() -> Result<(), Error> {
     // curve
     let x = [1.0, 2.0, 3.0, 4.0];
     let y = [1.0, 4.0, 9.0, 16.0];
@@ -465,7 +471,7 @@ fn test_plot_fontsize_and_color_2d() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_plot_fontsize_and_color_3d() -> Result<(), StrError> {
+fn test_plot_fontsize_and_color_3d() -> Result<(), Error> {
     // curve
     let curve = gen_curve_3d();
 
@@ -0,0 +1,66 @@
+use plotpy::{generate2d, linspace, Canvas, Contour, Curve, Error, FillBetween, Plot, Text, ZeroLocation};
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_polar() -> Result<(), Error> {
+    // antenna pattern
+    let theta = linspace(0.0, 2.0 * PI, 201);
+    let r: Vec<_> = theta.iter().map(|t| 1.0 + f64::abs(f64::cos(2.0 * t))).collect();
+    let zero = vec![0.0; theta.len()];
+    let mut curve = Curve::new();
+    curve.set_line_color("red").draw(&theta, &r);
+    let mut fill = FillBetween::new();
+    fill.set_facecolor("#ffaaaa").draw(&theta, &zero, Some(&r));
+
+    // angular distribution
+    let (tt, rr) = generate2d(0.0, 2.0 * PI, 0.1, 2.0, 21, 11);
+    let mut zz = vec![vec![0.0; tt[0].len()]; tt.len()];
+    for i in 0..tt.len() {
+        for j in 0..tt[i].len() {
+            zz[i][j] = rr[i][j] * f64::sin(tt[i][j]);
+        }
+    }
+    let mut contour = Contour::new();
+    contour.draw(&tt, &rr, &zz);
+
+    // text and canvas
+    let mut text = Text::new();
+    text.draw(PI / 4.0, 1.5, "main lobe");
+    let mut canvas = Canvas::new();
+    canvas.draw_polyline(&[[0.0, 0.5], [PI / 2.0, 0.5], [PI, 0.5]], false);
+    canvas.draw_grid(&[0.0, 0.0], &[PI, 1.0], &[4, 2], false, false)?;
+
+    // plot
+    let mut plot = Plot::new();
+    let left = plot.set_subplot_polar(1, 2, 1);
+    plot.add_to(left, &fill)
+        .add(&curve)
+        .add(&text)
+        .set_polar_zero_location(ZeroLocation::N, 0.0)
+        .set_polar_clockwise(true)
+        .set_polar_rrange(0.0, 2.5)
+        .set_polar_ticks_degrees(30.0);
+    let right = plot.set_subplot_polar(1, 2, 2);
+    plot.add_to(right, &contour)
+        .add(&canvas)
+        .set_polar_theta_range(0.0, 270.0)
+        .set_polar_rrange(0.1, 2.0)
+        .set_polar_log_r(true)
+        .set_polar_ticks_multiple_of_pi(4);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_polar.svg");
+    plot.set_figure_size_points(600.0, 300.0).save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 800);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use plotpy::{linspace, AxisTransform, Curve, Error, Plot, Scale, SlopeIcon};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_scale() -> Result<(), Error> {
+    // errors crossing zero
+    let x = linspace(-10.0, 10.0, 41);
+    let err: Vec<_> = x.iter().map(|v| v * v * v).collect();
+    let mut curve1 = Curve::new();
+    curve1.draw(&x, &err);
+
+    // probabilities
+    let p = linspace(0.01, 0.99, 50);
+    let q: Vec<_> = p.iter().map(|v| v * v).collect();
+    let mut curve2 = Curve::new();
+    curve2.draw(&p, &q);
+
+    // power law on log2-log2 axes
+    let x3 = linspace(1.0, 64.0, 64);
+    let y3: Vec<_> = x3.iter().map(|v| v * v).collect();
+    let mut curve3 = Curve::new();
+    curve3.draw(&x3, &y3);
+    let mut icon = SlopeIcon::new();
+    icon.draw(2.0, 8.0, 64.0);
+
+    // asinh and function scales
+    let mut curve4 = Curve::new();
+    curve4.draw(&x, &err);
+
+    // plot
+    let mut plot = Plot::new();
+    plot.set_subplot(2, 2, 1)
+        .set_scale_y(Scale::SymLog {
+            base: 10.0,
+            linthresh: 1.0,
+            linscale: 1.0,
+        })?
+        .add(&curve1)
+        .grid_and_labels("x", "symlog");
+    plot.set_subplot(2, 2, 2)
+        .add(&curve2)
+        .set_scale_x(Scale::Logit)?
+        .set_scale_y(Scale::Logit)?
+        .grid_and_labels("logit", "logit");
+    plot.set_subplot(2, 2, 3)
+        .add(&curve3)
+        .set_scale_x(Scale::Log { base: 2.0 })?
+        .set_scale_y(Scale::Log { base: 2.0 })?
+        .add(&icon)
+        .grid_and_labels("log2", "log2");
+    plot.set_subplot(2, 2, 4)
+        .add(&curve4)
+        .set_scale_x(Scale::Function(AxisTransform::Linear {
+            scale: 2.0,
+            offset: 1.0,
+        }))?
+        .set_scale_y(Scale::Asinh { linear_width: 1.0 })?
+        .grid_and_labels("function", "asinh");
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_scale.svg");
+    plot.set_figure_size_points(600.0, 600.0).save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 1000);
+    Ok(())
+}
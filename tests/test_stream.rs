@@ -1,4 +1,4 @@
-use plotpy::{generate2d, linspace, Plot, StrError, Stream};
+use plotpy::{generate2d, linspace, Error, Plot, Stream};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_stream_arrows_1() -> Result<(), StrError> {
+fn test_stream_arrows_1() -> Result<(), Error> {
     // object and options
     let mut stream = Stream::new();
     let mut quiver = Stream::new();
@@ -56,7 +56,7 @@ fn test_stream_arrows_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_stream_arrows_2() -> Result<(), StrError> {
+fn test_stream_arrows_2() -> Result<(), Error> {
     // object and options
     let mut stream = Stream::new();
     let mut quiver = Stream::new();
@@ -0,0 +1,43 @@
+use plotpy::{Curve, Error, Plot, Style};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_style() -> Result<(), Error> {
+    // curves
+    let x = [1.0, 2.0, 3.0, 4.0];
+    let mut curve1 = Curve::new();
+    let mut curve2 = Curve::new();
+    curve1.set_label("squared").draw(&x, &[1.0, 4.0, 9.0, 16.0]);
+    curve2.set_label("linear").draw(&x, &[1.0, 2.0, 3.0, 4.0]);
+
+    // style
+    let mut style = Style::mocha();
+    style
+        .set_font_family("serif")
+        .set_font_size(11.0)
+        .set_line_width(2.5)
+        .set_marker_style("o")
+        .set_tick_direction("in")
+        .set_grid(true)
+        .set_grid_line_style("--")
+        .set_color_cycle(&["#f38ba8", "#a6e3a1"]);
+
+    // plot
+    let mut plot = Plot::new();
+    plot.add(&curve1).add(&curve2).set_style(&style);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_style.svg");
+    plot.legend().set_labels("x", "y").set_show_errors(true).save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 500);
+    Ok(())
+}
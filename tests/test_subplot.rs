@@ -1,4 +1,4 @@
-use plotpy::{Curve, Plot, StrError};
+use plotpy::{Curve, Error, GridLayout, Plot};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_subplot() -> Result<(), StrError> {
+fn test_subplot() -> Result<(), Error> {
     // curves
     let mut curve1 = Curve::new();
     let mut curve2 = Curve::new();
@@ -51,7 +51,7 @@ fn test_subplot() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_gridspec_1() -> Result<(), StrError> {
+fn test_gridspec_1() -> Result<(), Error> {
     // curves
     let mut curve1 = Curve::new();
     let mut curve2 = Curve::new();
@@ -84,7 +84,7 @@ fn test_gridspec_1() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_gridspec_rotation_and_align_labels() -> Result<(), StrError> {
+fn test_gridspec_rotation_and_align_labels() -> Result<(), Error> {
     // curves
     let mut curve = Curve::new();
     let x = &[1000.0, 2000.0, 3000.0, 4000.0];
@@ -123,3 +123,104 @@ fn test_gridspec_rotation_and_align_labels() -> Result<(), StrError> {
     assert!(n < 960);
     Ok(())
 }
+
+#[test]
+fn test_subplots_axes() -> Result<(), Error> {
+    // curves
+    let mut curve1 = Curve::new();
+    let mut curve2 = Curve::new();
+    let x = &[1.0, 2.0, 3.0, 4.0];
+    let y = &[1.0, 1.424, 1.732, 2.0];
+    let z = &[1.0, 4.0, 9.0, 16.0];
+    curve1.draw(x, y);
+    curve2.draw(x, z);
+
+    // configure the axes in any order
+    let mut plot = Plot::new();
+    let axes = plot.subplots(2, 2);
+    plot.add_to(axes[3], &curve2).set_title("fourth");
+    plot.add_to(axes[0], &curve1).set_title("first");
+    plot.set_axes(axes[3])
+        .set_range(0.0, 5.0, 0.0, 20.0)
+        .set_labels("x", "z");
+    plot.set_axes(axes[0]).set_labels("x", "y");
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_subplots_axes.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 600);
+    Ok(())
+}
+
+#[test]
+fn test_grid_layout() -> Result<(), Error> {
+    // curves
+    let mut curve1 = Curve::new();
+    let mut curve2 = Curve::new();
+    let x = &[1.0, 2.0, 3.0, 4.0];
+    let y = &[1.0, 1.424, 1.732, 2.0];
+    let z = &[1.0, 4.0, 9.0, 16.0];
+    curve1.draw(x, y);
+    curve2.draw(x, z);
+
+    // typed grid layout
+    let mut layout = GridLayout::new(2, 3);
+    layout
+        .set_width_ratios(&[1.0, 1.0, 2.0])
+        .set_height_ratios(&[2.0, 1.0])
+        .set_wspace(0.3)
+        .set_hspace(0.4);
+    let mut plot = Plot::new();
+    let wide = plot.grid_axes(&layout, 0..1, 0..2)?;
+    let tall = plot.grid_axes(&layout, .., 2..)?;
+    plot.add_to(tall, &curve2).set_title("tall");
+    plot.add_to(wide, &curve1).set_title("wide");
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_grid_layout.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 600);
+    Ok(())
+}
+
+#[test]
+fn test_subplot_mosaic() -> Result<(), Error> {
+    // curves
+    let mut curve1 = Curve::new();
+    let mut curve2 = Curve::new();
+    let x = &[1.0, 2.0, 3.0, 4.0];
+    let y = &[1.0, 1.424, 1.732, 2.0];
+    let z = &[1.0, 4.0, 9.0, 16.0];
+    curve1.draw(x, y);
+    curve2.draw(x, z);
+
+    // mosaic
+    let layout = GridLayout::from_mosaic("AAB;CDB")?;
+    let mut plot = Plot::new();
+    let axes = plot.subplot_mosaic(&layout)?;
+    for (name, ax) in &axes {
+        plot.set_axes(*ax).set_title(&format!("panel {}", name));
+    }
+    plot.add_to(axes[&'A'], &curve1).add_to(axes[&'B'], &curve2);
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_subplot_mosaic.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 600);
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use plotpy::{generate3d, Plot, StrError, Surface};
+use plotpy::{generate3d, Error, Plot, Surface};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_subplot_3d() -> Result<(), StrError> {
+fn test_subplot_3d() -> Result<(), Error> {
     // data
     let n = 9;
     let (x, y, z) = generate3d(-2.0, 2.0, -2.0, 2.0, n, n, |x, y| x * x + y * y);
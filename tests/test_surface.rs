@@ -1,4 +1,4 @@
-use plotpy::{generate3d, Plot, StrError, Surface};
+use plotpy::{generate3d, Error, Plot, Surface};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_surface() -> Result<(), StrError> {
+fn test_surface() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface
         .set_row_stride(1)
@@ -43,7 +43,7 @@ fn test_surface() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_color() -> Result<(), StrError> {
+fn test_surface_color() -> Result<(), Error> {
     let mut surface = Surface::new();
 
     // draw surface
@@ -69,7 +69,7 @@ fn test_surface_color() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_lines() -> Result<(), StrError> {
+fn test_surface_lines() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface
         .set_surf_line_color("limegreen")
@@ -99,7 +99,7 @@ fn test_surface_lines() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_wireframe() -> Result<(), StrError> {
+fn test_surface_wireframe() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface.set_with_surface(false).set_with_wireframe(true);
 
@@ -125,7 +125,7 @@ fn test_surface_wireframe() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_points() -> Result<(), StrError> {
+fn test_surface_points() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface.set_with_surface(false).set_with_points(true);
 
@@ -152,7 +152,7 @@ fn test_surface_points() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_points_color() -> Result<(), StrError> {
+fn test_surface_points_color() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface.set_with_surface(false).set_with_points(true);
 
@@ -184,7 +184,7 @@ fn test_surface_points_color() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_points_void() -> Result<(), StrError> {
+fn test_surface_points_void() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface.set_with_surface(false).set_with_points(true);
 
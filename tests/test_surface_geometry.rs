@@ -1,4 +1,4 @@
-use plotpy::{Plot, StrError, Surface};
+use plotpy::{Error, Plot, Surface};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_surface_geometry() -> Result<(), StrError> {
+fn test_surface_geometry() -> Result<(), Error> {
     // plane
     let mut plane = Surface::new();
     plane.set_colormap_name("terrain").draw_plane_nzz(
@@ -47,7 +47,7 @@ fn test_surface_geometry() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_cylinder() -> Result<(), StrError> {
+fn test_surface_cylinder() -> Result<(), Error> {
     let mut surface = Surface::new();
     surface.set_surf_color("red");
     surface.draw_cylinder(&[0.0, 0.0, 0.0], &[5.0, 0.0, 0.0], 0.5, 1, 20)?;
@@ -79,7 +79,7 @@ fn test_surface_cylinder() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_surface_superquadric() -> Result<(), StrError> {
+fn test_surface_superquadric() -> Result<(), Error> {
     // star
     let r = &[1.0, 1.0, 1.0];
     let c = &[-1.0, -1.0, -1.0];
@@ -0,0 +1,25 @@
+#![cfg(feature = "testing")]
+
+use plotpy::testing::assert_golden;
+use plotpy::{Curve, GraphMaker, Plot};
+use std::path::Path;
+
+const GOLDEN_DIR: &str = "tests/golden";
+
+#[test]
+fn test_testing_golden_buffer() {
+    let mut curve = Curve::new();
+    curve.set_line_color("#cd0000").set_marker_style("o");
+    curve.draw(&[1.0, 2.0, 3.0], &[1.0, 4.0, 9.0]);
+    assert_golden(curve.get_buffer(), Path::new(GOLDEN_DIR).join("curve.py"));
+}
+
+#[test]
+fn test_testing_golden_plot() {
+    let mut curve = Curve::new();
+    curve.draw(&[1.0, 2.0, 3.0], &[1.0, 4.0, 9.0]);
+    let mut plot = Plot::new();
+    plot.add(&curve).set_title("golden").grid_and_labels("x", "y");
+    let script = plot.to_python_string("/tmp/plotpy/integ_tests/golden_plot.svg");
+    assert_golden(&script, Path::new(GOLDEN_DIR).join("plot.py"));
+}
@@ -1,4 +1,4 @@
-use plotpy::{Plot, StrError, Text};
+use plotpy::{Error, Plot, Text};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
@@ -6,7 +6,7 @@ use std::path::Path;
 const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
 
 #[test]
-fn test_text() -> Result<(), StrError> {
+fn test_text() -> Result<(), Error> {
     // text object and options
     let mut text = Text::new();
     text.set_color("blue")
@@ -49,7 +49,7 @@ fn test_text() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_text_negative_rotation() -> Result<(), StrError> {
+fn test_text_negative_rotation() -> Result<(), Error> {
     // text object and options
     let mut text = Text::new();
 
@@ -78,7 +78,7 @@ fn test_text_negative_rotation() -> Result<(), StrError> {
 }
 
 #[test]
-fn test_text_3d() -> Result<(), StrError> {
+fn test_text_3d() -> Result<(), Error> {
     // text object and options
     let mut text = Text::new();
     text.set_color("blue")
@@ -0,0 +1,98 @@
+use plotpy::{generate3d, linspace, Contour, Curve, Error, Formatter, Locator, Plot, Scale, Surface};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_ticks() -> Result<(), Error> {
+    // frequency response
+    let f = linspace(1e3, 1e7, 50);
+    let gain: Vec<_> = f.iter().map(|v| 1.0 / (1.0 + v / 1e5)).collect();
+    let mut curve1 = Curve::new();
+    curve1.draw(&f, &gain);
+
+    // small signal
+    let t = linspace(0.0, 2e-6, 21);
+    let v: Vec<_> = t.iter().map(|s| 12345.0 + s * 1e6).collect();
+    let mut curve2 = Curve::new();
+    curve2.draw(&t, &v);
+
+    // contour with colorbar
+    let (x, y, z) = generate3d(-1.0, 1.0, -1.0, 1.0, 11, 11, |x, y| x * x + y * y);
+    let mut contour = Contour::new();
+    contour
+        .set_colorbar_locator(&Locator::Fixed(vec![0.0, 0.5, 1.0, 1.5, 2.0]))?
+        .set_colorbar_formatter(&Formatter::from_fn(&[0.0, 0.5, 1.0, 1.5, 2.0], |v| {
+            format!("{:.0}%", v * 50.0)
+        }))?
+        .draw(&x, &y, &z);
+
+    // surface with colorbar
+    let mut surface = Surface::new();
+    surface
+        .set_with_colorbar(true)
+        .set_colorbar_formatter(&Formatter::Decimals(2))?
+        .draw(&x, &y, &z);
+
+    // plot
+    let mut plot = Plot::new();
+    plot.set_subplot(2, 2, 1)
+        .add(&curve1)
+        .set_scale_x(Scale::Log { base: 10.0 })?
+        .set_locator_x(&Locator::Log {
+            base: 10.0,
+            subs: vec![1.0],
+        })?
+        .set_minor_locator_x(&Locator::Log {
+            base: 10.0,
+            subs: vec![2.0, 5.0],
+        })?
+        .set_formatter_x(&Formatter::Engineering {
+            unit: "Hz".to_string(),
+            places: Some(0),
+        })?
+        .set_formatter_y(&Formatter::Percent {
+            xmax: 1.0,
+            decimals: Some(0),
+        })?;
+    plot.set_subplot(2, 2, 2)
+        .add(&curve2)
+        .set_formatter_x(&Formatter::Engineering {
+            unit: "s".to_string(),
+            places: None,
+        })?
+        .set_locator_y(&Locator::MaxN {
+            nbins: 4,
+            integer: false,
+        })?
+        .set_formatter_y(&Formatter::Scientific {
+            use_offset: true,
+            power_limits: (-2, 2),
+            math_text: true,
+        })?;
+    plot.set_subplot(2, 2, 3)
+        .add(&contour)
+        .set_locator_x(&Locator::Multiple { base: 0.5, offset: 0.0 })?
+        .set_minor_locator_x(&Locator::AutoMinor { ndivs: 0 })?
+        .set_ticks_y_fn(&[-1.0, 0.0, 1.0], |v| format!("y={}", v));
+    plot.set_subplot_3d(2, 2, 4)
+        .add(&surface)
+        .set_locator_z(&Locator::MaxN {
+            nbins: 3,
+            integer: true,
+        })?
+        .set_ticks_x_fn(&[-1.0, 1.0], |v| if v < 0.0 { "west".into() } else { "east".into() });
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_ticks.svg");
+    plot.set_figure_size_points(600.0, 600.0).save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 1000);
+    Ok(())
+}
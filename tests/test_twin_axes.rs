@@ -0,0 +1,70 @@
+use plotpy::{AxisTransform, Curve, Error, Plot};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+const OUT_DIR: &str = "/tmp/plotpy/integ_tests";
+
+#[test]
+fn test_twin_axes() -> Result<(), Error> {
+    // curves
+    let mut curve1 = Curve::new();
+    let mut curve2 = Curve::new();
+    let mut curve3 = Curve::new();
+    let x = &[1.0, 2.0, 3.0, 4.0];
+    curve1.draw(x, &[1.0, 1.424, 1.732, 2.0]);
+    curve2.set_line_color("red").draw(x, &[1.0, 10.0, 100.0, 1000.0]);
+    curve3
+        .set_line_color("green")
+        .draw(&[10.0, 20.0, 30.0, 40.0], &[1.0, 1.5, 1.7, 2.0]);
+
+    // plot with twin axes
+    let mut plot = Plot::new();
+    let main = plot.current_axes();
+    plot.add(&curve1).set_label_y("sqrt");
+    let right = plot.twin_x();
+    plot.add_to(right, &curve2)
+        .set_log_y(true)
+        .set_label_y("power")
+        .set_label_y_color("red");
+    plot.set_axes(main);
+    let top = plot.twin_y();
+    plot.add_to(top, &curve3).set_xrange(0.0, 50.0).set_label_x("scaled");
+    plot.set_axes(main).set_label_x("x");
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_twin_axes.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 800);
+    Ok(())
+}
+
+#[test]
+fn test_secondary_axes() -> Result<(), Error> {
+    // curve
+    let mut curve = Curve::new();
+    curve.draw(&[0.5, 1.0, 1.5, 2.0], &[0.0, 20.0, 40.0, 100.0]);
+
+    // plot with secondary axes
+    let mut plot = Plot::new();
+    plot.add(&curve)
+        .set_labels("wavelength [μm]", "temperature [°C]")
+        .set_secondary_x_axis(AxisTransform::Reciprocal(299.792458), "frequency [THz]")?
+        .set_secondary_y_axis(AxisTransform::CelsiusToKelvin, "temperature [K]")?;
+
+    // save figure
+    let path = Path::new(OUT_DIR).join("integ_secondary_axes.svg");
+    plot.save(&path)?;
+
+    // check number of lines
+    let file = File::open(path).map_err(|_| "cannot open file")?;
+    let buffered = BufReader::new(file);
+    let lines_iter = buffered.lines();
+    assert!(lines_iter.count() > 600);
+    Ok(())
+}